@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSettings {
+    #[serde(default = "default_keep")]
+    pub keep: usize, // 유지할 .bak 세대 수 (0이면 백업하지 않음)
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self { keep: default_keep() }
+    }
+}
+
+fn default_keep() -> usize {
+    5
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub path: String,
+    pub generation: usize, // 1이 가장 최근
+    pub modified: Option<u64>, // UNIX 초
+    pub size: u64,
+}
+
+// 기존 파일을 백업 세대로 돌린 뒤 원자적으로 저장
+pub fn save(path: &Path, content: &str, keep: usize) -> Result<(), String> {
+    rotate(path, keep)?;
+    write_atomic(path, content)
+}
+
+// 같은 폴더의 임시 파일에 쓰고 fsync 후 rename (중간에 실패해도 기존 파일은 그대로)
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let dir = parent_dir(path);
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("잘못된 파일 경로입니다: '{}'", path.display()))?;
+    let tmp = dir.join(format!(".{}.tmp", name.to_string_lossy()));
+
+    let written = (|| -> io::Result<()> {
+        let mut file = File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(format!("파일 저장 실패 '{}': {}", path.display(), e));
+    }
+
+    sync_dir(dir);
+    Ok(())
+}
+
+// 파일 옆의 백업 목록 (최근 세대부터)
+pub fn list(path: &Path) -> Result<Vec<BackupInfo>, String> {
+    let dir = parent_dir(path);
+    let Some(name) = path.file_name() else {
+        return Ok(Vec::new());
+    };
+    let prefix = format!("{}.bak.", name.to_string_lossy());
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", dir.display(), e))?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(generation) = file_name.strip_prefix(&prefix).and_then(|n| n.parse::<usize>().ok()) else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if generation == 0 || !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        backups.push(BackupInfo {
+            path: entry.path().to_string_lossy().to_string(),
+            generation,
+            modified,
+            size: metadata.len(),
+        });
+    }
+    backups.sort_by_key(|b| b.generation);
+    Ok(backups)
+}
+
+// 백업 세대의 내용 (복원 전에 읽어 두어야 회전으로 번호가 밀려도 안전)
+pub fn read(path: &Path, generation: usize) -> Result<String, String> {
+    let backup = backup_path(path, generation);
+    if !backup.is_file() {
+        return Err(format!("백업 {}세대가 없습니다: '{}'", generation, backup.display()));
+    }
+    fs::read_to_string(&backup).map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", backup.display(), e))
+}
+
+// .bak.N을 .bak.N+1로 밀고 현재 파일을 .bak.1로 복사. keep 이상 세대는 삭제
+fn rotate(path: &Path, keep: usize) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    for backup in list(path)?.iter().rev() {
+        let from = Path::new(&backup.path);
+        if backup.generation >= keep {
+            fs::remove_file(from).map_err(|e| format!("백업 삭제 실패 '{}': {}", from.display(), e))?;
+        } else {
+            let to = backup_path(path, backup.generation + 1);
+            fs::rename(from, &to).map_err(|e| format!("백업 이동 실패 '{}': {}", from.display(), e))?;
+        }
+    }
+    if keep > 0 {
+        let to = backup_path(path, 1);
+        fs::copy(path, &to).map_err(|e| format!("백업 생성 실패 '{}': {}", to.display(), e))?;
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".bak.{}", generation));
+    path.with_file_name(name)
+}
+
+// "diagram.json"처럼 폴더가 없는 상대 경로는 현재 폴더
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+// rename이 디스크에 남도록 폴더도 fsync (지원하지 않는 플랫폼은 무시)
+#[cfg(unix)]
+fn sync_dir(dir: &Path) {
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn rotation_keeps_the_newest_generations() {
+        let dir = temp_dir("backup_rotate");
+        let path = dir.join("d.json");
+        for i in 0..6 {
+            save(&path, &format!("v{}", i), 3).unwrap();
+        }
+        let generations: Vec<usize> = list(&path).unwrap().iter().map(|b| b.generation).collect();
+        let contents = (read(&path, 1).unwrap(), read(&path, 3).unwrap());
+        let current = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(current, "v5");
+        assert_eq!(generations, [1, 2, 3]);
+        assert_eq!(contents, ("v4".to_string(), "v2".to_string()));
+    }
+
+    #[test]
+    fn lowering_keep_drops_old_generations() {
+        let dir = temp_dir("backup_shrink");
+        let path = dir.join("d.json");
+        for i in 0..4 {
+            save(&path, &format!("v{}", i), 3).unwrap();
+        }
+        save(&path, "x", 1).unwrap();
+        let after_one = list(&path).unwrap().len();
+        save(&path, "y", 0).unwrap();
+        let after_zero = list(&path).unwrap().len();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!((after_one, after_zero), (1, 0));
+    }
+
+    #[test]
+    fn failed_write_leaves_no_temp_file() {
+        let dir = temp_dir("backup_fail");
+        assert!(write_atomic(&dir.join("missing").join("d.json"), "x").is_err());
+        write_atomic(&dir.join("d.json"), "x").unwrap();
+        let names: Vec<String> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().to_string()).collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(names, ["d.json"]);
+    }
+}
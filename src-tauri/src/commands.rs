@@ -1,7 +1,25 @@
 use tauri::command;
-use crate::erd::ErdDiagram;
+use crate::erd::{Entity, ErdDiagram, NormalizeOptions, Relation, SubtypeHierarchy, UserType};
+use crate::sql::SqlDialect;
+use crate::sql_import::{self, SqlImportResult};
+use crate::introspect;
+use crate::diff::{self, DiagramDiff};
+use crate::migration::{self, Migration};
+use crate::lint::{self, Diagnostic, Rule};
+use crate::naming::{self, NamingFixResult, NamingPolicy};
+use crate::dictionary::{self, DictionaryImportResult, NameStrategy, TermApplyResult, TermDictionary};
+use crate::user_types;
+use crate::junction;
+use crate::relations;
+use crate::hierarchy::{self, PhysicalModel};
+use crate::views::{self, ViewRefreshResult};
+use crate::file_format::{self, ParsedDiagram};
+use crate::backup::{self, BackupInfo, BackupSettings};
+use crate::recovery::{self, RecoverySession};
 use serde::{Serialize, Deserialize};
 use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc;
@@ -10,17 +28,21 @@ use std::sync::mpsc;
 pub struct LoadResult {
     pub diagram: ErdDiagram,
     pub file_path: String,
+    #[serde(default)]
+    pub migrations: Vec<String>, // 이전 형식 파일을 현재 형식으로 올린 단계
+    #[serde(default)]
+    pub warnings: Vec<String>, // 이전 형식의 관계 속성 참조 중 찾을 수 없는 것
 }
 
 #[command]
-pub async fn save_diagram_to_path(diagram: ErdDiagram, file_path: String) -> Result<String, String> {
+pub async fn save_diagram_to_path(app: tauri::AppHandle, diagram: ErdDiagram, file_path: String) -> Result<String, String> {
     println!("save_diagram_to_path 명령어 호출됨: {}", file_path);
     
-    let json_data = serde_json::to_string_pretty(&diagram)
-        .map_err(|e| format!("Failed to serialize diagram: {}", e))?;
+    let json_data = file_format::to_json(&diagram)?;
     
-    fs::write(&file_path, json_data)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+    // 기존 파일은 .bak 세대로 남기고 임시 파일 → rename으로 교체
+    backup::save(Path::new(&file_path), &json_data, backup_keep(&app)?)?;
+    discard_recovery(&app, &file_path);
     
     Ok(file_path)
 }
@@ -51,13 +73,13 @@ pub async fn save_diagram_to_file(app: tauri::AppHandle, diagram: ErdDiagram) ->
         Some(path) => {
             println!("저장 경로 선택됨: {:?}", path);
             let path_buf = path.as_path().unwrap();
-            let json_data = serde_json::to_string_pretty(&diagram)
-                .map_err(|e| format!("Failed to serialize diagram: {}", e))?;
+            let json_data = file_format::to_json(&diagram)?;
             
-            fs::write(path_buf, json_data)
-                .map_err(|e| format!("Failed to write file: {}", e))?;
+            backup::save(path_buf, &json_data, backup_keep(&app)?)?;
             
-            Ok(path_buf.to_string_lossy().to_string())
+            let saved_path = path_buf.to_string_lossy().to_string();
+            discard_recovery(&app, &saved_path);
+            Ok(saved_path)
         }
         None => {
             println!("저장 취소됨");
@@ -66,6 +88,12 @@ pub async fn save_diagram_to_file(app: tauri::AppHandle, diagram: ErdDiagram) ->
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct DiffResult {
+    pub diff: DiagramDiff,
+    pub markdown: String,
+}
+
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
 #[command]
@@ -98,53 +126,10 @@ pub async fn load_diagram_from_file(app: tauri::AppHandle) -> Result<LoadResult,
             println!("파일 경로 선택됨: {:?}", path);
             let path_buf = path.as_path().unwrap();
             
-            // 파일 크기 검사
-            let metadata = fs::metadata(path_buf)
-                .map_err(|e| format!("파일 정보를 읽을 수 없습니다: {}", e))?;
-            
-            if metadata.len() > MAX_FILE_SIZE {
-                return Err(format!("파일이 너무 큽니다. 최대 {}MB까지 지원합니다.", MAX_FILE_SIZE / (1024 * 1024)));
-            }
-            
-            // 파일 타입 검증
-            if let Some(extension) = path_buf.extension() {
-                if extension.to_string_lossy().to_lowercase() != "json" {
-                    return Err("JSON 파일만 지원합니다.".to_string());
-                }
-            } else {
-                return Err("파일 확장자가 필요합니다. JSON 파일을 선택해주세요.".to_string());
-            }
-            
-            // 파일 읽기
-            let file_content = fs::read_to_string(path_buf)
-                .map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", path_buf.display(), e))?;
-            
-            // 기본 검증
-            if file_content.trim().is_empty() {
-                return Err("파일이 비어있습니다.".to_string());
-            }
-            
-            // JSON 파싱
-            let mut diagram: ErdDiagram = serde_json::from_str(&file_content)
-                .map_err(|e| {
-                    if e.to_string().contains("recursion limit") {
-                        "JSON 구조가 너무 복잡합니다.".to_string()
-                    } else {
-                        format!("다이어그램 파일을 파싱할 수 없습니다: {}", e)
-                    }
-                })?;
-            
-            // 스키마 변화에 대한 후처리 (기본값/필수 필드 보정)
-            diagram.normalize();
-            
-            // 기본 다이어그램 검증
-            validate_basic_diagram(&diagram)?;
+            let result = read_diagram_file(path_buf)?;
             
             println!("다이어그램 로드 성공");
-            Ok(LoadResult {
-                diagram,
-                file_path: path_buf.to_string_lossy().to_string(),
-            })
+            Ok(result)
         }
         None => {
             println!("파일 선택 취소됨");
@@ -153,17 +138,105 @@ pub async fn load_diagram_from_file(app: tauri::AppHandle) -> Result<LoadResult,
     }
 }
 
-fn validate_basic_diagram(diagram: &ErdDiagram) -> Result<(), String> {
-    // 기본적인 검증만 수행 (빠른 처리를 위해)
-    if diagram.entities.len() > 1000 {
-        return Err("엔티티가 너무 많습니다.".to_string());
+fn read_diagram_file(path_buf: &Path) -> Result<LoadResult, String> {
+    // 파일 크기 검사
+    let metadata = fs::metadata(path_buf)
+        .map_err(|e| format!("파일 정보를 읽을 수 없습니다: {}", e))?;
+    
+    if metadata.len() > MAX_FILE_SIZE {
+        return Err(format!("파일이 너무 큽니다. 최대 {}MB까지 지원합니다.", MAX_FILE_SIZE / (1024 * 1024)));
     }
     
-    if diagram.relations.len() > 5000 {
-        return Err("관계가 너무 많습니다.".to_string());
+    // 파일 타입 검증
+    if let Some(extension) = path_buf.extension() {
+        if extension.to_string_lossy().to_lowercase() != "json" {
+            return Err("JSON 파일만 지원합니다.".to_string());
+        }
+    } else {
+        return Err("파일 확장자가 필요합니다. JSON 파일을 선택해주세요.".to_string());
     }
     
-    Ok(())
+    // 파일 읽기
+    let file_content = fs::read_to_string(path_buf)
+        .map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", path_buf.display(), e))?;
+    
+    // 기본 검증
+    if file_content.trim().is_empty() {
+        return Err("파일이 비어있습니다.".to_string());
+    }
+    
+    // JSON 파싱 후 이전 형식이면 현재 형식으로 마이그레이션 (더 새로운 형식이면 오류)
+    finish_load(file_format::parse(&file_content)?, path_buf)
+}
+
+// 마이그레이션 결과 출력, normalize, 한도 검사
+fn finish_load(parsed: ParsedDiagram, path: &Path) -> Result<LoadResult, String> {
+    let ParsedDiagram { mut diagram, migrations } = parsed;
+    for migration in &migrations {
+        println!("파일 형식 마이그레이션: {}", migration);
+    }
+    
+    // 기본값/필수 필드 보정, 빈 물리명은 로마자로 채움
+    let warnings = diagram.normalize_with(&NormalizeOptions { romanize: true });
+    
+    // 기본 다이어그램 검증
+    lint::check_limits(&diagram)?;
+    
+    Ok(LoadResult {
+        diagram,
+        file_path: path.to_string_lossy().to_string(),
+        migrations,
+        warnings,
+    })
+}
+
+// 폴더면 폴더 형식, 아니면 JSON 파일로 읽음
+fn read_diagram_path(path: &Path) -> Result<LoadResult, String> {
+    if path.is_dir() {
+        finish_load(file_format::read_split(path)?, path)
+    } else {
+        read_diagram_file(path)
+    }
+}
+
+// 엔티티마다 파일을 나눈 폴더 형식으로 저장 (diagram.json + entities/*.json)
+#[command]
+pub async fn save_diagram_to_directory(diagram: ErdDiagram, dir_path: String) -> Result<String, String> {
+    println!("save_diagram_to_directory 명령어 호출됨: {}", dir_path);
+    file_format::write_split(&diagram, Path::new(&dir_path))?;
+    Ok(dir_path)
+}
+
+// 폴더 형식 다이어그램 열기
+#[command]
+pub async fn load_diagram_from_directory(dir_path: String) -> Result<LoadResult, String> {
+    println!("load_diagram_from_directory 명령어 호출됨: {}", dir_path);
+    let dir = Path::new(&dir_path);
+    if !dir.is_dir() {
+        return Err(format!("폴더가 아닙니다: {}", dir_path));
+    }
+    read_diagram_path(dir)
+}
+
+// 저장 파일 옆의 .bak 백업 목록 (최근 세대부터)
+#[command]
+pub async fn list_backups(file_path: String) -> Result<Vec<BackupInfo>, String> {
+    println!("list_backups 명령어 호출됨: {}", file_path);
+    backup::list(Path::new(&file_path))
+}
+
+// 백업 세대를 원래 파일로 복원 (복원 직전 파일도 .bak.1로 남음)
+#[command]
+pub async fn restore_backup(app: tauri::AppHandle, file_path: String, generation: usize) -> Result<LoadResult, String> {
+    println!("restore_backup 명령어 호출됨: {} ({}세대)", file_path, generation);
+    let path = Path::new(&file_path);
+    let content = backup::read(path, generation)?;
+    
+    // 깨진 백업으로 현재 파일을 덮어쓰지 않도록 먼저 파싱해 봄
+    file_format::parse(&content)?;
+    
+    backup::save(path, &content, backup_keep(&app)?)?;
+    read_diagram_file(path)
 }
 
 #[command]
@@ -238,6 +311,52 @@ pub async fn export_mermaid(app: tauri::AppHandle, diagram: ErdDiagram) -> Resul
     }
 }
 
+// 슈퍼타입/서브타입 계층은 계층별 변환 방식으로 테이블을 만들고, 변환 경고는 SQL 주석으로 남김
+// expand_many_to_many: N:M 관계를 연결 테이블로 풀어서 출력 (기본값 true, false면 N:M은 SQL 주석으로만 남김)
+#[command]
+pub async fn export_sql(
+    app: tauri::AppHandle,
+    diagram: ErdDiagram,
+    dialect: SqlDialect,
+    expand_many_to_many: Option<bool>,
+) -> Result<String, String> {
+    println!("export_sql 명령어 호출됨: {:?}", dialect);
+    let physical = hierarchy::transform(&diagram);
+    let diagram = if expand_many_to_many.unwrap_or(true) { junction::expand_all(&physical.diagram) } else { physical.diagram };
+    
+    let (tx, rx) = mpsc::channel();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    
+    app.dialog()
+        .file()
+        .set_title("SQL 파일로 내보내기")
+        .add_filter("SQL Files", &["sql"])
+        .set_file_name("erd_diagram.sql")
+        .save_file(move |file_path| {
+            if let Ok(mut sender) = tx.lock() {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(file_path);
+                }
+            }
+        });
+    
+    let file_path = rx.recv().map_err(|_| "Dialog was cancelled".to_string())?;
+    
+    match file_path {
+        Some(path) => {
+            let path_buf = path.as_path().unwrap();
+            let mut sql_content: String = physical.warnings.iter().map(|w| format!("-- 경고: {}\n", w)).collect();
+            sql_content.push_str(&diagram.to_sql(dialect));
+            
+            fs::write(path_buf, sql_content)
+                .map_err(|e| format!("Failed to write SQL file: {}", e))?;
+            
+            Ok(path_buf.to_string_lossy().to_string())
+        }
+        None => Err("Export cancelled".to_string())
+    }
+}
+
 #[command]
 pub async fn export_xlsx(app: tauri::AppHandle, xlsx_data: Vec<u8>) -> Result<String, String> {
     println!("export_xlsx 명령어 호출됨");
@@ -328,4 +447,433 @@ pub async fn import_xlsx(app: tauri::AppHandle) -> Result<Vec<u8>, String> {
         }
         None => Err("파일 선택이 취소되었습니다.".to_string())
     }
-}
\ No newline at end of file
+}
+
+#[command]
+pub async fn import_sql(app: tauri::AppHandle) -> Result<SqlImportResult, String> {
+    println!("import_sql 명령어 호출됨");
+    
+    let (tx, rx) = mpsc::channel();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    
+    app.dialog()
+        .file()
+        .set_title("SQL 파일 가져오기")
+        .add_filter("SQL Files", &["sql"])
+        .pick_file(move |file_path| {
+            if let Ok(mut sender) = tx.lock() {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(file_path);
+                }
+            }
+        });
+    
+    let file_path = rx.recv().map_err(|_| "Dialog was cancelled".to_string())?;
+    
+    match file_path {
+        Some(path) => {
+            let path_buf = path.as_path().unwrap();
+            
+            // 파일 크기 검사
+            let metadata = fs::metadata(path_buf)
+                .map_err(|e| format!("파일 정보를 읽을 수 없습니다: {}", e))?;
+            
+            if metadata.len() > MAX_FILE_SIZE {
+                return Err(format!("파일이 너무 큽니다. 최대 {}MB까지 지원합니다.", MAX_FILE_SIZE / (1024 * 1024)));
+            }
+            
+            // 파일 읽기
+            let script = fs::read_to_string(path_buf)
+                .map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", path_buf.display(), e))?;
+            
+            if script.trim().is_empty() {
+                return Err("파일이 비어있습니다.".to_string());
+            }
+            
+            let result = sql_import::parse_sql(&script);
+            lint::check_limits(&result.diagram)?;
+            
+            println!("SQL 가져오기 완료: 엔티티 {}개, 경고 {}개", result.diagram.entities.len(), result.warnings.len());
+            Ok(result)
+        }
+        None => Err("파일 선택이 취소되었습니다.".to_string())
+    }
+}
+
+#[command]
+pub async fn import_sqlite(app: tauri::AppHandle) -> Result<SqlImportResult, String> {
+    println!("import_sqlite 명령어 호출됨");
+    
+    let (tx, rx) = mpsc::channel();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    
+    app.dialog()
+        .file()
+        .set_title("SQLite 데이터베이스 가져오기")
+        .add_filter("SQLite Database", &["sqlite", "sqlite3", "db"])
+        .pick_file(move |file_path| {
+            if let Ok(mut sender) = tx.lock() {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(file_path);
+                }
+            }
+        });
+    
+    let file_path = rx.recv().map_err(|_| "Dialog was cancelled".to_string())?;
+    
+    match file_path {
+        Some(path) => {
+            let path_buf = path.as_path().unwrap();
+            
+            // 파일 타입 검증
+            if let Some(extension) = path_buf.extension() {
+                let extension = extension.to_string_lossy().to_lowercase();
+                if !["sqlite", "sqlite3", "db"].contains(&extension.as_str()) {
+                    return Err("SQLite 데이터베이스 파일(.sqlite, .db)만 지원합니다.".to_string());
+                }
+            } else {
+                return Err("파일 확장자가 필요합니다. SQLite 데이터베이스 파일을 선택해주세요.".to_string());
+            }
+            
+            let result = introspect::sqlite(path_buf)?;
+            lint::check_limits(&result.diagram)?;
+            
+            println!("SQLite 가져오기 완료: 엔티티 {}개, 경고 {}개", result.diagram.entities.len(), result.warnings.len());
+            Ok(result)
+        }
+        None => Err("파일 선택이 취소되었습니다.".to_string())
+    }
+}
+
+#[command]
+pub async fn import_postgres(connection_string: String, schema: String) -> Result<SqlImportResult, String> {
+    // 접속 문자열에는 비밀번호가 포함될 수 있으므로 스키마만 기록
+    println!("import_postgres 명령어 호출됨: schema={}", schema);
+    
+    let schema = if schema.trim().is_empty() { "public".to_string() } else { schema.trim().to_string() };
+    
+    // postgres 동기 클라이언트는 자체 런타임을 사용하므로 블로킹 스레드에서 실행
+    let result = tauri::async_runtime::spawn_blocking(move || introspect::postgres(&connection_string, &schema))
+        .await
+        .map_err(|e| format!("PostgreSQL 가져오기 작업 실패: {}", e))??;
+    
+    lint::check_limits(&result.diagram)?;
+    
+    println!("PostgreSQL 가져오기 완료: 엔티티 {}개, 경고 {}개", result.diagram.entities.len(), result.warnings.len());
+    Ok(result)
+}
+
+#[command]
+pub async fn diff_diagrams(old: ErdDiagram, new: ErdDiagram) -> Result<DiffResult, String> {
+    println!("diff_diagrams 명령어 호출됨");
+    
+    let diff = diff::diff_diagrams(&old, &new);
+    let markdown = diff.to_markdown();
+    
+    Ok(DiffResult { diff, markdown })
+}
+
+#[command]
+pub async fn diff_diagram_files(old_path: String, new_path: String) -> Result<DiffResult, String> {
+    println!("diff_diagram_files 명령어 호출됨: {} -> {}", old_path, new_path);
+    
+    let old = read_diagram_path(Path::new(&old_path))?.diagram;
+    let new = read_diagram_path(Path::new(&new_path))?.diagram;
+    
+    let diff = diff::diff_diagrams(&old, &new);
+    let markdown = diff.to_markdown();
+    
+    Ok(DiffResult { diff, markdown })
+}
+
+#[command]
+pub async fn generate_migration(old: ErdDiagram, new: ErdDiagram, dialect: SqlDialect) -> Result<Migration, String> {
+    println!("generate_migration 명령어 호출됨: {:?}", dialect);
+    
+    // 계층은 물리 모델로 바꾼 뒤 비교
+    let old = hierarchy::transform(&old).diagram;
+    let new = hierarchy::transform(&new);
+    let mut result = migration::migration(&old, &new.diagram, dialect);
+    result.warnings.extend(new.warnings);
+    Ok(result)
+}
+
+#[command]
+pub async fn validate_diagram(app: tauri::AppHandle, diagram: ErdDiagram) -> Result<Vec<Diagnostic>, String> {
+    println!("validate_diagram 명령어 호출됨");
+    lint::check_limits(&diagram)?;
+    let project_policy: Option<NamingPolicy> = read_config(&app, NAMING_POLICY_FILE)?;
+    Ok(lint::lint(&diagram, project_policy.as_ref()))
+}
+
+#[command]
+pub async fn list_lint_rules() -> Result<Vec<Rule>, String> {
+    Ok(lint::RULES.to_vec())
+}
+
+#[command]
+pub async fn fix_naming(app: tauri::AppHandle, diagram: ErdDiagram) -> Result<NamingFixResult, String> {
+    println!("fix_naming 명령어 호출됨");
+    let policy = match diagram.naming_policy.clone() {
+        Some(policy) => policy,
+        None => read_config(&app, NAMING_POLICY_FILE)?
+            .ok_or_else(|| "명명 규칙이 설정되어 있지 않습니다.".to_string())?,
+    };
+    Ok(naming::fix(&diagram, &policy))
+}
+
+#[command]
+pub async fn get_project_naming_policy(app: tauri::AppHandle) -> Result<Option<NamingPolicy>, String> {
+    read_config(&app, NAMING_POLICY_FILE)
+}
+
+#[command]
+pub async fn save_project_naming_policy(app: tauri::AppHandle, policy: Option<NamingPolicy>) -> Result<(), String> {
+    println!("save_project_naming_policy 명령어 호출됨");
+    write_config(&app, NAMING_POLICY_FILE, policy.as_ref())
+}
+
+// 열린 파일 경로별 복구 슬롯에 자동 저장 (file_path가 없으면 아직 저장하지 않은 다이어그램)
+#[command]
+pub async fn autosave_diagram(app: tauri::AppHandle, diagram: ErdDiagram, file_path: Option<String>) -> Result<(), String> {
+    println!("autosave_diagram 명령어 호출됨: {:?}", file_path);
+    recovery::write(&recovery_dir(&app)?, file_path.as_deref(), &diagram)?;
+    Ok(())
+}
+
+// 시작 시 디스크 파일보다 새로운 자동 저장본 목록
+#[command]
+pub async fn list_recovery_sessions(app: tauri::AppHandle) -> Result<Vec<RecoverySession>, String> {
+    println!("list_recovery_sessions 명령어 호출됨");
+    recovery::sessions(&recovery_dir(&app)?)
+}
+
+#[command]
+pub async fn restore_recovery_session(app: tauri::AppHandle, key: String) -> Result<LoadResult, String> {
+    println!("restore_recovery_session 명령어 호출됨: {}", key);
+    let (file_path, parsed) = recovery::restore(&recovery_dir(&app)?, &key)?;
+    finish_load(parsed, Path::new(file_path.as_deref().unwrap_or_default()))
+}
+
+#[command]
+pub async fn discard_recovery_session(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    println!("discard_recovery_session 명령어 호출됨: {}", key);
+    recovery::discard(&recovery_dir(&app)?, &key)
+}
+
+fn recovery_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| format!("앱 데이터 폴더를 찾을 수 없습니다: {}", e))?;
+    Ok(dir.join("recovery"))
+}
+
+// 파일로 저장했으면 자동 저장본은 필요 없음 (실패해도 다음 시작 때 파일보다 오래된 슬롯으로 정리됨)
+fn discard_recovery(app: &tauri::AppHandle, file_path: &str) {
+    if let Ok(dir) = recovery_dir(app) {
+        let _ = recovery::discard(&dir, &recovery::key(Some(file_path)));
+    }
+}
+
+#[command]
+pub async fn get_backup_settings(app: tauri::AppHandle) -> Result<BackupSettings, String> {
+    Ok(read_config(&app, BACKUP_SETTINGS_FILE)?.unwrap_or_default())
+}
+
+#[command]
+pub async fn save_backup_settings(app: tauri::AppHandle, settings: BackupSettings) -> Result<(), String> {
+    println!("save_backup_settings 명령어 호출됨: {}세대", settings.keep);
+    write_config(&app, BACKUP_SETTINGS_FILE, Some(&settings))
+}
+
+fn backup_keep(app: &tauri::AppHandle) -> Result<usize, String> {
+    Ok(read_config::<BackupSettings>(app, BACKUP_SETTINGS_FILE)?.unwrap_or_default().keep)
+}
+
+#[command]
+pub async fn import_term_dictionary(app: tauri::AppHandle) -> Result<DictionaryImportResult, String> {
+    println!("import_term_dictionary 명령어 호출됨");
+    
+    let (tx, rx) = mpsc::channel();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    
+    app.dialog()
+        .file()
+        .set_title("표준 단어 사전 가져오기")
+        .add_filter("Dictionary Files", &["csv", "xlsx"])
+        .pick_file(move |file_path| {
+            if let Ok(mut sender) = tx.lock() {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(file_path);
+                }
+            }
+        });
+    
+    let file_path = rx.recv().map_err(|_| "Dialog was cancelled".to_string())?;
+    
+    match file_path {
+        Some(path) => {
+            let path_buf = path.as_path().unwrap();
+            
+            let metadata = fs::metadata(path_buf)
+                .map_err(|e| format!("파일 정보를 읽을 수 없습니다: {}", e))?;
+            if metadata.len() > MAX_FILE_SIZE {
+                return Err(format!("파일이 너무 큽니다. 최대 {}MB까지 지원합니다.", MAX_FILE_SIZE / (1024 * 1024)));
+            }
+            
+            let result = dictionary::import(path_buf)?;
+            write_config(&app, TERM_DICTIONARY_FILE, Some(&result.dictionary))?;
+            Ok(result)
+        }
+        None => Err("파일 선택이 취소되었습니다.".to_string())
+    }
+}
+
+#[command]
+pub async fn get_term_dictionary(app: tauri::AppHandle) -> Result<TermDictionary, String> {
+    Ok(read_config(&app, TERM_DICTIONARY_FILE)?.unwrap_or_default())
+}
+
+#[command]
+pub async fn save_term_dictionary(app: tauri::AppHandle, dictionary: TermDictionary) -> Result<(), String> {
+    println!("save_term_dictionary 명령어 호출됨: {}개 단어", dictionary.terms.len());
+    write_config(&app, TERM_DICTIONARY_FILE, Some(&dictionary))
+}
+
+#[command]
+pub async fn apply_term_dictionary(app: tauri::AppHandle, diagram: ErdDiagram, overwrite: bool) -> Result<TermApplyResult, String> {
+    println!("apply_term_dictionary 명령어 호출됨");
+    let dictionary: TermDictionary = read_config(&app, TERM_DICTIONARY_FILE)?
+        .ok_or_else(|| "표준 단어 사전이 없습니다. 먼저 사전을 가져와 주세요.".to_string())?;
+    Ok(dictionary::apply(&diagram, &dictionary, overwrite))
+}
+
+#[command]
+pub async fn regenerate_physical_names(
+    app: tauri::AppHandle,
+    diagram: ErdDiagram,
+    entity_ids: Vec<String>,
+    strategy: NameStrategy,
+) -> Result<TermApplyResult, String> {
+    println!("regenerate_physical_names 명령어 호출됨: {:?}, {}개 엔티티", strategy, entity_ids.len());
+    let dictionary: TermDictionary = match strategy {
+        NameStrategy::Dictionary => read_config(&app, TERM_DICTIONARY_FILE)?
+            .ok_or_else(|| "표준 단어 사전이 없습니다. 먼저 사전을 가져와 주세요.".to_string())?,
+        _ => TermDictionary::default(),
+    };
+    Ok(dictionary::regenerate(&diagram, &entity_ids, strategy, &dictionary))
+}
+
+// 열거형/도메인 추가 또는 수정 (old_name: 수정할 기존 타입 이름)
+#[command]
+pub async fn save_user_type(diagram: ErdDiagram, old_name: Option<String>, user_type: UserType) -> Result<ErdDiagram, String> {
+    println!("save_user_type 명령어 호출됨: {}", user_type.name());
+    user_types::save(&diagram, old_name.as_deref(), user_type)
+}
+
+// 타입 삭제 (참조하던 속성에는 타입 정의를 풀어서 넣음)
+#[command]
+pub async fn remove_user_type(diagram: ErdDiagram, name: String) -> Result<ErdDiagram, String> {
+    println!("remove_user_type 명령어 호출됨: {}", name);
+    user_types::remove(&diagram, &name)
+}
+
+// N:M 관계를 연결 엔티티와 두 1:N 관계로 변환
+#[command]
+pub async fn resolve_many_to_many(diagram: ErdDiagram, relation_id: String) -> Result<ErdDiagram, String> {
+    println!("resolve_many_to_many 명령어 호출됨: {}", relation_id);
+    junction::resolve(&diagram, &relation_id)
+}
+
+// 연결 엔티티를 지우고 원래 N:M 관계로 되돌림
+#[command]
+pub async fn restore_many_to_many(diagram: ErdDiagram, entity_id: String) -> Result<ErdDiagram, String> {
+    println!("restore_many_to_many 명령어 호출됨: {}", entity_id);
+    junction::restore(&diagram, &entity_id)
+}
+
+// 관계를 추가하고 자식 엔티티에 FK 속성을 만들어 줌
+// (새 관계는 비식별이 기본, isIdentifying을 true로 넘긴 경우만 FK를 자식 PK에 포함)
+#[command]
+pub async fn create_relation(diagram: ErdDiagram, mut relation: Relation, is_identifying: Option<bool>) -> Result<ErdDiagram, String> {
+    println!("create_relation 명령어 호출됨: {}", relation.id);
+    relation.is_identifying = is_identifying.unwrap_or(false);
+    relations::create(&diagram, relation)
+}
+
+// 관계를 삭제하고 더 이상 쓰이지 않는 FK 속성을 제거
+#[command]
+pub async fn delete_relation(diagram: ErdDiagram, relation_id: String) -> Result<ErdDiagram, String> {
+    println!("delete_relation 명령어 호출됨: {}", relation_id);
+    relations::delete(&diagram, &relation_id)
+}
+
+// 엔티티를 수정하고 PK 변경을 자식 엔티티의 FK에 반영
+#[command]
+pub async fn update_entity(diagram: ErdDiagram, entity: Entity) -> Result<ErdDiagram, String> {
+    println!("update_entity 명령어 호출됨: {}", entity.id);
+    relations::update_entity(&diagram, entity)
+}
+
+// 슈퍼타입/서브타입 계층 추가/수정
+#[command]
+pub async fn save_subtype_hierarchy(diagram: ErdDiagram, hierarchy: SubtypeHierarchy) -> Result<ErdDiagram, String> {
+    println!("save_subtype_hierarchy 명령어 호출됨: {}", hierarchy.id);
+    hierarchy::save(&diagram, hierarchy)
+}
+
+// 계층 삭제 (엔티티는 남김)
+#[command]
+pub async fn remove_subtype_hierarchy(diagram: ErdDiagram, id: String) -> Result<ErdDiagram, String> {
+    println!("remove_subtype_hierarchy 명령어 호출됨: {}", id);
+    hierarchy::remove(&diagram, &id)
+}
+
+// 계층을 변환 방식대로 테이블로 바꾼 물리 모델 미리보기
+#[command]
+pub async fn preview_physical_model(diagram: ErdDiagram) -> Result<PhysicalModel, String> {
+    println!("preview_physical_model 명령어 호출됨");
+    Ok(hierarchy::transform(&diagram))
+}
+
+// 뷰 정의 SQL에서 참조 테이블을 다시 읽고, derive_columns면 컬럼도 다시 만듦
+#[command]
+pub async fn refresh_view(diagram: ErdDiagram, entity_id: String, derive_columns: bool) -> Result<ViewRefreshResult, String> {
+    println!("refresh_view 명령어 호출됨: {}", entity_id);
+    views::refresh(&diagram, &entity_id, derive_columns)
+}
+
+const NAMING_POLICY_FILE: &str = "naming_policy.json";
+const TERM_DICTIONARY_FILE: &str = "term_dictionary.json";
+const BACKUP_SETTINGS_FILE: &str = "backup_settings.json";
+
+// 프로젝트 공통 설정(명명 규칙, 단어 사전, 백업)은 앱 설정 폴더에 저장
+fn config_path(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| format!("설정 폴더를 찾을 수 없습니다: {}", e))?;
+    Ok(dir.join(file_name))
+}
+
+fn read_config<T: serde::de::DeserializeOwned>(app: &tauri::AppHandle, file_name: &str) -> Result<Option<T>, String> {
+    let path = config_path(app, file_name)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("설정 읽기 실패: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("설정 파일 '{}'을(를) 파싱할 수 없습니다: {}", file_name, e))
+}
+
+// None이면 설정 파일 삭제
+fn write_config<T: Serialize>(app: &tauri::AppHandle, file_name: &str, value: Option<&T>) -> Result<(), String> {
+    let path = config_path(app, file_name)?;
+    match value {
+        Some(value) => {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| format!("설정 폴더 생성 실패: {}", e))?;
+            }
+            let json = serde_json::to_string_pretty(value).map_err(|e| format!("직렬화 실패: {}", e))?;
+            fs::write(&path, json).map_err(|e| format!("설정 저장 실패: {}", e))
+        }
+        None if path.exists() => fs::remove_file(&path).map_err(|e| format!("설정 삭제 실패: {}", e)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,481 @@
+use crate::erd::{is_blank_physical, sanitize_physical, ErdDiagram};
+use crate::naming::{self, NameChange};
+use crate::romanize::romanize;
+use calamine::{open_workbook_auto, Data, Reader};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+// 표준 단어 사전 항목 (예: 회원 -> MBR)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Term {
+    pub word: String,
+    pub abbreviation: String,
+    #[serde(default)]
+    pub english_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TermDictionary {
+    #[serde(default)]
+    pub terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictionaryImportResult {
+    pub dictionary: TermDictionary,
+    pub warnings: Vec<String>,
+}
+
+// 논리명 하나를 단어로 나눠 만든 물리명
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Composition {
+    pub physical_name: String,
+    pub unknown_words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownWords {
+    pub entity_id: String,
+    pub attribute: Option<String>, // 속성이면 물리명, 엔티티면 None
+    pub logical_name: String,
+    pub words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermApplyResult {
+    pub diagram: ErdDiagram,
+    pub changes: Vec<NameChange>,
+    pub unknown_words: Vec<UnknownWords>,
+}
+
+// 물리명 재생성 전략
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum NameStrategy {
+    Keep,       // 기존 물리명 유지 (비어 있으면 로마자로 채움)
+    Romanize,   // 논리명을 로마자로 표기
+    Dictionary, // 표준 단어 사전 (없는 단어는 로마자)
+}
+
+const WORD_HEADERS: &[&str] = &["표준단어명", "단어명", "표준단어", "단어", "논리명", "word"];
+const ABBREVIATION_HEADERS: &[&str] = &["영문약어명", "영문약어", "약어명", "약어", "물리명", "abbreviation", "abbr"];
+const ENGLISH_HEADERS: &[&str] = &["영문명", "영문", "english", "english_name"];
+
+impl TermDictionary {
+    // romanize_unknown이면 사전에 없는 단어는 로마자로 표기해 이름에 포함 (보고는 그대로 함)
+    pub fn compose(&self, logical_name: &str, romanize_unknown: bool) -> Composition {
+        let index = self.index();
+        let longest = index.keys().map(|w| w.chars().count()).max().unwrap_or(0);
+        let mut parts = Vec::new();
+        let mut unknown_words = Vec::new();
+
+        for chunk in logical_name.split(|c: char| !c.is_alphanumeric()).filter(|c| !c.is_empty()) {
+            for segment in segment(chunk, &index, longest) {
+                match segment {
+                    Segment::Term(abbreviation) => parts.push(abbreviation.to_lowercase()),
+                    Segment::Literal(text) => parts.push(text.to_lowercase()),
+                    Segment::Unknown(text) => {
+                        if romanize_unknown {
+                            parts.push(romanize(&text).to_lowercase());
+                        }
+                        unknown_words.push(text);
+                    }
+                }
+            }
+        }
+
+        Composition {
+            physical_name: parts.join("_"),
+            unknown_words,
+        }
+    }
+
+    fn index(&self) -> HashMap<String, &str> {
+        self.terms
+            .iter()
+            .filter(|t| !t.abbreviation.trim().is_empty())
+            .map(|t| (t.word.split_whitespace().collect::<String>(), t.abbreviation.trim()))
+            .collect()
+    }
+}
+
+enum Segment<'a> {
+    Term(&'a str),
+    Literal(String), // 영문/숫자는 그대로 사용
+    Unknown(String),
+}
+
+// 모르는 글자 수가 가장 적고, 그다음으로 조각 수가 적은 분할을 선택
+fn segment<'a>(chunk: &str, index: &HashMap<String, &'a str>, longest: usize) -> Vec<Segment<'a>> {
+    let chars: Vec<char> = chunk.chars().collect();
+    let n = chars.len();
+    // best[i] = (모르는 글자 수, 조각 수, 직전 위치, 사전 단어 여부)
+    let mut best: Vec<Option<(usize, usize, usize, bool)>> = vec![None; n + 1];
+    best[0] = Some((0, 0, 0, false));
+
+    for start in 0..n {
+        let Some((unknown, pieces, _, _)) = best[start] else { continue };
+        let mut relax = |end: usize, cost: (usize, usize), is_term: bool| {
+            match best[end] {
+                Some((u, p, _, _)) if cost >= (u, p) => {}
+                _ => best[end] = Some((cost.0, cost.1, start, is_term)),
+            }
+        };
+        for end in (start + 1)..=(start + longest).min(n) {
+            let word: String = chars[start..end].iter().collect();
+            if index.contains_key(&word) {
+                relax(end, (unknown, pieces + 1), true);
+            }
+        }
+        let known = chars[start].is_ascii_alphanumeric();
+        relax(start + 1, (unknown + usize::from(!known), pieces + 1), false);
+    }
+
+    let mut spans = Vec::new();
+    let mut end = n;
+    while end > 0 {
+        let (_, _, start, is_term) = best[end].expect("reachable");
+        spans.push((start, end, is_term));
+        end = start;
+    }
+    spans.reverse();
+
+    // 한 글자씩 나뉜 영문/모르는 글자를 이어 붙임
+    let mut segments: Vec<Segment<'a>> = Vec::new();
+    for (start, end, is_term) in spans {
+        let text: String = chars[start..end].iter().collect();
+        if is_term {
+            segments.push(Segment::Term(index[&text]));
+            continue;
+        }
+        let literal = chars[start].is_ascii_alphanumeric();
+        match segments.last_mut() {
+            Some(Segment::Literal(prev)) if literal => prev.push_str(&text),
+            Some(Segment::Unknown(prev)) if !literal => prev.push_str(&text),
+            _ if literal => segments.push(Segment::Literal(text)),
+            _ => segments.push(Segment::Unknown(text)),
+        }
+    }
+    segments
+}
+
+// 사전으로 물리명을 만들어 다이어그램에 반영 (overwrite가 false면 비어 있는 물리명만 채움)
+pub fn apply(diagram: &ErdDiagram, dictionary: &TermDictionary, overwrite: bool) -> TermApplyResult {
+    generate(diagram, None, |logical, current| {
+        (overwrite || is_blank_physical(current)).then(|| dictionary.compose(logical, false))
+    })
+}
+
+// 선택한 엔티티(와 속성)의 물리명을 전략에 따라 다시 생성
+pub fn regenerate(
+    diagram: &ErdDiagram,
+    entity_ids: &[String],
+    strategy: NameStrategy,
+    dictionary: &TermDictionary,
+) -> TermApplyResult {
+    let selected: HashSet<&str> = entity_ids.iter().map(String::as_str).collect();
+    generate(diagram, Some(&selected), |logical, current| match strategy {
+        NameStrategy::Keep => is_blank_physical(current).then(|| Composition {
+            physical_name: sanitize_physical(logical, true),
+            unknown_words: Vec::new(),
+        }),
+        NameStrategy::Romanize => Some(Composition {
+            physical_name: sanitize_physical(logical, true),
+            unknown_words: Vec::new(),
+        }),
+        NameStrategy::Dictionary => Some(dictionary.compose(logical, true)),
+    })
+}
+
+// name_for(논리명, 현재 물리명)이 Some이면 그 이름으로 바꾸고 관계/FK 참조도 갱신
+fn generate(
+    diagram: &ErdDiagram,
+    selected: Option<&HashSet<&str>>,
+    name_for: impl Fn(&str, &str) -> Option<Composition>,
+) -> TermApplyResult {
+    let mut diagram = diagram.clone();
+    let mut changes = Vec::new();
+    let mut unknown_words = Vec::new();
+
+    let mut ids: Vec<String> = diagram
+        .entities
+        .keys()
+        .filter(|id| match selected {
+            Some(selected) => selected.contains(id.as_str()),
+            None => true,
+        })
+        .cloned()
+        .collect();
+    ids.sort_by_key(|id| diagram.entities[id].logical_name.clone());
+
+    for id in &ids {
+        let entity = diagram.entities.get_mut(id).expect("entity exists");
+        if let Some(composition) = name_for(&entity.logical_name, &entity.physical_name) {
+            if !composition.physical_name.is_empty() && composition.physical_name != entity.physical_name {
+                changes.push(NameChange {
+                    entity_id: id.clone(),
+                    attribute: None,
+                    old_name: entity.physical_name.clone(),
+                    new_name: composition.physical_name.clone(),
+                });
+                entity.physical_name = composition.physical_name;
+            }
+            if !composition.unknown_words.is_empty() {
+                unknown_words.push(UnknownWords {
+                    entity_id: id.clone(),
+                    attribute: None,
+                    logical_name: entity.logical_name.clone(),
+                    words: composition.unknown_words,
+                });
+            }
+        }
+
+        for attr in entity.attributes.iter_mut() {
+            let Some(composition) = name_for(&attr.logical_name, &attr.physical_name) else { continue };
+            if !composition.physical_name.is_empty() && composition.physical_name != attr.physical_name {
+                changes.push(NameChange {
+                    entity_id: id.clone(),
+                    attribute: Some(attr.physical_name.clone()),
+                    old_name: attr.physical_name.clone(),
+                    new_name: composition.physical_name.clone(),
+                });
+                attr.physical_name = composition.physical_name;
+            }
+            if !composition.unknown_words.is_empty() {
+                unknown_words.push(UnknownWords {
+                    entity_id: id.clone(),
+                    attribute: Some(attr.physical_name.clone()),
+                    logical_name: attr.logical_name.clone(),
+                    words: composition.unknown_words,
+                });
+            }
+        }
+    }
+
+    naming::update_references(&mut diagram, &changes);
+    TermApplyResult { diagram, changes, unknown_words }
+}
+
+// CSV(UTF-8 또는 CP949) / XLSX 파일에서 사전 가져오기
+pub fn import(path: &Path) -> Result<DictionaryImportResult, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let rows = match extension.as_str() {
+        "csv" => read_csv(path)?,
+        "xlsx" | "xls" => read_workbook(path)?,
+        _ => return Err("CSV 또는 XLSX 파일만 가져올 수 있습니다.".to_string()),
+    };
+    from_rows(rows)
+}
+
+fn read_csv(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+    // 엑셀에서 저장한 한글 CSV는 CP949인 경우가 많음
+    let text = match std::str::from_utf8(&bytes) {
+        Ok(text) => text.trim_start_matches('\u{feff}').to_string(),
+        Err(_) => encoding_rs::EUC_KR.decode(&bytes).0.into_owned(),
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map(|r| r.iter().map(|field| field.trim().to_string()).collect())
+                .map_err(|e| format!("CSV 파싱 실패: {}", e))
+        })
+        .collect()
+}
+
+fn read_workbook(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("XLSX 파일을 열 수 없습니다: {}", e))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| "시트가 없습니다.".to_string())?
+        .map_err(|e| format!("시트 읽기 실패: {}", e))?;
+    Ok(range
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Data::Empty => String::new(),
+                    other => other.to_string().trim().to_string(),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+// 머리글이 있으면 열 이름으로, 없으면 (단어, 약어, 영문명) 순서로 해석
+fn from_rows(rows: Vec<Vec<String>>) -> Result<DictionaryImportResult, String> {
+    let mut warnings = Vec::new();
+    let mut rows = rows.into_iter().filter(|r| r.iter().any(|c| !c.is_empty())).peekable();
+
+    let header = rows.peek().cloned().unwrap_or_default();
+    let find = |names: &[&str]| {
+        header
+            .iter()
+            .position(|h| names.iter().any(|n| h.replace(' ', "").eq_ignore_ascii_case(n)))
+    };
+    let (word_column, abbreviation_column, english_column) = match (find(WORD_HEADERS), find(ABBREVIATION_HEADERS)) {
+        (Some(word), Some(abbreviation)) => {
+            rows.next();
+            (word, abbreviation, find(ENGLISH_HEADERS))
+        }
+        _ => (0, 1, Some(2)),
+    };
+
+    let mut dictionary = TermDictionary::default();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (line, row) in rows.enumerate() {
+        let cell = |column: usize| row.get(column).map(|c| c.trim().to_string()).unwrap_or_default();
+        let word = cell(word_column);
+        let abbreviation = cell(abbreviation_column);
+        if word.is_empty() || abbreviation.is_empty() {
+            warnings.push(format!("{}번째 항목: 단어 또는 약어가 비어 있어 건너뜀", line + 1));
+            continue;
+        }
+        let term = Term {
+            word: word.clone(),
+            abbreviation,
+            english_name: english_column.map(cell).filter(|e| !e.is_empty()),
+        };
+        match seen.get(&word) {
+            Some(&index) => {
+                warnings.push(format!("'{}'이(가) 중복되어 마지막 항목을 사용함", word));
+                dictionary.terms[index] = term;
+            }
+            None => {
+                seen.insert(word, dictionary.terms.len());
+                dictionary.terms.push(term);
+            }
+        }
+    }
+
+    if dictionary.terms.is_empty() {
+        return Err("가져올 단어가 없습니다.".to_string());
+    }
+    Ok(DictionaryImportResult { dictionary, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs;
+
+    fn dictionary(terms: &[(&str, &str)]) -> TermDictionary {
+        TermDictionary {
+            terms: terms
+                .iter()
+                .map(|(word, abbreviation)| Term {
+                    word: word.to_string(),
+                    abbreviation: abbreviation.to_string(),
+                    english_name: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn words(dictionary: &TermDictionary) -> Vec<(&str, &str, Option<&str>)> {
+        dictionary
+            .terms
+            .iter()
+            .map(|t| (t.word.as_str(), t.abbreviation.as_str(), t.english_name.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn splits_into_known_terms() {
+        let terms = dictionary(&[("회원", "MBR"), ("번호", "NO"), ("회원번", "X")]);
+        let composition = terms.compose("회원번호", false);
+        assert_eq!(composition.physical_name, "mbr_no");
+        assert!(composition.unknown_words.is_empty());
+    }
+
+    #[test]
+    fn prefers_fewer_unknown_characters_over_longer_terms() {
+        // 회원번(3글자)을 쓰면 '호'가 남으므로 회원 + 번호를 선택
+        let terms = dictionary(&[("회원번", "X"), ("회원", "MBR"), ("번호", "NO")]);
+        assert_eq!(terms.compose("회원번호", false).physical_name, "mbr_no");
+        // 모르는 글자 수가 같으면 조각 수가 적은 분할
+        let terms = dictionary(&[("회원", "MBR"), ("회", "A"), ("원", "B")]);
+        assert_eq!(terms.compose("회원", false).physical_name, "mbr");
+    }
+
+    #[test]
+    fn reports_unknown_words() {
+        let terms = dictionary(&[("회원", "MBR"), ("번호", "NO")]);
+        let composition = terms.compose("회원 가입일자 번호", false);
+        assert_eq!(composition.physical_name, "mbr_no");
+        assert_eq!(composition.unknown_words, ["가입일자"]);
+
+        let romanized = terms.compose("회원가입번호", true);
+        assert_eq!(romanized.physical_name, format!("mbr_{}_no", romanize("가입").to_lowercase()));
+        assert_eq!(romanized.unknown_words, ["가입"]);
+    }
+
+    #[test]
+    fn keeps_ascii_words_as_literals() {
+        let terms = dictionary(&[("회원", "MBR")]);
+        let composition = terms.compose("회원ID2", false);
+        assert_eq!(composition.physical_name, "mbr_id2");
+        assert!(composition.unknown_words.is_empty());
+    }
+
+    #[test]
+    fn imports_utf8_csv_with_header_and_bom() {
+        let dir = temp_dir("dictionary_utf8");
+        let path = dir.join("terms.csv");
+        let text = "\u{feff}영문명,표준 단어명,영문약어명\nMember,회원,MBR\n,번호,NO\nMember,회원,MEM\n,,\n,주소,\n";
+        fs::write(&path, text).unwrap();
+        let result = import(&path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(words(&result.dictionary), [("회원", "MEM", Some("Member")), ("번호", "NO", None)]);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[0].contains("'회원'"));
+        assert!(result.warnings[1].contains("비어 있어"));
+    }
+
+    #[test]
+    fn imports_cp949_csv_without_header() {
+        let dir = temp_dir("dictionary_cp949");
+        let path = dir.join("terms.CSV");
+        let (bytes, _, _) = encoding_rs::EUC_KR.encode("회원,MBR,Member\n번호,NO\n");
+        fs::write(&path, &bytes).unwrap();
+        let result = import(&path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(words(&result.dictionary), [("회원", "MBR", Some("Member")), ("번호", "NO", None)]);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_and_unsupported_files() {
+        let dir = temp_dir("dictionary_reject");
+        let empty = dir.join("empty.csv");
+        fs::write(&empty, "단어,약어\n").unwrap();
+        let txt = dir.join("terms.txt");
+        fs::write(&txt, "회원,MBR\n").unwrap();
+        let results = (import(&empty), import(&txt));
+        let _ = fs::remove_dir_all(&dir);
+        assert!(results.0.is_err());
+        assert!(results.1.err().is_some_and(|e| e.contains("CSV 또는 XLSX")));
+    }
+
+    #[test]
+    fn imports_xlsx() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dictionary.xlsx");
+        let result = import(&path).unwrap();
+        assert_eq!(
+            words(&result.dictionary),
+            [("회원", "MBR", Some("Member")), ("번호", "NO", Some("Number")), ("주문", "ORD", None)]
+        );
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.dictionary.compose("주문회원번호", false).physical_name, "ord_mbr_no");
+    }
+}
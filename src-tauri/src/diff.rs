@@ -0,0 +1,653 @@
+use crate::erd::{
+    pair_lists, Attribute, Cardinality, CheckConstraint, Entity, ErdDiagram, Index, Relation, UniqueConstraint, UserType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// 필드 하나의 변경 (값은 표시용 문자열)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeDiff {
+    pub old: Attribute,
+    pub new: Attribute,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDiff {
+    pub old_id: String,
+    pub new_id: String,
+    pub old_physical_name: String, // 스키마가 있으면 "스키마.물리명"
+    pub new_physical_name: String,
+    pub changes: Vec<FieldChange>, // 엔티티 자체 필드 (논리명/물리명)
+    pub added_attributes: Vec<Attribute>,
+    pub removed_attributes: Vec<Attribute>,
+    pub changed_attributes: Vec<AttributeDiff>,
+}
+
+impl EntityDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+            && self.added_attributes.is_empty()
+            && self.removed_attributes.is_empty()
+            && self.changed_attributes.is_empty()
+    }
+}
+
+// 관계를 사람이 읽을 수 있는 형태로 (엔티티 id 대신 물리명)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationSummary {
+    pub id: String,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub cardinality: String,
+    pub multiplicity: String, // "1 — 0..N" (비식별이면 ", 비식별")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationDiff {
+    pub old: RelationSummary,
+    pub new: RelationSummary,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiagramDiff {
+    pub added_entities: Vec<Entity>,
+    pub removed_entities: Vec<Entity>,
+    pub changed_entities: Vec<EntityDiff>,
+    pub added_relations: Vec<RelationSummary>,
+    pub removed_relations: Vec<RelationSummary>,
+    pub changed_relations: Vec<RelationDiff>,
+    pub type_changes: Vec<FieldChange>, // 열거형/도메인 ("type:이름")
+}
+
+// 엔티티는 id로 먼저 매칭하고, 남은 것끼리 물리명으로 매칭
+pub fn diff_diagrams(old: &ErdDiagram, new: &ErdDiagram) -> DiagramDiff {
+    let mut diff = DiagramDiff::default();
+    named_changes(&mut diff.type_changes, "type", &old.user_types, &new.user_types, UserType::name, |t| t.definition());
+
+    let mut old_entities: Vec<&Entity> = old.entities.values().collect();
+    old_entities.sort_by_key(|e| e.qualified_name().to_lowercase());
+    let mut new_entities: Vec<&Entity> = new.entities.values().collect();
+    new_entities.sort_by_key(|e| e.qualified_name().to_lowercase());
+
+    let pairs = match_pairs(
+        &old_entities,
+        &new_entities,
+        &[&|a, b| a.id == b.id, &|a, b| a.qualified_name().eq_ignore_ascii_case(&b.qualified_name())],
+    );
+
+    for (old_entity, new_entity) in pairs {
+        match (old_entity, new_entity) {
+            (Some(o), Some(n)) => {
+                let entity_diff = diff_entity(o, n);
+                if !entity_diff.is_empty() {
+                    diff.changed_entities.push(entity_diff);
+                }
+            }
+            (Some(o), None) => diff.removed_entities.push(o.clone()),
+            (None, Some(n)) => diff.added_entities.push(n.clone()),
+            (None, None) => {}
+        }
+    }
+
+    let old_relations: Vec<RelationSummary> = old.relations.iter().map(|r| summarize_relation(old, r)).collect();
+    let new_relations: Vec<RelationSummary> = new.relations.iter().map(|r| summarize_relation(new, r)).collect();
+    let old_refs: Vec<&RelationSummary> = old_relations.iter().collect();
+    let new_refs: Vec<&RelationSummary> = new_relations.iter().collect();
+
+    let pairs = match_pairs(
+        &old_refs,
+        &new_refs,
+        &[&|a, b| a.id == b.id, &|a, b| a.from.eq_ignore_ascii_case(&b.from) && a.to.eq_ignore_ascii_case(&b.to)],
+    );
+    for (old_relation, new_relation) in pairs {
+        match (old_relation, new_relation) {
+            (Some(o), Some(n)) => {
+                let mut changes = Vec::new();
+                compare(&mut changes, "name", &o.name, &n.name);
+                compare(&mut changes, "from", &o.from, &n.from);
+                compare(&mut changes, "to", &o.to, &n.to);
+                compare(&mut changes, "cardinality", &o.cardinality, &n.cardinality);
+                compare(&mut changes, "multiplicity", &o.multiplicity, &n.multiplicity);
+                if !changes.is_empty() {
+                    diff.changed_relations.push(RelationDiff { old: o.clone(), new: n.clone(), changes });
+                }
+            }
+            (Some(o), None) => diff.removed_relations.push(o.clone()),
+            (None, Some(n)) => diff.added_relations.push(n.clone()),
+            (None, None) => {}
+        }
+    }
+
+    diff
+}
+
+pub fn diff_entity(old: &Entity, new: &Entity) -> EntityDiff {
+    let mut changes = Vec::new();
+    compare(&mut changes, "logical_name", &old.logical_name, &new.logical_name);
+    compare(&mut changes, "physical_name", &old.physical_name, &new.physical_name);
+    compare_opt(&mut changes, "schema", &old.schema, &new.schema);
+    compare(&mut changes, "kind", &old.kind.label(), &new.kind.label());
+    compare_opt(&mut changes, "view_definition", &old.view_definition, &new.view_definition);
+    named_changes(&mut changes, "index", &old.indexes, &new.indexes, |i| &i.name, describe_index);
+    named_changes(&mut changes, "unique", &old.unique_constraints, &new.unique_constraints, |u| &u.name, describe_unique);
+    named_changes(&mut changes, "check", &old.check_constraints, &new.check_constraints, |c| &c.name, describe_check);
+
+    let mut entity_diff = EntityDiff {
+        old_id: old.id.clone(),
+        new_id: new.id.clone(),
+        old_physical_name: old.qualified_name(),
+        new_physical_name: new.qualified_name(),
+        changes,
+        added_attributes: Vec::new(),
+        removed_attributes: Vec::new(),
+        changed_attributes: Vec::new(),
+    };
+
+    for (old_attr, new_attr) in attribute_pairs(old, new) {
+        match (old_attr, new_attr) {
+            (Some(o), Some(n)) => {
+                let changes = attribute_changes(o, n);
+                if !changes.is_empty() {
+                    entity_diff.changed_attributes.push(AttributeDiff { old: o.clone(), new: n.clone(), changes });
+                }
+            }
+            (Some(o), None) => entity_diff.removed_attributes.push(o.clone()),
+            (None, Some(n)) => entity_diff.added_attributes.push(n.clone()),
+            (None, None) => {}
+        }
+    }
+
+    entity_diff
+}
+
+// 속성은 물리명으로 먼저, 남은 것끼리 논리명으로 매칭 (물리명만 바뀐 경우)
+pub fn attribute_pairs<'a>(old: &'a Entity, new: &'a Entity) -> Vec<(Option<&'a Attribute>, Option<&'a Attribute>)> {
+    let old_attrs: Vec<&Attribute> = old.attributes.iter().collect();
+    let new_attrs: Vec<&Attribute> = new.attributes.iter().collect();
+    match_pairs(
+        &old_attrs,
+        &new_attrs,
+        &[
+            &|a, b| !a.id.is_empty() && a.id == b.id,
+            &|a, b| a.physical_name.eq_ignore_ascii_case(&b.physical_name),
+            &|a, b| a.logical_name == b.logical_name,
+        ],
+    )
+}
+
+fn attribute_changes(old: &Attribute, new: &Attribute) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    compare(&mut changes, "logical_name", &old.logical_name, &new.logical_name);
+    compare(&mut changes, "physical_name", &old.physical_name, &new.physical_name);
+    compare(&mut changes, "data_type", &old.data_type, &new.data_type);
+    compare_opt(&mut changes, "length", &old.length, &new.length);
+    compare(&mut changes, "is_nullable", &old.is_nullable, &new.is_nullable);
+    compare_opt(&mut changes, "default_value", &old.default_value, &new.default_value);
+    compare(&mut changes, "is_primary_key", &old.is_primary_key, &new.is_primary_key);
+    compare(&mut changes, "is_foreign_key", &old.is_foreign_key, &new.is_foreign_key);
+    compare(&mut changes, "is_unique", &old.is_unique, &new.is_unique);
+    compare(&mut changes, "is_auto_increment", &old.is_auto_increment, &new.is_auto_increment);
+    compare_opt(&mut changes, "foreign_key_reference", &old.foreign_key_reference, &new.foreign_key_reference);
+    compare_opt(&mut changes, "remark", &old.remark, &new.remark);
+    compare_opt(&mut changes, "check", &old.check, &new.check);
+    changes
+}
+
+// 인덱스/제약조건은 이름으로 매칭 ("index:이름", "check:이름" 형태의 필드로 표시)
+fn named_changes<T: PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    kind: &str,
+    old: &[T],
+    new: &[T],
+    name: fn(&T) -> &str,
+    describe: fn(&T) -> String,
+) {
+    for item in old {
+        let after = new.iter().find(|n| name(n).eq_ignore_ascii_case(name(item)));
+        if after != Some(item) {
+            changes.push(FieldChange {
+                field: format!("{}:{}", kind, name(item)),
+                old: Some(describe(item)),
+                new: after.map(describe),
+            });
+        }
+    }
+    for item in new.iter().filter(|n| !old.iter().any(|o| name(o).eq_ignore_ascii_case(name(n)))) {
+        changes.push(FieldChange {
+            field: format!("{}:{}", kind, name(item)),
+            old: None,
+            new: Some(describe(item)),
+        });
+    }
+}
+
+// "UNIQUE hash (a, b DESC) WHERE ..."
+pub fn describe_index(index: &Index) -> String {
+    let mut text = String::new();
+    if index.is_unique {
+        text.push_str("UNIQUE ");
+    }
+    text.push_str(&format!("{} ({})", index.method.label(), index.column_list()));
+    if let Some(predicate) = index.predicate.as_deref().filter(|p| !p.trim().is_empty()) {
+        text.push_str(&format!(" WHERE {}", predicate));
+    }
+    text
+}
+
+fn describe_unique(unique: &UniqueConstraint) -> String {
+    format!("UNIQUE ({})", unique.columns.join(", "))
+}
+
+fn describe_check(check: &CheckConstraint) -> String {
+    format!("CHECK ({})", check.expression)
+}
+
+fn compare<T: PartialEq + ToString>(changes: &mut Vec<FieldChange>, field: &str, old: &T, new: &T) {
+    if old != new {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old: Some(old.to_string()),
+            new: Some(new.to_string()),
+        });
+    }
+}
+
+// 빈 문자열과 None은 같은 값으로 취급
+fn compare_opt(changes: &mut Vec<FieldChange>, field: &str, old: &Option<String>, new: &Option<String>) {
+    let old = old.as_deref().map(str::trim).filter(|v| !v.is_empty());
+    let new = new.as_deref().map(str::trim).filter(|v| !v.is_empty());
+    if old != new {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old: old.map(str::to_string),
+            new: new.map(str::to_string),
+        });
+    }
+}
+
+fn summarize_relation(diagram: &ErdDiagram, relation: &Relation) -> RelationSummary {
+    let entity_name = |id: &str| {
+        diagram.entities.get(id).map(|e| e.qualified_name()).unwrap_or_else(|| id.to_string())
+    };
+    let (from_columns, to_columns) = pair_lists(&relation.pairs());
+    RelationSummary {
+        id: relation.id.clone(),
+        name: relation.name.clone(),
+        from: format!("{}.{}", entity_name(&relation.from_entity_id), from_columns),
+        to: format!("{}.{}", entity_name(&relation.to_entity_id), to_columns),
+        cardinality: cardinality_label(&relation.cardinality).to_string(),
+        multiplicity: relation.multiplicity_label(),
+    }
+}
+
+pub fn cardinality_label(cardinality: &Cardinality) -> &'static str {
+    match cardinality {
+        Cardinality::OneToOne => "1:1",
+        Cardinality::OneToMany => "1:N",
+        Cardinality::ManyToMany => "N:M",
+    }
+}
+
+type Matcher<T> = dyn Fn(&T, &T) -> bool;
+
+// 앞의 기준부터 차례로 매칭 (남은 항목만 다음 기준으로), 순서는 new 기준 + 삭제 항목
+fn match_pairs<'a, T>(
+    old: &[&'a T],
+    new: &[&'a T],
+    matchers: &[&Matcher<T>],
+) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let mut used_old: HashSet<usize> = HashSet::new();
+    let mut matched: Vec<Option<usize>> = vec![None; new.len()];
+
+    for matches in matchers {
+        for (ni, n) in new.iter().enumerate() {
+            if matched[ni].is_some() {
+                continue;
+            }
+            if let Some(oi) = (0..old.len()).find(|&oi| !used_old.contains(&oi) && matches(old[oi], n)) {
+                used_old.insert(oi);
+                matched[ni] = Some(oi);
+            }
+        }
+    }
+
+    let mut pairs: Vec<(Option<&T>, Option<&T>)> = new
+        .iter()
+        .enumerate()
+        .map(|(ni, n)| (matched[ni].map(|oi| old[oi]), Some(*n)))
+        .collect();
+    pairs.extend((0..old.len()).filter(|oi| !used_old.contains(oi)).map(|oi| (Some(old[oi]), None)));
+    pairs
+}
+
+impl DiagramDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.changed_entities.is_empty()
+            && self.added_relations.is_empty()
+            && self.removed_relations.is_empty()
+            && self.changed_relations.is_empty()
+            && self.type_changes.is_empty()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str("# ERD Diff\n\n");
+
+        if self.is_empty() {
+            markdown.push_str("변경 사항 없음\n");
+            return markdown;
+        }
+
+        if !self.type_changes.is_empty() {
+            markdown.push_str("## Types\n\n");
+            for change in &self.type_changes {
+                markdown.push_str(&format!("- {}: {} → {}\n", change.field, display(&change.old), display(&change.new)));
+            }
+            markdown.push('\n');
+        }
+
+        if !self.added_entities.is_empty() || !self.removed_entities.is_empty() || !self.changed_entities.is_empty() {
+            markdown.push_str("## Entities\n\n");
+        }
+        for entity in &self.added_entities {
+            markdown.push_str(&format!("- ➕ `{}` ({}) — 속성 {}개\n", entity.qualified_name(), entity.logical_name, entity.attributes.len()));
+        }
+        for entity in &self.removed_entities {
+            markdown.push_str(&format!("- ➖ `{}` ({})\n", entity.qualified_name(), entity.logical_name));
+        }
+        if !self.added_entities.is_empty() || !self.removed_entities.is_empty() {
+            markdown.push('\n');
+        }
+
+        for entity in &self.changed_entities {
+            markdown.push_str(&format!("### `{}`\n\n", entity.new_physical_name));
+            for change in &entity.changes {
+                markdown.push_str(&format!("- {}: {} → {}\n", change.field, display(&change.old), display(&change.new)));
+            }
+            if !entity.changes.is_empty() {
+                markdown.push('\n');
+            }
+
+            if entity.added_attributes.is_empty() && entity.removed_attributes.is_empty() && entity.changed_attributes.is_empty() {
+                continue;
+            }
+            markdown.push_str("| Attribute | Change | Field | Old | New |\n");
+            markdown.push_str("|-----------|--------|-------|-----|-----|\n");
+            for attr in &entity.added_attributes {
+                markdown.push_str(&format!("| {} | added | | | {} |\n", escape(&attr.physical_name), escape(&describe_attribute(attr))));
+            }
+            for attr in &entity.removed_attributes {
+                markdown.push_str(&format!("| {} | removed | | {} | |\n", escape(&attr.physical_name), escape(&describe_attribute(attr))));
+            }
+            for attr in &entity.changed_attributes {
+                for change in &attr.changes {
+                    markdown.push_str(&format!(
+                        "| {} | changed | {} | {} | {} |\n",
+                        escape(&attr.new.physical_name),
+                        change.field,
+                        escape(&display(&change.old)),
+                        escape(&display(&change.new))
+                    ));
+                }
+            }
+            markdown.push('\n');
+        }
+
+        if !self.added_relations.is_empty() || !self.removed_relations.is_empty() || !self.changed_relations.is_empty() {
+            markdown.push_str("## Relations\n\n");
+        }
+        for relation in &self.added_relations {
+            markdown.push_str(&format!(
+                "- ➕ {} ({}, {}) → {} ({})\n",
+                relation.from, relation.cardinality, relation.multiplicity, relation.to, relation.name
+            ));
+        }
+        for relation in &self.removed_relations {
+            markdown.push_str(&format!(
+                "- ➖ {} ({}, {}) → {} ({})\n",
+                relation.from, relation.cardinality, relation.multiplicity, relation.to, relation.name
+            ));
+        }
+        for relation in &self.changed_relations {
+            let changes: Vec<String> = relation
+                .changes
+                .iter()
+                .map(|c| format!("{}: {} → {}", c.field, display(&c.old), display(&c.new)))
+                .collect();
+            markdown.push_str(&format!("- ✏️ {} ({}) — {}\n", relation.new.name, relation.new.id, changes.join(", ")));
+        }
+
+        markdown
+    }
+}
+
+fn describe_attribute(attr: &Attribute) -> String {
+    let mut text = match &attr.length {
+        Some(length) => format!("{}({})", attr.data_type, length),
+        None => attr.data_type.clone(),
+    };
+    if attr.is_primary_key { text.push_str(" PK"); }
+    if attr.is_foreign_key { text.push_str(" FK"); }
+    if !attr.is_nullable { text.push_str(" NOT NULL"); }
+    text
+}
+
+fn display(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "-".to_string())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erd::{ColumnPair, Multiplicity};
+    use crate::sql_import::parse_sql;
+
+    fn users() -> ErdDiagram {
+        parse_sql("CREATE TABLE users (id INT PRIMARY KEY, mail VARCHAR(100), nickname VARCHAR(20));").diagram
+    }
+
+    fn entity_mut(diagram: &mut ErdDiagram) -> &mut Entity {
+        diagram.entities.get_mut("entity_users").unwrap()
+    }
+
+    fn posts() -> ErdDiagram {
+        parse_sql(
+            "CREATE TABLE users (id INT PRIMARY KEY, mail VARCHAR(100));
+             CREATE TABLE posts (id INT PRIMARY KEY, user_id INT REFERENCES users (id), editor_id INT);",
+        )
+        .diagram
+    }
+
+    fn fields(changes: &[FieldChange]) -> Vec<(&str, Option<&str>, Option<&str>)> {
+        changes.iter().map(|c| (c.field.as_str(), c.old.as_deref(), c.new.as_deref())).collect()
+    }
+
+    #[test]
+    fn attribute_rename_is_matched_by_id() {
+        let old = users();
+        let mut new = old.clone();
+        entity_mut(&mut new).attributes[1].physical_name = "email".to_string();
+
+        let diff = diff_diagrams(&old, &new);
+        let entity = &diff.changed_entities[0];
+        assert!(entity.added_attributes.is_empty() && entity.removed_attributes.is_empty());
+        let change = &entity.changed_attributes[0].changes[0];
+        assert_eq!(change.field, "physical_name");
+        assert_eq!((change.old.as_deref(), change.new.as_deref()), (Some("mail"), Some("email")));
+    }
+
+    // id가 없는 이전 파일은 논리명이 같으면 물리명만 바뀐 것으로 봄
+    #[test]
+    fn attribute_without_id_falls_back_to_logical_name() {
+        let mut old = users();
+        for attr in &mut entity_mut(&mut old).attributes {
+            attr.id.clear();
+            attr.logical_name = format!("논리_{}", attr.physical_name);
+        }
+        let mut new = old.clone();
+        entity_mut(&mut new).attributes[1].physical_name = "email".to_string();
+
+        let entity = &diff_diagrams(&old, &new).changed_entities[0];
+        assert!(entity.added_attributes.is_empty() && entity.removed_attributes.is_empty());
+        assert_eq!(entity.changed_attributes.len(), 1);
+    }
+
+    #[test]
+    fn entity_rename_keeps_the_entity() {
+        let old = users();
+        let mut new = old.clone();
+        entity_mut(&mut new).physical_name = "members".to_string();
+
+        let diff = diff_diagrams(&old, &new);
+        assert!(diff.added_entities.is_empty() && diff.removed_entities.is_empty());
+        assert_eq!(diff.changed_entities[0].new_physical_name, "members");
+    }
+
+    #[test]
+    fn replaced_attribute_is_added_and_removed() {
+        let old = users();
+        let mut new = old.clone();
+        let attr = &mut entity_mut(&mut new).attributes[2];
+        attr.id = "attr_phone".to_string();
+        attr.physical_name = "phone".to_string();
+        attr.logical_name = "전화번호".to_string();
+
+        let entity = &diff_diagrams(&old, &new).changed_entities[0];
+        assert_eq!(entity.removed_attributes[0].physical_name, "nickname");
+        assert_eq!(entity.added_attributes[0].physical_name, "phone");
+    }
+
+    #[test]
+    fn attribute_field_changes() {
+        let old = users();
+        let mut new = old.clone();
+        let attrs = &mut entity_mut(&mut new).attributes;
+        attrs[0].data_type = "BIGINT".to_string();
+        attrs[0].is_auto_increment = true;
+        attrs[1].length = Some("255".to_string());
+        attrs[1].is_nullable = false;
+        attrs[1].is_unique = true;
+        attrs[2].default_value = Some("'guest'".to_string());
+        attrs[2].is_primary_key = true;
+        attrs[2].is_foreign_key = true;
+
+        let entity = &diff_diagrams(&old, &new).changed_entities[0];
+        let changes: Vec<_> = entity.changed_attributes.iter().map(|a| (a.new.physical_name.as_str(), fields(&a.changes))).collect();
+        assert_eq!(
+            changes,
+            [
+                ("id", vec![("data_type", Some("INT"), Some("BIGINT")), ("is_auto_increment", Some("false"), Some("true"))]),
+                (
+                    "mail",
+                    vec![
+                        ("length", Some("100"), Some("255")),
+                        ("is_nullable", Some("true"), Some("false")),
+                        ("is_unique", Some("false"), Some("true")),
+                    ]
+                ),
+                (
+                    "nickname",
+                    vec![
+                        ("default_value", None, Some("'guest'")),
+                        ("is_primary_key", Some("false"), Some("true")),
+                        ("is_foreign_key", Some("false"), Some("true")),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    // 빈 문자열과 None은 같은 값이므로 변경이 아님
+    #[test]
+    fn blank_optional_fields_are_unchanged() {
+        let old = users();
+        let mut new = old.clone();
+        entity_mut(&mut new).attributes[1].default_value = Some("  ".to_string());
+        entity_mut(&mut new).attributes[1].remark = Some(String::new());
+        assert!(diff_diagrams(&old, &new).is_empty());
+        assert_eq!(diff_diagrams(&old, &new).to_markdown(), "# ERD Diff\n\n변경 사항 없음\n");
+    }
+
+    #[test]
+    fn relation_added_removed_and_changed() {
+        let old = posts();
+        let mut new = old.clone();
+        let relation = &mut new.relations[0];
+        relation.name = "fk_posts_author".to_string();
+        relation.to_multiplicity = Some(Multiplicity::ONE_OR_MANY);
+
+        let diff = diff_diagrams(&old, &new);
+        assert!(diff.added_relations.is_empty() && diff.removed_relations.is_empty());
+        assert_eq!(
+            fields(&diff.changed_relations[0].changes),
+            [
+                ("name", Some("fk_posts_user_id"), Some("fk_posts_author")),
+                ("multiplicity", Some("0..1 — 0..N, 비식별"), Some("0..1 — 1..N, 비식별")),
+            ]
+        );
+
+        // id와 FK 컬럼이 모두 다르면 삭제 + 추가
+        let mut new = old.clone();
+        let mut relation = new.relations[0].clone();
+        relation.id = "relation_editor".to_string();
+        relation.name = "fk_posts_editor".to_string();
+        relation.set_pairs(vec![ColumnPair::new("id".to_string(), Some("editor_id".to_string()))]);
+        new.relations = vec![relation];
+
+        let diff = diff_diagrams(&old, &new);
+        assert!(diff.changed_relations.is_empty());
+        assert_eq!(diff.removed_relations[0].to, "posts.user_id");
+        assert_eq!(diff.added_relations[0].to, "posts.editor_id");
+        assert_eq!(diff.added_relations[0].from, "users.id");
+    }
+
+    #[test]
+    fn markdown_report() {
+        let old = posts();
+        let mut new = old.clone();
+        new.entities.remove("entity_users");
+        new.relations.clear();
+        let posts = new.entities.get_mut("entity_posts").unwrap();
+        posts.logical_name = "게시글".to_string();
+        posts.attributes.retain(|a| a.physical_name != "editor_id");
+        posts.attributes[1].is_nullable = false;
+        let mut title = Attribute::new("title", "VARCHAR");
+        title.length = Some("200".to_string());
+        title.is_nullable = false;
+        posts.attributes.push(title);
+        new.entities.insert("entity_tags".to_string(), Entity::new("entity_tags", "tags"));
+
+        assert_eq!(
+            diff_diagrams(&old, &new).to_markdown(),
+            "# ERD Diff\n\n\
+             ## Entities\n\n\
+             - ➕ `tags` (tags) — 속성 0개\n\
+             - ➖ `users` (users)\n\n\
+             ### `posts`\n\n\
+             - logical_name: posts → 게시글\n\n\
+             | Attribute | Change | Field | Old | New |\n\
+             |-----------|--------|-------|-----|-----|\n\
+             | title | added | | | VARCHAR(200) NOT NULL |\n\
+             | editor_id | removed | | INT | |\n\
+             | user_id | changed | is_nullable | true | false |\n\n\
+             ## Relations\n\n\
+             - ➖ users.id (1:N, 0..1 — 0..N, 비식별) → posts.user_id (fk_posts_user_id)\n"
+        );
+    }
+}
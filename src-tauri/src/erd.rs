@@ -1,9 +1,14 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::file_format;
+use crate::naming::NamingPolicy;
+use crate::romanize;
+use crate::sql::{self, SqlDialect};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attribute {
-    #[serde(alias = "name")]
+    #[serde(default)]
+    pub id: String, // 엔티티 안에서 고유한 id (이름이 바뀌어도 유지, 관계가 이 값으로 참조)
     pub logical_name: String,  // 논리적 속성명 (한글 등) - 필수
     #[serde(default)]
     pub physical_name: String, // 물리적 속성명 (영문, DB 컬럼명) - 필수(없으면 후처리)
@@ -23,25 +28,96 @@ pub struct Attribute {
     pub foreign_key_reference: Option<String>,
     #[serde(default)]
     pub remark: Option<String>, // 비고/설명
+    #[serde(default)]
+    pub check: Option<String>, // 컬럼 CHECK 식 (예: "amount >= 0")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Entity {
     pub id: String,
-    #[serde(alias = "name")]
     pub logical_name: String, // 논리적 엔티티명 (한글 등) - 필수
     #[serde(default)]
     pub physical_name: String, // 물리적 엔티티명 (영문, DB 테이블명) - 필수(없으면 후처리)
-    #[serde(default = "default_pos_x")]
+    #[serde(default)]
+    pub schema: Option<String>, // 스키마/네임스페이스 (없으면 DB 기본 스키마)
+    #[serde(default = "default_pos_x", serialize_with = "serialize_rounded")]
     pub x: f64,
-    #[serde(default = "default_pos_y")]
+    #[serde(default = "default_pos_y", serialize_with = "serialize_rounded")]
     pub y: f64,
-    #[serde(default = "default_width")]
+    #[serde(default = "default_width", serialize_with = "serialize_rounded")]
     pub width: f64,
-    #[serde(default = "default_height")]
+    #[serde(default = "default_height", serialize_with = "serialize_rounded")]
     pub height: f64,
     #[serde(default)]
     pub attributes: Vec<Attribute>,
+    #[serde(default)]
+    pub indexes: Vec<Index>,
+    #[serde(default)]
+    pub unique_constraints: Vec<UniqueConstraint>, // 이름 있는 (복합) UNIQUE
+    #[serde(default)]
+    pub check_constraints: Vec<CheckConstraint>, // 테이블 단위 CHECK
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_relation: Option<Relation>, // N:M 관계를 풀어 만든 연결 엔티티면 원래 관계 (되돌리기용)
+    #[serde(default, skip_serializing_if = "EntityKind::is_table")]
+    pub kind: EntityKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view_definition: Option<String>, // 뷰의 SELECT 문
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub view_dependencies: Vec<String>, // 뷰가 조회하는 테이블/뷰 엔티티 id
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum EntityKind {
+    #[default]
+    Table,
+    View,
+    MaterializedView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub columns: Vec<String>, // 컬럼 물리명 (순서 유지)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub name: String,
+    pub expression: String, // 예: "status IN ('A', 'B')"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IndexMethod {
+    #[default]
+    BTree,
+    Hash,
+    Gin,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexColumn {
+    pub name: String, // 컬럼 물리명
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Index {
+    pub name: String,
+    pub columns: Vec<IndexColumn>, // 순서 있는 인덱스 컬럼
+    #[serde(default)]
+    pub is_unique: bool,
+    #[serde(default)]
+    pub predicate: Option<String>, // 부분 인덱스 WHERE 조건
+    #[serde(default)]
+    pub method: IndexMethod,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,108 +127,632 @@ pub enum Cardinality {
     ManyToMany,
 }
 
+// 관계 한쪽 끝에 참여하는 인스턴스 수 (max가 None이면 여러 개)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Multiplicity {
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+// 관계의 컬럼 쌍: 부모(from)의 참조 컬럼 -> 자식(to)의 FK 컬럼
+// 이름은 표시/DDL용이고 id가 있으면 id가 기준 (normalize에서 이름을 id에 맞춤)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnPair {
+    pub from: String,
+    pub to: Option<String>, // FK 속성 이름 (자동 생성될 수 있음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_id: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub generated: bool, // 관계를 만들 때 자동 생성한 FK 속성 (관계를 지우면 같이 삭제)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Relation {
     pub id: String,
     pub from_entity_id: String,
-    pub from_attribute: String,
+    #[serde(default)]
+    pub from_attribute: String, // 첫 번째 컬럼 쌍 (단일 컬럼 형식 호환용)
     pub to_entity_id: String,
-    pub to_attribute: Option<String>, // FK 속성 이름 (자동 생성될 수 있음)
+    #[serde(default)]
+    pub to_attribute: Option<String>, // 첫 번째 컬럼 쌍의 FK 속성
+    #[serde(default)]
+    pub columns: Vec<ColumnPair>, // 복합 키를 위한 순서 있는 컬럼 쌍
     pub cardinality: Cardinality,
     pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_multiplicity: Option<Multiplicity>, // 부모 쪽 (없으면 cardinality에서 도출)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_multiplicity: Option<Multiplicity>, // 자식 쪽
+    #[serde(default = "default_true")]
+    pub is_identifying: bool, // 식별 관계 (FK가 자식의 PK에 포함)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErdDiagram {
     #[serde(default)]
-    pub entities: HashMap<String, Entity>,
-    #[serde(default)]
-    pub relations: Vec<Relation>,
-    #[serde(default = "default_canvas_width")]
+    pub format_version: u32, // 저장 형식 버전 (파일을 열 때 file_format에서 현재 버전으로 올림)
+    #[serde(default, serialize_with = "serialize_sorted_entities")]
+    pub entities: HashMap<String, Entity>, // 저장할 때는 id 순
+    #[serde(default, serialize_with = "serialize_sorted_relations")]
+    pub relations: Vec<Relation>, // 저장할 때는 id 순 (속성/인덱스 순서는 의미가 있어 그대로 둠)
+    #[serde(default = "default_canvas_width", serialize_with = "serialize_rounded")]
     pub canvas_width: f64,
-    #[serde(default = "default_canvas_height")]
+    #[serde(default = "default_canvas_height", serialize_with = "serialize_rounded")]
     pub canvas_height: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub naming_policy: Option<NamingPolicy>, // 다이어그램 전용 명명 규칙 (없으면 프로젝트 설정)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_types: Vec<UserType>, // 열거형/도메인 (Attribute::data_type에서 이름으로 참조)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hierarchies: Vec<SubtypeHierarchy>, // 슈퍼타입/서브타입 계층
+}
+
+// 슈퍼타입/서브타입 계층을 테이블로 옮기는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SubtypeTransform {
+    #[default]
+    TablePerType, // 슈퍼타입/서브타입마다 테이블, 서브타입 PK가 슈퍼타입 PK를 참조
+    SingleTable, // 슈퍼타입 테이블 하나에 서브타입 속성을 합침
+    TablePerConcreteClass, // 서브타입 테이블마다 슈퍼타입 속성을 복사 (완전 계층이면 슈퍼타입 테이블 없음)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subtype {
+    pub entity_id: String,
+    #[serde(default)]
+    pub discriminator_value: Option<String>, // 구분자 값 (없으면 서브타입 물리명)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtypeHierarchy {
+    pub id: String,
+    pub supertype_id: String,
+    pub subtypes: Vec<Subtype>,
+    #[serde(default = "default_true")]
+    pub is_exclusive: bool, // 배타적: 슈퍼타입 인스턴스 하나가 서브타입 하나에만 속함 (아니면 포괄적)
+    #[serde(default)]
+    pub is_complete: bool, // 완전: 모든 슈퍼타입 인스턴스가 어느 서브타입에 속함
+    #[serde(default)]
+    pub discriminator: Option<String>, // 서브타입을 구분하는 슈퍼타입 속성 물리명
+    #[serde(default)]
+    pub transform: SubtypeTransform,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum UserType {
+    Enum(EnumType),
+    Domain(DomainType),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumType {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainType {
+    pub name: String,
+    pub base_type: String,
+    #[serde(default)]
+    pub length: Option<String>,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub check: Option<String>, // 값은 VALUE로 가리킴 (예: "VALUE <> ''")
+}
+
+impl IndexMethod {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IndexMethod::BTree => "btree",
+            IndexMethod::Hash => "hash",
+            IndexMethod::Gin => "gin",
+        }
+    }
+}
+
+impl Index {
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.name.clone()).collect()
+    }
+
+    // "a, b DESC"
+    pub fn column_list(&self) -> String {
+        self.columns
+            .iter()
+            .map(|c| match c.order {
+                SortOrder::Asc => c.name.clone(),
+                SortOrder::Desc => format!("{} DESC", c.name),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Multiplicity {
+    pub const ZERO_OR_ONE: Multiplicity = Multiplicity { min: 0, max: Some(1) };
+    pub const EXACTLY_ONE: Multiplicity = Multiplicity { min: 1, max: Some(1) };
+    pub const ZERO_OR_MANY: Multiplicity = Multiplicity { min: 0, max: None };
+    pub const ONE_OR_MANY: Multiplicity = Multiplicity { min: 1, max: None };
+
+    pub fn is_many(&self) -> bool {
+        !matches!(self.max, Some(max) if max <= 1)
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.min == 0
+    }
+
+    // "0..1", "1", "0..N", "1..N"
+    pub fn label(&self) -> String {
+        let max = match self.max {
+            Some(max) if max <= 1 => return if self.min == 0 { "0..1".to_string() } else { "1".to_string() },
+            Some(max) => max.to_string(),
+            None => "N".to_string(),
+        };
+        format!("{}..{}", self.min, max)
+    }
+
+    // Mermaid 까마귀발 기호 (왼쪽 끝은 "|o", 오른쪽 끝은 "o|" 방향)
+    pub fn mermaid_symbol(&self, left: bool) -> &'static str {
+        match (self.is_optional(), self.is_many(), left) {
+            (true, false, true) => "|o",
+            (true, false, false) => "o|",
+            (false, false, _) => "||",
+            (true, true, true) => "}o",
+            (true, true, false) => "o{",
+            (false, true, true) => "}|",
+            (false, true, false) => "|{",
+        }
+    }
+}
+
+impl EntityKind {
+    pub fn is_table(&self) -> bool {
+        *self == EntityKind::Table
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntityKind::Table => "TABLE",
+            EntityKind::View => "VIEW",
+            EntityKind::MaterializedView => "MATERIALIZED VIEW",
+        }
+    }
+}
+
+impl SubtypeTransform {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubtypeTransform::TablePerType => "타입별 테이블",
+            SubtypeTransform::SingleTable => "단일 테이블",
+            SubtypeTransform::TablePerConcreteClass => "구체 타입별 테이블",
+        }
+    }
+}
+
+impl SubtypeHierarchy {
+    // "배타적, 완전"
+    pub fn label(&self) -> String {
+        format!(
+            "{}, {}",
+            if self.is_exclusive { "배타적" } else { "포괄적" },
+            if self.is_complete { "완전" } else { "불완전" }
+        )
+    }
+
+    pub fn discriminator_name(&self) -> Option<&str> {
+        self.discriminator.as_deref().map(str::trim).filter(|d| !d.is_empty())
+    }
+}
+
+impl Subtype {
+    pub fn value(&self, entity: &Entity) -> String {
+        match self.discriminator_value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => value.to_string(),
+            None => entity.physical_name.clone(),
+        }
+    }
+}
+
+impl Cardinality {
+    // 양 끝의 최대 참여 수로 정한 카디널리티 (부모 쪽만 여러 개이면 FK 방향과 맞지 않아 None)
+    pub fn from_multiplicities(from: Multiplicity, to: Multiplicity) -> Option<Cardinality> {
+        match (from.is_many(), to.is_many()) {
+            (false, false) => Some(Cardinality::OneToOne),
+            (false, true) => Some(Cardinality::OneToMany),
+            (true, true) => Some(Cardinality::ManyToMany),
+            (true, false) => None,
+        }
+    }
+}
+
+impl Relation {
+    // 지정하지 않은 끝은 카디널리티의 기본값 (1:1은 1—1, 1:N은 1—0..N, N:M은 0..N—0..N)
+    pub fn multiplicities(&self) -> (Multiplicity, Multiplicity) {
+        let (from, to) = match self.cardinality {
+            Cardinality::OneToOne => (Multiplicity::EXACTLY_ONE, Multiplicity::EXACTLY_ONE),
+            Cardinality::OneToMany => (Multiplicity::EXACTLY_ONE, Multiplicity::ZERO_OR_MANY),
+            Cardinality::ManyToMany => (Multiplicity::ZERO_OR_MANY, Multiplicity::ZERO_OR_MANY),
+        };
+        (self.from_multiplicity.unwrap_or(from), self.to_multiplicity.unwrap_or(to))
+    }
+
+    // "1 — 0..N" (비식별 관계면 ", 비식별")
+    pub fn multiplicity_label(&self) -> String {
+        let (from, to) = self.multiplicities();
+        let mut label = format!("{} — {}", from.label(), to.label());
+        if !self.is_identifying {
+            label.push_str(", 비식별");
+        }
+        label
+    }
+
+    // 순서 있는 컬럼 쌍. 단일 컬럼이면 from_attribute/to_attribute가 우선
+    // (단일 컬럼 형식만 아는 편집기가 수정한 값을 따르기 위함, 같은 컬럼이면 id 유지)
+    pub fn pairs(&self) -> Vec<ColumnPair> {
+        if self.columns.len() <= 1 && !self.from_attribute.trim().is_empty() {
+            let mut pair = ColumnPair::new(self.from_attribute.clone(), self.to_attribute.clone());
+            if let Some(first) = self.columns.first() {
+                if first.from.eq_ignore_ascii_case(&pair.from) {
+                    pair.from_id = first.from_id.clone();
+                }
+                if matches!((&first.to, &pair.to), (Some(a), Some(b)) if a.eq_ignore_ascii_case(b)) {
+                    pair.to_id = first.to_id.clone();
+                    pair.generated = first.generated;
+                }
+            }
+            return vec![pair];
+        }
+        self.columns.clone()
+    }
+
+    // 컬럼 쌍을 바꾸고 단일 컬럼 필드도 첫 번째 쌍으로 맞춤
+    pub fn set_pairs(&mut self, pairs: Vec<ColumnPair>) {
+        self.from_attribute = pairs.first().map(|p| p.from.clone()).unwrap_or_default();
+        self.to_attribute = pairs.first().and_then(|p| p.to.clone());
+        self.columns = pairs;
+    }
+
+    // FK 컬럼이 모두 지정된 경우의 (부모 컬럼, 자식 컬럼) 목록
+    pub fn foreign_key_columns(&self) -> Option<(Vec<String>, Vec<String>)> {
+        let pairs = self.pairs();
+        if pairs.is_empty() {
+            return None;
+        }
+        let mut from = Vec::new();
+        let mut to = Vec::new();
+        for pair in pairs {
+            let child = pair.to.filter(|c| !c.trim().is_empty())?;
+            if pair.from.trim().is_empty() {
+                return None;
+            }
+            from.push(pair.from);
+            to.push(child);
+        }
+        Some((from, to))
+    }
+}
+
+// normalize 옵션
+#[derive(Debug, Clone, Default)]
+pub struct NormalizeOptions {
+    pub romanize: bool, // 물리명이 비어 있으면 한글 논리명을 로마자로 표기해 채움
+}
+
+impl ColumnPair {
+    // 이름만 정한 쌍 (id는 link_attributes에서 채움)
+    pub fn new(from: String, to: Option<String>) -> Self {
+        ColumnPair { from, to, from_id: None, to_id: None, generated: false }
+    }
+}
+
+impl Attribute {
+    pub fn new(physical_name: &str, data_type: &str) -> Self {
+        Attribute {
+            id: String::new(),
+            logical_name: physical_name.to_string(),
+            physical_name: physical_name.to_string(),
+            data_type: data_type.to_string(),
+            length: None,
+            default_value: None,
+            is_primary_key: false,
+            is_nullable: true,
+            is_foreign_key: false,
+            is_unique: false,
+            is_auto_increment: false,
+            foreign_key_reference: None,
+            remark: None,
+            check: None,
+        }
+    }
+}
+
+impl Entity {
+    pub fn new(id: &str, physical_name: &str) -> Self {
+        Entity {
+            id: id.to_string(),
+            logical_name: physical_name.to_string(),
+            physical_name: physical_name.to_string(),
+            schema: None,
+            x: default_pos_x(),
+            y: default_pos_y(),
+            width: default_width(),
+            height: default_height(),
+            attributes: Vec::new(),
+            indexes: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+            resolved_relation: None,
+            kind: EntityKind::Table,
+            view_definition: None,
+            view_dependencies: Vec::new(),
+        }
+    }
+
+    pub fn is_view(&self) -> bool {
+        !self.kind.is_table()
+    }
+
+    // 끝의 세미콜론과 공백을 뺀 뷰 정의 (비어 있으면 None)
+    pub fn view_query(&self) -> Option<&str> {
+        self.view_definition
+            .as_deref()
+            .map(|d| d.trim().trim_end_matches(';').trim_end())
+            .filter(|d| !d.is_empty())
+    }
+
+    // 공백이 아닌 스키마 이름
+    pub fn schema_name(&self) -> Option<&str> {
+        self.schema.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    // "스키마.물리명" (스키마가 없으면 물리명, 점이 들어간 이름은 큰따옴표로 감쌈)
+    pub fn qualified_name(&self) -> String {
+        sql::qualify(self.schema_name(), &self.physical_name)
+    }
+}
+
+impl Default for ErdDiagram {
+    fn default() -> Self {
+        ErdDiagram {
+            format_version: file_format::FORMAT_VERSION,
+            entities: HashMap::new(),
+            relations: Vec::new(),
+            canvas_width: default_canvas_width(),
+            canvas_height: default_canvas_height(),
+            naming_policy: None,
+            user_types: Vec::new(),
+            hierarchies: Vec::new(),
+        }
+    }
+}
+
+impl UserType {
+    pub fn name(&self) -> &str {
+        match self {
+            UserType::Enum(e) => &e.name,
+            UserType::Domain(d) => &d.name,
+        }
+    }
+
+    // 문서/비교용 정의 ("ENUM ('A', 'B')", "VARCHAR(10) DEFAULT 'A' CHECK (...)")
+    pub fn definition(&self) -> String {
+        match self {
+            UserType::Enum(e) => format!("ENUM ({})", sql::quote_literals(&e.values)),
+            UserType::Domain(d) => {
+                let mut text = match d.length.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+                    Some(length) => format!("{}({})", d.base_type, length),
+                    None => d.base_type.clone(),
+                };
+                if let Some(default) = d.default_value.as_deref().filter(|v| !v.trim().is_empty()) {
+                    text.push_str(&format!(" DEFAULT {}", default));
+                }
+                if let Some(check) = d.check.as_deref().filter(|c| !c.trim().is_empty()) {
+                    text.push_str(&format!(" CHECK ({})", check));
+                }
+                text
+            }
+        }
+    }
 }
 
 impl ErdDiagram {
-    // 저장 포맷 변경에 따른 후처리/마이그레이션
-    pub fn normalize(&mut self) {
+    // data_type이 등록된 사용자 정의 타입을 가리키면 반환 (대소문자 무시)
+    pub fn user_type(&self, data_type: &str) -> Option<&UserType> {
+        let data_type = data_type.trim();
+        self.user_types.iter().find(|t| t.name().eq_ignore_ascii_case(data_type))
+    }
+
+    // 물리명(대소문자 무시)으로 엔티티 id 찾기. "스키마.물리명"이면 스키마도 일치해야 하고,
+    // 스키마 없이 찾으면 스키마 없는 엔티티를 우선
+    pub fn entity_id_by_physical_name(&self, physical_name: &str) -> Option<String> {
+        let (schema, name) = sql::split_table(physical_name);
+        let mut candidates = self.entities.values().filter(|e| e.physical_name.eq_ignore_ascii_case(name));
+        match schema {
+            Some(schema) => candidates.find(|e| e.schema_name().is_some_and(|s| s.eq_ignore_ascii_case(schema))),
+            None => {
+                let candidates: Vec<&Entity> = candidates.collect();
+                candidates.iter().find(|e| e.schema_name().is_none()).or(candidates.first()).copied()
+            }
+        }
+        .map(|e| e.id.clone())
+    }
+
+    // 가져오기로 생성된 엔티티를 물리명 순서대로 격자 배치
+    pub fn arrange_grid(&mut self) {
+        let mut ids: Vec<(String, String)> = self.entities
+            .values()
+            .map(|e| (e.physical_name.to_lowercase(), e.id.clone()))
+            .collect();
+        ids.sort();
+
+        let columns = (ids.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let mut row_height = 0.0_f64;
+        let mut y = default_pos_y();
+        for (index, (_, id)) in ids.iter().enumerate() {
+            if index > 0 && index % columns == 0 {
+                y += row_height + 80.0;
+                row_height = 0.0;
+            }
+            if let Some(entity) = self.entities.get_mut(id) {
+                // 속성 수에 맞춰 대략적인 높이 산정 (캔버스가 다시 계산함)
+                entity.height = entity.height.max(40.0 + 22.0 * entity.attributes.len() as f64);
+                entity.x = default_pos_x() + (index % columns) as f64 * 280.0;
+                entity.y = y;
+                row_height = row_height.max(entity.height);
+            }
+        }
+
+        let right = self.entities.values().map(|e| e.x + e.width + 100.0).fold(0.0, f64::max);
+        let bottom = self.entities.values().map(|e| e.y + e.height + 100.0).fold(0.0, f64::max);
+        self.canvas_width = self.canvas_width.max(right);
+        self.canvas_height = self.canvas_height.max(bottom);
+    }
+
+    // 저장 포맷 변경에 따른 후처리/마이그레이션. 찾을 수 없는 관계 속성 참조를 반환
+    pub fn normalize(&mut self) -> Vec<String> {
+        self.normalize_with(&NormalizeOptions::default())
+    }
+
+    pub fn normalize_with(&mut self, options: &NormalizeOptions) -> Vec<String> {
         for (_id, entity) in self.entities.iter_mut() {
-            if entity.physical_name.trim().is_empty() {
-                entity.physical_name = sanitize_physical(&entity.logical_name);
+            if is_blank_physical(&entity.physical_name) {
+                let generated = sanitize_physical(&entity.logical_name, options.romanize);
+                if !generated.is_empty() || entity.physical_name.trim().is_empty() {
+                    entity.physical_name = generated;
+                }
             }
             if entity.width <= 0.0 { entity.width = default_width(); }
             if entity.height <= 0.0 { entity.height = default_height(); }
             if entity.x.is_nan() { entity.x = default_pos_x(); }
             if entity.y.is_nan() { entity.y = default_pos_y(); }
+            entity.schema = entity.schema_name().map(str::to_string);
 
             for attr in entity.attributes.iter_mut() {
-                if attr.physical_name.trim().is_empty() {
-                    attr.physical_name = sanitize_physical(&attr.logical_name);
+                if is_blank_physical(&attr.physical_name) {
+                    let generated = sanitize_physical(&attr.logical_name, options.romanize);
+                    if !generated.is_empty() || attr.physical_name.trim().is_empty() {
+                        attr.physical_name = generated;
+                    }
+                }
+                // 사용자 정의 타입 참조는 등록된 이름으로 맞추고 길이는 도메인 정의를 따름
+                let data_type = attr.data_type.trim();
+                if let Some(user_type) = self.user_types.iter().find(|t| t.name().eq_ignore_ascii_case(data_type)) {
+                    attr.data_type = user_type.name().to_string();
+                    attr.length = None;
                 }
             }
         }
+        // 단일 컬럼 형식(from_attribute/to_attribute)을 columns로 옮기고 이름 참조를 id로 연결
+        let warnings = self.link_attributes();
+        for relation in self.relations.iter_mut() {
+            // 최소가 최대보다 크면 최대로 맞추고, 카디널리티는 양 끝의 최대에 따름
+            for multiplicity in [&mut relation.from_multiplicity, &mut relation.to_multiplicity].into_iter().flatten() {
+                if let Some(max) = multiplicity.max {
+                    multiplicity.min = multiplicity.min.min(max);
+                }
+            }
+            if relation.from_multiplicity.is_some() || relation.to_multiplicity.is_some() {
+                let (from, to) = relation.multiplicities();
+                if let Some(cardinality) = Cardinality::from_multiplicities(from, to) {
+                    relation.cardinality = cardinality;
+                }
+            }
+        }
+        for hierarchy in self.hierarchies.iter_mut() {
+            hierarchy.discriminator = hierarchy.discriminator_name().map(str::to_string);
+        }
         if self.canvas_width <= 0.0 { self.canvas_width = default_canvas_width(); }
         if self.canvas_height <= 0.0 { self.canvas_height = default_canvas_height(); }
+        warnings
+    }
+
+    // 빈 속성 id를 채우고, 관계의 컬럼 쌍은 id가 가리키는 속성의 현재 이름으로 맞춤
+    // id가 없거나 가리키는 속성이 없으면 이름으로 찾아 id를 채우고, 둘 다 안 되면 보고
+    pub fn link_attributes(&mut self) -> Vec<String> {
+        for entity in self.entities.values_mut() {
+            assign_attribute_ids(entity);
+        }
+
+        let mut warnings = Vec::new();
+        for relation in self.relations.iter_mut() {
+            let (Some(parent), Some(child)) = (self.entities.get(&relation.from_entity_id), self.entities.get(&relation.to_entity_id))
+            else {
+                continue;
+            };
+            let mut pairs = relation.pairs();
+            for pair in pairs.iter_mut() {
+                if let Some(attr) = link_attribute(parent, &pair.from, &mut pair.from_id) {
+                    pair.from = attr.physical_name.clone();
+                } else {
+                    warnings.push(unresolved_reference(relation, parent, Some(&pair.from), &pair.from_id));
+                }
+                if pair.to.is_none() && pair.to_id.is_none() {
+                    continue; // 아직 FK 컬럼을 만들지 않은 쌍
+                }
+                let name = pair.to.clone().unwrap_or_default();
+                if let Some(attr) = link_attribute(child, &name, &mut pair.to_id) {
+                    pair.to = Some(attr.physical_name.clone());
+                } else {
+                    warnings.push(unresolved_reference(relation, child, pair.to.as_deref(), &pair.to_id));
+                }
+            }
+            relation.set_pairs(pairs);
+        }
+        warnings
     }
 
     pub fn to_markdown(&self) -> String {
         let mut markdown = String::new();
         
         markdown.push_str("# ERD Diagram\n\n");
+
+        if !self.user_types.is_empty() {
+            markdown.push_str("## Types\n\n");
+            markdown.push_str("| Name | Kind | Definition |\n");
+            markdown.push_str("|------|------|------------|\n");
+            for user_type in &self.user_types {
+                let kind = match user_type {
+                    UserType::Enum(_) => "ENUM",
+                    UserType::Domain(_) => "DOMAIN",
+                };
+                markdown.push_str(&format!("| {} | {} | {} |\n", user_type.name(), kind, user_type.definition().replace('|', "\\|")));
+            }
+            markdown.push('\n');
+        }
         
-        if !self.entities.is_empty() {
-            markdown.push_str("## Entities\n\n");
-            
-            for entity in self.entities.values() {
-                markdown.push_str(&format!("### {} ({})\n", entity.logical_name, entity.physical_name));
-                markdown.push_str(&format!("**논리명**: {} | **물리명**: {}\n\n", entity.logical_name, entity.physical_name));
-                
-                if !entity.attributes.is_empty() {
-                    markdown.push_str("| Attribute | Logical Name | Physical Name | Type | Default | Constraints |\n");
-                    markdown.push_str("|-----------|--------------|---------------|------|---------|-------------|\n");
-                    
-                    for attr in &entity.attributes {
-                        let mut constraints = Vec::new();
-                        if attr.is_primary_key {
-                            constraints.push("PK");
-                        }
-                        if attr.is_foreign_key {
-                            constraints.push("FK");
-                        }
-                        if attr.is_unique {
-                            constraints.push("UNIQUE");
-                        }
-                        if attr.is_auto_increment {
-                            constraints.push("AUTO_INCREMENT");
-                        }
-                        if !attr.is_nullable {
-                            constraints.push("NOT NULL");
-                        }
-                        
-                        let constraints_str = if constraints.is_empty() {
-                            "".to_string()
-                        } else {
-                            constraints.join(", ")
-                        };
-                        
-                        let type_display = if let Some(ref length) = attr.length {
-                            format!("{}({})", attr.data_type, length)
-                        } else {
-                            attr.data_type.clone()
-                        };
-                        
-                        let default_display = attr.default_value.as_deref().unwrap_or("-");
-                        
-                        markdown.push_str(&format!(
-                            "| {} | {} | {} | {} | {} | {} |\n",
-                            attr.logical_name, attr.logical_name, attr.physical_name, type_display, default_display, constraints_str
-                        ));
-                    }
-                    markdown.push('\n');
+        let tables: Vec<&Entity> = self.entities.values().filter(|e| !e.is_view()).collect();
+        if !tables.is_empty() {
+            // 스키마별로 묶어서 출력 (스키마 없는 엔티티가 먼저)
+            let mut schemas: Vec<Option<&str>> = tables.iter().map(|e| e.schema_name()).collect();
+            schemas.sort();
+            schemas.dedup();
+
+            for schema in schemas {
+                match schema {
+                    Some(schema) => markdown.push_str(&format!("## Entities ({})\n\n", schema)),
+                    None => markdown.push_str("## Entities\n\n"),
+                }
+                for entity in tables.iter().filter(|e| e.schema_name() == schema) {
+                    markdown.push_str(&Self::entity_markdown(entity));
                 }
             }
         }
+
+        // 뷰는 테이블과 따로 정의 SQL, 참조 테이블과 함께 출력
+        let mut views: Vec<&Entity> = self.entities.values().filter(|e| e.is_view()).collect();
+        if !views.is_empty() {
+            views.sort_by_key(|e| e.qualified_name().to_lowercase());
+            markdown.push_str("## Views\n\n");
+            for view in views {
+                markdown.push_str(&self.view_markdown(view));
+            }
+        }
         
         if !self.relations.is_empty() {
             markdown.push_str("## Relations\n\n");
@@ -169,16 +769,155 @@ impl ErdDiagram {
                     };
                     
                     markdown.push_str(&format!(
-                        "- {} ({}) → {} ({})\n",
+                        "- {} ({}) → {} ({})",
                         from.logical_name, cardinality_str, to.logical_name, relation.name
                     ));
+                    let pairs = relation.pairs();
+                    if !pairs.is_empty() {
+                        let (from_columns, to_columns) = pair_lists(&pairs);
+                        markdown.push_str(&format!(": {} → {}", from_columns, to_columns));
+                    }
+                    if relation.from_multiplicity.is_some() || relation.to_multiplicity.is_some() || !relation.is_identifying {
+                        markdown.push_str(&format!(" [{}]", relation.multiplicity_label()));
+                    }
+                    markdown.push('\n');
+                }
+            }
+        }
+
+        if !self.hierarchies.is_empty() {
+            markdown.push_str("\n## Subtypes\n\n");
+
+            for hierarchy in &self.hierarchies {
+                let Some(supertype) = self.entities.get(&hierarchy.supertype_id) else { continue };
+                let discriminator = hierarchy.discriminator_name();
+                let subtypes: Vec<String> = hierarchy
+                    .subtypes
+                    .iter()
+                    .filter_map(|s| self.entities.get(&s.entity_id).map(|e| (s, e)))
+                    .map(|(s, e)| match discriminator {
+                        Some(_) => format!("{} ('{}')", e.logical_name, s.value(e)),
+                        None => e.logical_name.clone(),
+                    })
+                    .collect();
+                markdown.push_str(&format!("- {} ⊃ {} [{}", supertype.logical_name, subtypes.join(", "), hierarchy.label()));
+                if let Some(discriminator) = discriminator {
+                    markdown.push_str(&format!(", 구분자: {}", discriminator));
                 }
+                markdown.push_str(&format!(", 변환: {}]\n", hierarchy.transform.label()));
             }
         }
         
         markdown
     }
     
+    fn view_markdown(&self, view: &Entity) -> String {
+        let mut markdown = Self::entity_markdown(view);
+        markdown.push_str(&format!("**종류**: {}\n\n", view.kind.label()));
+        let dependencies: Vec<String> = view
+            .view_dependencies
+            .iter()
+            .filter_map(|id| self.entities.get(id))
+            .map(|e| e.qualified_name())
+            .collect();
+        if !dependencies.is_empty() {
+            markdown.push_str(&format!("**참조**: {}\n\n", dependencies.join(", ")));
+        }
+        if let Some(query) = view.view_query() {
+            markdown.push_str(&format!("```sql\n{}\n```\n\n", query));
+        }
+        markdown
+    }
+
+    fn entity_markdown(entity: &Entity) -> String {
+        let mut markdown = String::new();
+        markdown.push_str(&format!("### {} ({})\n", entity.logical_name, entity.physical_name));
+        markdown.push_str(&format!("**논리명**: {} | **물리명**: {}\n\n", entity.logical_name, entity.physical_name));
+        
+        if !entity.attributes.is_empty() {
+            markdown.push_str("| Attribute | Logical Name | Physical Name | Type | Default | Constraints |\n");
+            markdown.push_str("|-----------|--------------|---------------|------|---------|-------------|\n");
+            
+            for attr in &entity.attributes {
+                let check = attr
+                    .check
+                    .as_deref()
+                    .filter(|c| !c.trim().is_empty())
+                    .map(|c| format!("CHECK ({})", c.replace('|', "\\|")));
+                let mut constraints = Vec::new();
+                if attr.is_primary_key {
+                    constraints.push("PK");
+                }
+                if attr.is_foreign_key {
+                    constraints.push("FK");
+                }
+                if attr.is_unique {
+                    constraints.push("UNIQUE");
+                }
+                if attr.is_auto_increment {
+                    constraints.push("AUTO_INCREMENT");
+                }
+                if !attr.is_nullable {
+                    constraints.push("NOT NULL");
+                }
+                if let Some(ref check) = check {
+                    constraints.push(check);
+                }
+                
+                let constraints_str = if constraints.is_empty() {
+                    "".to_string()
+                } else {
+                    constraints.join(", ")
+                };
+                
+                let type_display = if let Some(ref length) = attr.length {
+                    format!("{}({})", attr.data_type, length)
+                } else {
+                    attr.data_type.clone()
+                };
+                
+                let default_display = attr.default_value.as_deref().unwrap_or("-");
+                
+                markdown.push_str(&format!(
+                    "| {} | {} | {} | {} | {} | {} |\n",
+                    attr.logical_name, attr.logical_name, attr.physical_name, type_display, default_display, constraints_str
+                ));
+            }
+            markdown.push('\n');
+        }
+
+        if !entity.indexes.is_empty() {
+            markdown.push_str("**인덱스**\n\n");
+            markdown.push_str("| Name | Columns | Unique | Method | Where |\n");
+            markdown.push_str("|------|---------|--------|--------|-------|\n");
+            for index in &entity.indexes {
+                markdown.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    index.name,
+                    index.column_list(),
+                    if index.is_unique { "Y" } else { "" },
+                    index.method.label(),
+                    index.predicate.as_deref().unwrap_or("-")
+                ));
+            }
+            markdown.push('\n');
+        }
+
+        if !entity.unique_constraints.is_empty() || !entity.check_constraints.is_empty() {
+            markdown.push_str("**제약조건**\n\n");
+            markdown.push_str("| Name | Type | Definition |\n");
+            markdown.push_str("|------|------|------------|\n");
+            for unique in &entity.unique_constraints {
+                markdown.push_str(&format!("| {} | UNIQUE | ({}) |\n", unique.name, unique.columns.join(", ")));
+            }
+            for check in &entity.check_constraints {
+                markdown.push_str(&format!("| {} | CHECK | {} |\n", check.name, check.expression.replace('|', "\\|")));
+            }
+            markdown.push('\n');
+        }
+        markdown
+    }
+
     fn sanitize_name(name: &str) -> String {
         // 한글과 영문, 숫자를 모두 허용하되 공백과 특수문자만 언더스코어로 변환
         let result = name.chars()
@@ -202,6 +941,14 @@ impl ErdDiagram {
         }
     }
     
+    // 스키마가 있으면 "스키마_논리명"으로 구분
+    fn mermaid_entity_name(entity: &Entity) -> String {
+        match entity.schema_name() {
+            Some(schema) => format!("{}_{}", Self::sanitize_name(schema), Self::sanitize_name(&entity.logical_name)),
+            None => Self::sanitize_name(&entity.logical_name),
+        }
+    }
+
     pub fn to_mermaid(&self) -> String {
         let mut mermaid = String::new();
         
@@ -209,11 +956,25 @@ impl ErdDiagram {
         
         // 엔티티 정의 먼저 - 결정적 순서(논리명 기준)
         let mut entities_sorted: Vec<&Entity> = self.entities.values().collect();
-        entities_sorted.sort_by(|a, b| a.logical_name.to_lowercase().cmp(&b.logical_name.to_lowercase()));
+        entities_sorted.sort_by(|a, b| {
+            a.schema_name()
+                .cmp(&b.schema_name())
+                .then_with(|| a.logical_name.to_lowercase().cmp(&b.logical_name.to_lowercase()))
+        });
 
         for entity in entities_sorted {
-            let entity_name = Self::sanitize_name(&entity.logical_name);
-            mermaid.push_str(&format!("    {} {{\n", entity_name));
+            let entity_name = Self::mermaid_entity_name(entity);
+            // 스키마가 있으면 별칭으로 "스키마.논리명", 뷰는 앞에 «view» 표시
+            let logical_name = entity.logical_name.replace('"', "'");
+            let mut label = entity.schema_name().map(|schema| format!("{}.{}", schema, logical_name));
+            if entity.is_view() {
+                let name = label.unwrap_or(logical_name);
+                label = Some(format!("«{}» {}", entity.kind.label().to_lowercase(), name));
+            }
+            match label {
+                Some(label) => mermaid.push_str(&format!("    {}[\"{}\"] {{\n", entity_name, label)),
+                None => mermaid.push_str(&format!("    {} {{\n", entity_name)),
+            }
             
             // 속성도 결정적 순서: PK -> FK -> 기타, 그 다음 논리명
             let mut attrs_sorted = entity.attributes.clone();
@@ -271,16 +1032,25 @@ impl ErdDiagram {
             let to_entity = self.entities.get(&relation.to_entity_id);
             
             if let (Some(from), Some(to)) = (from_entity, to_entity) {
-                let from_name = Self::sanitize_name(&from.logical_name);
-                let to_name = Self::sanitize_name(&to.logical_name);
-                let relation_name = Self::sanitize_name(&relation.name);
-                
-                let symbol = match relation.cardinality {
-                    Cardinality::OneToOne => "||--||",
-                    Cardinality::OneToMany => "||--o{",
-                    Cardinality::ManyToMany => "}o--o{",
+                let from_name = Self::mermaid_entity_name(from);
+                let to_name = Self::mermaid_entity_name(to);
+                // 복합 키 관계는 라벨에 FK 컬럼 목록을 함께 표시
+                let pairs = relation.pairs();
+                let relation_name = if pairs.len() > 1 {
+                    format!("\"{} {}\"", Self::sanitize_name(&relation.name), pair_lists(&pairs).1)
+                } else {
+                    Self::sanitize_name(&relation.name)
                 };
                 
+                // 비식별 관계는 점선
+                let (from_end, to_end) = relation.multiplicities();
+                let symbol = format!(
+                    "{}{}{}",
+                    from_end.mermaid_symbol(true),
+                    if relation.is_identifying { "--" } else { ".." },
+                    to_end.mermaid_symbol(false)
+                );
+                
                 mermaid.push_str(&format!(
                     "    {} {} {} : {}\n",
                     from_name, 
@@ -291,14 +1061,102 @@ impl ErdDiagram {
             }
         }
         
+        // 뷰가 조회하는 테이블은 점선으로 연결
+        let mut views: Vec<&Entity> = self.entities.values().filter(|e| e.is_view()).collect();
+        views.sort_by_key(|e| e.qualified_name().to_lowercase());
+        for view in views {
+            for base in view.view_dependencies.iter().filter_map(|id| self.entities.get(id)) {
+                mermaid.push_str(&format!(
+                    "    {} }}o..|| {} : \"참조\"\n",
+                    Self::mermaid_entity_name(view),
+                    Self::mermaid_entity_name(base)
+                ));
+            }
+        }
+
+        // 서브타입은 슈퍼타입과의 1:0..1 식별 관계로 표시
+        for hierarchy in &self.hierarchies {
+            let Some(supertype) = self.entities.get(&hierarchy.supertype_id) else { continue };
+            for subtype in hierarchy.subtypes.iter().filter_map(|s| self.entities.get(&s.entity_id)) {
+                mermaid.push_str(&format!(
+                    "    {} ||--o| {} : \"서브타입 ({})\"\n",
+                    Self::mermaid_entity_name(supertype),
+                    Self::mermaid_entity_name(subtype),
+                    hierarchy.label()
+                ));
+            }
+        }
+        
         mermaid.push_str("```\n");
         mermaid
     }
+
+    pub fn to_sql(&self, dialect: SqlDialect) -> String {
+        sql::create_script(self, dialect.dialect())
+    }
+}
+
+// 컬럼 쌍을 "(a, b)" 형태의 부모/자식 컬럼 목록으로 (단일 컬럼이면 괄호 없이)
+pub fn pair_lists(pairs: &[ColumnPair]) -> (String, String) {
+    let from: Vec<&str> = pairs.iter().map(|p| p.from.as_str()).collect();
+    let to: Vec<&str> = pairs.iter().map(|p| p.to.as_deref().unwrap_or("?")).collect();
+    let list = |columns: Vec<&str>| {
+        if columns.len() == 1 { columns[0].to_string() } else { format!("({})", columns.join(", ")) }
+    };
+    (list(from), list(to))
 }
 
-fn sanitize_physical(name: &str) -> String {
-    // 물리명 기본 생성: 소문자, 공백/특수문자 -> '_'
-    let mut s = name.to_lowercase();
+// 비었거나 앞의 속성과 겹치는 id는 "attr_물리명"으로 (겹치면 _2, _3 ...)
+fn assign_attribute_ids(entity: &mut Entity) {
+    for i in 0..entity.attributes.len() {
+        let id = entity.attributes[i].id.trim();
+        if !id.is_empty() && !entity.attributes[..i].iter().any(|a| a.id == id) {
+            continue;
+        }
+        let base = match entity.attributes[i].physical_name.trim() {
+            "" => format!("attr_{}", i + 1),
+            name => format!("attr_{}", name.to_lowercase()),
+        };
+        let mut id = base.clone();
+        let mut n = 2;
+        while entity.attributes.iter().any(|a| a.id == id) {
+            id = format!("{}_{}", base, n);
+            n += 1;
+        }
+        entity.attributes[i].id = id;
+    }
+}
+
+// id로 먼저 찾고, 없으면 이름으로 찾아 id를 채움
+fn link_attribute<'a>(entity: &'a Entity, name: &str, id: &mut Option<String>) -> Option<&'a Attribute> {
+    if let Some(attr) = id.as_deref().and_then(|id| entity.attributes.iter().find(|a| a.id == id)) {
+        return Some(attr);
+    }
+    let attr = entity
+        .attributes
+        .iter()
+        .find(|a| !name.trim().is_empty() && a.physical_name.eq_ignore_ascii_case(name.trim()))?;
+    *id = Some(attr.id.clone());
+    Some(attr)
+}
+
+fn unresolved_reference(relation: &Relation, entity: &Entity, name: Option<&str>, id: &Option<String>) -> String {
+    let target = match (name.filter(|n| !n.trim().is_empty()), id) {
+        (Some(name), _) => format!("'{}'", name),
+        (None, Some(id)) => format!("id '{}'", id),
+        (None, None) => "(비어 있음)".to_string(),
+    };
+    format!("관계 '{}': '{}'에서 속성 {}을(를) 찾을 수 없습니다", relation.name, entity.physical_name, target)
+}
+
+// 비어 있거나 '_' 같은 구분자만 남은 물리명
+pub fn is_blank_physical(name: &str) -> bool {
+    !name.chars().any(char::is_alphanumeric)
+}
+
+pub fn sanitize_physical(name: &str, romanize: bool) -> String {
+    // 물리명 기본 생성: 소문자, 공백/특수문자 -> '_' (romanize면 한글을 로마자로 표기)
+    let mut s = if romanize { romanize_words(name) } else { name.to_string() }.to_lowercase();
     s = s.chars()
         .map(|c| match c {
             'a'..='z' | '0'..='9' => c,
@@ -309,10 +1167,247 @@ fn sanitize_physical(name: &str) -> String {
     s.split('_').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("_")
 }
 
+// 한글 덩어리를 음역하고 앞뒤 영문/숫자와는 '_'로 구분 ("회원ID" -> "hoewon_id")
+fn romanize_words(name: &str) -> String {
+    let mut out = String::new();
+    let mut run = String::new();
+    for c in name.chars() {
+        if romanize::is_hangul(c) {
+            if run.is_empty() {
+                out.push('_');
+            }
+            run.push(c);
+            continue;
+        }
+        if !run.is_empty() {
+            out.push_str(&romanize::romanize(&run));
+            out.push('_');
+            run.clear();
+        }
+        out.push(c);
+    }
+    out.push_str(&romanize::romanize(&run));
+    out
+}
+
 fn default_true() -> bool { true }
+fn is_false(value: &bool) -> bool { !value }
+
+// 저장할 때마다 diff가 생기지 않도록 좌표/크기는 소수 둘째 자리까지 (-0은 0으로)
+fn serialize_rounded<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    let rounded = (value * 100.0).round() / 100.0;
+    serializer.serialize_f64(if rounded == 0.0 { 0.0 } else { rounded })
+}
+
+fn serialize_sorted_entities<S: Serializer>(entities: &HashMap<String, Entity>, serializer: S) -> Result<S::Ok, S::Error> {
+    entities.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+fn serialize_sorted_relations<S: Serializer>(relations: &[Relation], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&Relation> = relations.iter().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+    sorted.serialize(serializer)
+}
+
 fn default_width() -> f64 { 150.0 }
 fn default_height() -> f64 { 100.0 }
 fn default_pos_x() -> f64 { 50.0 }
 fn default_pos_y() -> f64 { 50.0 }
 fn default_canvas_width() -> f64 { 1200.0 }
-fn default_canvas_height() -> f64 { 800.0 }
\ No newline at end of file
+fn default_canvas_height() -> f64 { 800.0 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn posts() -> ErdDiagram {
+        crate::sql_import::parse_sql(
+            "CREATE TABLE users (id INT PRIMARY KEY);
+             CREATE TABLE posts (id INT PRIMARY KEY, user_id INT NOT NULL REFERENCES users (id));",
+        )
+        .diagram
+    }
+
+    // 복합 키(shop_id, order_no)로 참조하는 주문 상세
+    fn order_items() -> ErdDiagram {
+        crate::sql_import::parse_sql(
+            "CREATE TABLE orders (shop_id INT, order_no INT, PRIMARY KEY (shop_id, order_no));
+             CREATE TABLE order_items (shop_id INT, order_no INT, line INT, PRIMARY KEY (shop_id, order_no, line),
+               FOREIGN KEY (shop_id, order_no) REFERENCES orders (shop_id, order_no));",
+        )
+        .diagram
+    }
+
+    fn names(pairs: &[ColumnPair]) -> Vec<(&str, Option<&str>)> {
+        pairs.iter().map(|p| (p.from.as_str(), p.to.as_deref())).collect()
+    }
+
+    #[test]
+    fn multiplicity_labels() {
+        assert_eq!(Multiplicity::ZERO_OR_ONE.label(), "0..1");
+        assert_eq!(Multiplicity::EXACTLY_ONE.label(), "1");
+        assert_eq!(Multiplicity::ONE_OR_MANY.label(), "1..N");
+        assert_eq!(Multiplicity { min: 2, max: Some(5) }.label(), "2..5");
+    }
+
+    #[test]
+    fn cardinality_from_multiplicities() {
+        let from = Cardinality::from_multiplicities;
+        assert!(matches!(from(Multiplicity::EXACTLY_ONE, Multiplicity::ZERO_OR_ONE), Some(Cardinality::OneToOne)));
+        assert!(matches!(from(Multiplicity::EXACTLY_ONE, Multiplicity::ONE_OR_MANY), Some(Cardinality::OneToMany)));
+        assert!(matches!(from(Multiplicity::ZERO_OR_MANY, Multiplicity::ONE_OR_MANY), Some(Cardinality::ManyToMany)));
+        assert!(from(Multiplicity::ONE_OR_MANY, Multiplicity::EXACTLY_ONE).is_none());
+    }
+
+    // 지정하지 않으면 카디널리티 기본값, 지정하면 Mermaid 까마귀발에 반영
+    #[test]
+    fn relation_multiplicities_in_mermaid() {
+        // user_id가 PK에 없으므로 비식별 관계로 가져옴
+        let mut diagram = posts();
+        assert_eq!(diagram.relations[0].multiplicity_label(), "1 — 0..N, 비식별");
+        assert!(diagram.to_mermaid().contains("||..o{"));
+
+        diagram.relations[0].to_multiplicity = Some(Multiplicity::ONE_OR_MANY);
+        diagram.relations[0].is_identifying = true;
+        assert_eq!(diagram.relations[0].multiplicity_label(), "1 — 1..N");
+        assert!(diagram.to_mermaid().contains("||--|{"));
+    }
+
+    // 스키마가 다른 같은 이름의 테이블은 Mermaid 엔티티 이름도 달라야 함
+    #[test]
+    fn mermaid_names_include_the_schema() {
+        let diagram = crate::sql_import::parse_sql(
+            "CREATE TABLE sales.orders (id INT PRIMARY KEY);
+             CREATE TABLE hr.orders (id INT PRIMARY KEY);
+             CREATE TABLE \"sales\".\"my.items\" (id INT PRIMARY KEY, order_id INT REFERENCES sales.orders (id));",
+        )
+        .diagram;
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("    sales_orders[\"sales.orders\"] {"));
+        assert!(mermaid.contains("    hr_orders[\"hr.orders\"] {"));
+        assert!(mermaid.contains("    sales_my_items[\"sales.my.items\"] {"));
+        assert!(mermaid.contains("sales_orders |o..o{ sales_my_items"));
+    }
+
+    #[test]
+    fn composite_pairs_keep_their_order() {
+        let mut relation = order_items().relations.remove(0);
+        assert_eq!(names(&relation.pairs()), [("shop_id", Some("shop_id")), ("order_no", Some("order_no"))]);
+        assert_eq!((relation.from_attribute.as_str(), relation.to_attribute.as_deref()), ("shop_id", Some("shop_id")));
+
+        // 단일 컬럼 필드는 첫 번째 쌍을 따름
+        relation.set_pairs(vec![
+            ColumnPair::new("order_no".to_string(), Some("ord_no".to_string())),
+            ColumnPair::new("shop_id".to_string(), None),
+        ]);
+        assert_eq!((relation.from_attribute.as_str(), relation.to_attribute.as_deref()), ("order_no", Some("ord_no")));
+        assert_eq!(names(&relation.pairs()), [("order_no", Some("ord_no")), ("shop_id", None)]);
+        assert!(relation.foreign_key_columns().is_none());
+
+        relation.set_pairs(Vec::new());
+        assert!(relation.from_attribute.is_empty() && relation.to_attribute.is_none());
+        assert!(relation.pairs().is_empty());
+    }
+
+    // 단일 컬럼 형식만 아는 편집기가 바꾼 from_attribute/to_attribute가 우선 (이름이 같으면 id 유지)
+    #[test]
+    fn single_column_fields_override_a_single_pair() {
+        let mut relation = posts().relations.remove(0);
+        assert_eq!(relation.pairs()[0].to_id.as_deref(), Some("attr_user_id"));
+
+        relation.to_attribute = Some("USER_ID".to_string());
+        assert_eq!(relation.pairs()[0].to_id.as_deref(), Some("attr_user_id"));
+
+        relation.to_attribute = Some("author_id".to_string());
+        let pairs = relation.pairs();
+        assert_eq!(names(&pairs), [("id", Some("author_id"))]);
+        assert_eq!(pairs[0].from_id.as_deref(), Some("attr_id"));
+        assert!(pairs[0].to_id.is_none());
+    }
+
+    // columns 없이 저장된 이전 형식 파일을 열면 columns와 id를 채우고, 다시 저장해 열어도 같음
+    #[test]
+    fn legacy_relations_round_trip() {
+        let mut json = serde_json::to_value(posts()).unwrap();
+        let relation = &mut json["relations"][0];
+        relation.as_object_mut().unwrap().remove("columns");
+        assert_eq!(relation["from_attribute"], "id");
+        assert_eq!(relation["to_attribute"], "user_id");
+
+        let mut diagram: ErdDiagram = serde_json::from_value(json).unwrap();
+        assert!(diagram.relations[0].columns.is_empty());
+        assert!(diagram.normalize().is_empty());
+        let pairs = diagram.relations[0].columns.clone();
+        assert_eq!(names(&pairs), [("id", Some("user_id"))]);
+        assert_eq!((pairs[0].from_id.as_deref(), pairs[0].to_id.as_deref()), (Some("attr_id"), Some("attr_user_id")));
+
+        let saved = serde_json::to_string(&diagram).unwrap();
+        let mut reopened: ErdDiagram = serde_json::from_str(&saved).unwrap();
+        assert!(reopened.normalize().is_empty());
+        assert_eq!(reopened.relations[0].columns, pairs);
+        assert_eq!(reopened.relations[0].from_attribute, "id");
+        assert_eq!(reopened.relations[0].to_attribute.as_deref(), Some("user_id"));
+    }
+
+    #[test]
+    fn two_column_foreign_key_outputs() {
+        let diagram = order_items();
+
+        let markdown = diagram.to_markdown();
+        assert!(markdown.contains(
+            "- orders (1:N) → order_items (fk_order_items_shop_id_order_no): (shop_id, order_no) → (shop_id, order_no) [1 — 0..N]"
+        ));
+        assert!(markdown.contains("| shop_id | shop_id | shop_id | INT | - | PK, FK, NOT NULL |"));
+        assert!(markdown.contains("| order_no | order_no | order_no | INT | - | PK, FK, NOT NULL |"));
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("orders ||--o{ order_items : \"fk_order_items_shop_id_order_no (shop_id, order_no)\""));
+        assert!(mermaid.contains("        order_no INT PK FK\n        shop_id INT PK FK\n"));
+
+        let ddl = diagram.to_sql(SqlDialect::PostgreSql);
+        assert!(ddl.contains(
+            "ALTER TABLE \"order_items\" ADD CONSTRAINT \"fk_order_items_shop_id_order_no\" \
+             FOREIGN KEY (\"shop_id\", \"order_no\") REFERENCES \"orders\" (\"shop_id\", \"order_no\");"
+        ));
+    }
+
+    // 이름 있는 UNIQUE/CHECK 제약조건은 DDL과 데이터 사전 모두에 나와야 함
+    #[test]
+    fn named_constraints_in_ddl_and_markdown() {
+        let mut diagram = crate::sql_import::parse_sql(
+            "CREATE TABLE members (id INT PRIMARY KEY, email VARCHAR(100) UNIQUE, shop_id INT, code VARCHAR(10),
+               amount INT CHECK (amount >= 0), status CHAR(1),
+               CONSTRAINT uq_members_shop_code UNIQUE (shop_id, code),
+               CONSTRAINT ck_members_status CHECK (status IN ('A', 'B')));",
+        )
+        .diagram;
+        let members = diagram.entities.get_mut("entity_members").unwrap();
+        members.check_constraints.push(CheckConstraint {
+            name: "ck_members_code".to_string(),
+            expression: "code || '' <> ''".to_string(),
+        });
+
+        for dialect in [SqlDialect::PostgreSql, SqlDialect::MySql, SqlDialect::Sqlite] {
+            let ddl = diagram.to_sql(dialect).replace('`', "\"");
+            for clause in [
+                "CONSTRAINT \"uq_members_email\" UNIQUE (\"email\"),",
+                "CONSTRAINT \"uq_members_shop_code\" UNIQUE (\"shop_id\", \"code\"),",
+                "CONSTRAINT \"ck_members_amount\" CHECK (amount >= 0),",
+                "CONSTRAINT \"ck_members_status\" CHECK (status IN ('A', 'B')),",
+                "CONSTRAINT \"ck_members_code\" CHECK (code || '' <> '')\n",
+            ] {
+                assert!(ddl.contains(clause), "{:?}: {}", dialect, clause);
+            }
+        }
+
+        let markdown = diagram.to_markdown();
+        assert!(markdown.contains("| email | email | email | VARCHAR(100) | - | UNIQUE |"));
+        assert!(markdown.contains("| amount | amount | amount | INT | - | CHECK (amount >= 0) |"));
+        assert!(markdown.contains(
+            "**제약조건**\n\n| Name | Type | Definition |\n|------|------|------------|\n\
+             | uq_members_shop_code | UNIQUE | (shop_id, code) |\n\
+             | ck_members_status | CHECK | status IN ('A', 'B') |\n\
+             | ck_members_code | CHECK | code \\|\\| '' <> '' |\n"
+        ));
+    }
+}
@@ -0,0 +1,410 @@
+use crate::backup;
+use crate::erd::{Entity, ErdDiagram};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 현재 저장 형식 버전. 형식을 바꾸면 올리고 MIGRATIONS에 이전 버전에서 올리는 단계를 추가
+pub const FORMAT_VERSION: u32 = 3;
+
+// version → version + 1 변환 (JSON 단계에서 처리해 이후 구조체가 바뀌어도 유지됨)
+struct FormatMigration {
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Value),
+}
+
+const MIGRATIONS: &[FormatMigration] = &[
+    FormatMigration { from: 0, description: "엔티티/속성의 name을 logical_name으로 옮김", apply: logical_names },
+    FormatMigration { from: 1, description: "단일 컬럼 관계(from_attribute/to_attribute)를 columns로 옮김", apply: relation_columns },
+    FormatMigration { from: 2, description: "속성 id를 부여하고 관계의 컬럼 쌍을 속성 id로 연결", apply: attribute_ids },
+];
+
+pub struct ParsedDiagram {
+    pub diagram: ErdDiagram,
+    pub migrations: Vec<String>, // 실행한 마이그레이션 ("0 → 1: 설명")
+}
+
+// 다이어그램 JSON을 읽어 현재 형식으로 올림 (format_version이 없으면 0)
+pub fn parse(json: &str) -> Result<ParsedDiagram, String> {
+    parse_value(serde_json::from_str(json).map_err(parse_error)?)
+}
+
+// 다른 JSON에 담긴 다이어그램용 (복구 슬롯 등)
+pub fn parse_value(mut value: Value) -> Result<ParsedDiagram, String> {
+    let Some(root) = value.as_object_mut() else {
+        return Err("다이어그램 파일을 파싱할 수 없습니다: 최상위가 객체가 아닙니다.".to_string());
+    };
+    let version = match root.get("format_version") {
+        None | Some(Value::Null) => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("format_version '{}'을(를) 읽을 수 없습니다.", v))?,
+    };
+    if version > FORMAT_VERSION {
+        return Err(format!(
+            "더 새로운 버전의 편집기로 저장한 파일입니다 (파일 형식 {}, 지원하는 형식 {}까지). 편집기를 업데이트한 뒤 여세요.",
+            version, FORMAT_VERSION
+        ));
+    }
+
+    let mut migrations = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+        (migration.apply)(&mut value);
+        migrations.push(format!("{} → {}: {}", migration.from, migration.from + 1, migration.description));
+    }
+    value["format_version"] = Value::from(FORMAT_VERSION);
+
+    let diagram = serde_json::from_value(value).map_err(parse_error)?;
+    Ok(ParsedDiagram { diagram, migrations })
+}
+
+// 저장용 JSON: 현재 형식 버전을 적고, 엔티티/관계는 id 순, 필드는 선언 순, 2칸 들여쓰기, 끝에 줄바꿈
+pub fn to_json(diagram: &ErdDiagram) -> Result<String, String> {
+    let mut diagram = diagram.clone();
+    diagram.format_version = FORMAT_VERSION;
+    pretty(&diagram)
+}
+
+fn pretty<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| json + "\n")
+        .map_err(|e| format!("직렬화 실패: {}", e))
+}
+
+// 폴더 형식: diagram.json(엔티티를 뺀 나머지) + entities/<엔티티 id>.json
+// 엔티티마다 파일이 나뉘어 병합 충돌이 그 엔티티 파일 안에서만 생김
+const SPLIT_DIAGRAM_FILE: &str = "diagram.json";
+const SPLIT_ENTITY_DIR: &str = "entities";
+
+pub fn write_split(diagram: &ErdDiagram, dir: &Path) -> Result<(), String> {
+    let mut rest = diagram.clone();
+    rest.format_version = FORMAT_VERSION;
+    let mut entities: Vec<Entity> = std::mem::take(&mut rest.entities).into_values().collect();
+    entities.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let entity_dir = dir.join(SPLIT_ENTITY_DIR);
+    fs::create_dir_all(&entity_dir).map_err(|e| format!("폴더 생성 실패 '{}': {}", entity_dir.display(), e))?;
+    let mut written: Vec<String> = Vec::new();
+    for entity in &entities {
+        let mut file_name = format!("{}.json", file_stem(&entity.id));
+        let mut n = 2;
+        while written.iter().any(|w| w.eq_ignore_ascii_case(&file_name)) {
+            file_name = format!("{}_{}.json", file_stem(&entity.id), n);
+            n += 1;
+        }
+        write_file(&entity_dir.join(&file_name), &pretty(entity)?)?;
+        written.push(file_name);
+    }
+    // 삭제된 엔티티의 파일 정리
+    for entry in fs::read_dir(&entity_dir).map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", entity_dir.display(), e))? {
+        let path = entry.map_err(|e| format!("폴더를 읽을 수 없습니다: {}", e))?.path();
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if name.ends_with(".json") && !written.contains(&name) {
+            fs::remove_file(&path).map_err(|e| format!("파일 삭제 실패 '{}': {}", path.display(), e))?;
+        }
+    }
+    // 엔티티는 entities/ 파일에 있으므로 diagram.json에서는 키를 뺌
+    let mut rest = serde_json::to_value(&rest).map_err(|e| format!("직렬화 실패: {}", e))?;
+    if let Some(root) = rest.as_object_mut() {
+        root.remove("entities");
+    }
+    write_file(&dir.join(SPLIT_DIAGRAM_FILE), &pretty(&rest)?)
+}
+
+pub fn read_split(dir: &Path) -> Result<ParsedDiagram, String> {
+    let mut value: Value = serde_json::from_str(&read_file(&dir.join(SPLIT_DIAGRAM_FILE))?).map_err(parse_error)?;
+    let mut entities = Map::new();
+    let entity_dir = dir.join(SPLIT_ENTITY_DIR);
+    if entity_dir.is_dir() {
+        let mut paths: Vec<_> = fs::read_dir(&entity_dir)
+            .map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", entity_dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let entity: Value = serde_json::from_str(&read_file(&path)?)
+                .map_err(|e| format!("엔티티 파일 '{}'을(를) 파싱할 수 없습니다: {}", path.display(), e))?;
+            let id = entity
+                .get("id")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| format!("엔티티 파일 '{}'에 id가 없습니다.", path.display()))?;
+            if entities.insert(id.clone(), entity).is_some() {
+                return Err(format!("엔티티 id '{}'이(가) 여러 파일에 있습니다.", id));
+            }
+        }
+    }
+    let Some(root) = value.as_object_mut() else {
+        return Err(format!("'{}'의 최상위가 객체가 아닙니다.", SPLIT_DIAGRAM_FILE));
+    };
+    root.insert("entities".to_string(), Value::Object(entities));
+    parse_value(value)
+}
+
+// 파일 이름에 쓸 수 없는 문자는 _로
+fn file_stem(id: &str) -> String {
+    let stem: String = id.chars().map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' }).collect();
+    if stem.is_empty() { "entity".to_string() } else { stem }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", path.display(), e))
+}
+
+// 폴더 형식은 Git으로 이력을 관리하므로 백업 없이 원자적 저장만
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    backup::write_atomic(path, content)
+}
+
+fn parse_error(e: serde_json::Error) -> String {
+    if e.to_string().contains("recursion limit") {
+        "JSON 구조가 너무 복잡합니다.".to_string()
+    } else {
+        format!("다이어그램 파일을 파싱할 수 없습니다: {}", e)
+    }
+}
+
+fn entities(value: &mut Value) -> impl Iterator<Item = &mut Map<String, Value>> {
+    value
+        .get_mut("entities")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(|entities| entities.values_mut())
+        .filter_map(Value::as_object_mut)
+}
+
+fn attributes(entity: &mut Map<String, Value>) -> impl Iterator<Item = &mut Map<String, Value>> {
+    entity
+        .get_mut("attributes")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flat_map(|attributes| attributes.iter_mut())
+        .filter_map(Value::as_object_mut)
+}
+
+fn relations(value: &mut Value) -> impl Iterator<Item = &mut Map<String, Value>> {
+    value
+        .get_mut("relations")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flat_map(|relations| relations.iter_mut())
+        .filter_map(Value::as_object_mut)
+}
+
+fn text<'a>(object: &'a Map<String, Value>, key: &str) -> Option<&'a str> {
+    object.get(key).and_then(Value::as_str).filter(|s| !s.trim().is_empty())
+}
+
+// 0 → 1: 물리명이 생기기 전에는 name 하나만 있었음
+fn logical_names(value: &mut Value) {
+    let rename = |object: &mut Map<String, Value>| {
+        if !object.contains_key("logical_name") {
+            if let Some(name) = object.remove("name") {
+                object.insert("logical_name".to_string(), name);
+            }
+        }
+    };
+    for entity in entities(value) {
+        rename(entity);
+        for attr in attributes(entity) {
+            rename(attr);
+        }
+    }
+}
+
+// 1 → 2: 복합 키 이전에는 관계마다 컬럼 하나만 있었음
+fn relation_columns(value: &mut Value) {
+    for relation in relations(value) {
+        let has_columns = relation.get("columns").and_then(Value::as_array).is_some_and(|c| !c.is_empty());
+        let Some(from) = text(relation, "from_attribute").map(str::to_string) else { continue };
+        if has_columns {
+            continue;
+        }
+        let to = relation.get("to_attribute").cloned().unwrap_or(Value::Null);
+        let mut pair = Map::new();
+        pair.insert("from".to_string(), Value::from(from));
+        pair.insert("to".to_string(), to);
+        relation.insert("columns".to_string(), Value::Array(vec![Value::Object(pair)]));
+    }
+}
+
+// 2 → 3: 속성 id는 "attr_물리명" (엔티티 안에서 겹치면 _2, _3 ...), 컬럼 쌍은 물리명으로 찾아 id 연결
+// 찾지 못한 참조는 그대로 두고 normalize에서 보고
+fn attribute_ids(value: &mut Value) {
+    let mut ids: HashMap<(String, String), String> = HashMap::new(); // (엔티티 id, 소문자 물리명) → 속성 id
+    for (entity_id, entity) in value.get_mut("entities").and_then(Value::as_object_mut).into_iter().flatten() {
+        let Some(entity) = entity.as_object_mut() else { continue };
+        let mut taken: Vec<String> = attributes(entity).filter_map(|a| text(a, "id").map(str::to_string)).collect();
+        let mut seen: Vec<String> = Vec::new();
+        for (i, attr) in attributes(entity).enumerate() {
+            let physical = text(attr, "physical_name").map(|n| n.trim().to_lowercase());
+            let id = match text(attr, "id") {
+                Some(id) if !seen.iter().any(|s| s == id) => id.to_string(),
+                _ => {
+                    let base = format!("attr_{}", physical.clone().unwrap_or_else(|| (i + 1).to_string()));
+                    let mut id = base.clone();
+                    let mut n = 2;
+                    while taken.contains(&id) {
+                        id = format!("{}_{}", base, n);
+                        n += 1;
+                    }
+                    taken.push(id.clone());
+                    attr.insert("id".to_string(), Value::from(id.clone()));
+                    id
+                }
+            };
+            seen.push(id.clone());
+            if let Some(name) = physical {
+                ids.entry((entity_id.clone(), name)).or_insert(id);
+            }
+        }
+    }
+
+    let lookup = |entity_id: Option<&str>, name: Option<&str>| -> Option<Value> {
+        ids.get(&(entity_id?.to_string(), name?.trim().to_lowercase())).cloned().map(Value::from)
+    };
+    for relation in relations(value) {
+        let from_entity = text(relation, "from_entity_id").map(str::to_string);
+        let to_entity = text(relation, "to_entity_id").map(str::to_string);
+        let Some(columns) = relation.get_mut("columns").and_then(Value::as_array_mut) else { continue };
+        for pair in columns.iter_mut().filter_map(Value::as_object_mut) {
+            for (name_key, id_key, entity_id) in [("from", "from_id", &from_entity), ("to", "to_id", &to_entity)] {
+                if text(pair, id_key).is_some() {
+                    continue;
+                }
+                if let Some(id) = lookup(entity_id.as_deref(), text(pair, name_key)) {
+                    pair.insert(id_key.to_string(), id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    // 버전 표시가 없던 시절의 파일: name만 있고 관계는 단일 컬럼
+    const V0: &str = r#"{
+        "entities": {
+            "u": {"id": "u", "name": "사용자", "physical_name": "users", "attributes": [
+                {"name": "아이디", "physical_name": "id", "data_type": "INT", "is_primary_key": true, "is_nullable": false}
+            ]},
+            "p": {"id": "p", "name": "글", "physical_name": "posts", "attributes": [
+                {"name": "작성자", "physical_name": "user_id", "data_type": "INT"}
+            ]}
+        },
+        "relations": [{"id": "r1", "name": "writes", "from_entity_id": "u", "from_attribute": "id",
+                       "to_entity_id": "p", "to_attribute": "user_id", "cardinality": "OneToMany"}]
+    }"#;
+
+    #[test]
+    fn unversioned_file_runs_the_whole_chain() {
+        let parsed = parse(V0).unwrap();
+        assert_eq!(parsed.migrations.len(), MIGRATIONS.len());
+        assert!(parsed.migrations[0].starts_with("0 → 1"));
+
+        let diagram = parsed.diagram;
+        assert_eq!(diagram.format_version, FORMAT_VERSION);
+        assert_eq!(diagram.entities["u"].logical_name, "사용자");
+        assert_eq!(diagram.entities["u"].attributes[0].logical_name, "아이디");
+        let pair = &diagram.relations[0].columns[0];
+        assert_eq!((pair.from.as_str(), pair.to.as_deref()), ("id", Some("user_id")));
+        assert_eq!(pair.from_id.as_deref(), Some("attr_id"));
+        assert_eq!(pair.to_id.as_deref(), Some("attr_user_id"));
+    }
+
+    #[test]
+    fn chain_starts_at_the_file_version() {
+        let v2 = r#"{"format_version": 2, "entities": {
+            "u": {"id": "u", "logical_name": "사용자", "physical_name": "users", "attributes": [
+                {"logical_name": "아이디", "physical_name": "id", "data_type": "INT"},
+                {"logical_name": "아이디", "physical_name": "ID", "data_type": "INT"}
+            ]}}}"#;
+        let parsed = parse(v2).unwrap();
+        assert_eq!(parsed.migrations.len(), 1);
+        assert!(parsed.migrations[0].starts_with("2 → 3"));
+        let ids: Vec<&str> = parsed.diagram.entities["u"].attributes.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["attr_id", "attr_id_2"]);
+    }
+
+    #[test]
+    fn current_version_is_not_migrated_again() {
+        let json = to_json(&parse(V0).unwrap().diagram).unwrap();
+        let again = parse(&json).unwrap();
+        assert!(again.migrations.is_empty());
+        assert_eq!(to_json(&again.diagram).unwrap(), json);
+    }
+
+    #[test]
+    fn newer_or_invalid_versions_are_rejected() {
+        let newer = format!(r#"{{"format_version": {}}}"#, FORMAT_VERSION + 1);
+        assert!(parse(&newer).err().is_some_and(|e| e.contains("더 새로운 버전")));
+        assert!(parse(r#"{"format_version": "x"}"#).is_err());
+        assert!(parse("[1]").is_err());
+    }
+
+    fn diagram_with(ids: &[&str]) -> ErdDiagram {
+        let mut diagram = ErdDiagram::default();
+        for id in ids {
+            let mut entity = Entity::new(id, id);
+            entity.attributes.push(crate::erd::Attribute::new("id", "INT"));
+            entity.x = 10.0 / 3.0;
+            diagram.entities.insert(id.to_string(), entity);
+        }
+        diagram.normalize();
+        diagram
+    }
+
+    #[test]
+    fn json_is_canonical() {
+        let forward = to_json(&diagram_with(&["a", "b", "c"])).unwrap();
+        let backward = to_json(&diagram_with(&["c", "b", "a"])).unwrap();
+        assert_eq!(forward, backward);
+        assert!(forward.ends_with("}\n"));
+        assert!(forward.contains("\"x\": 3.33"));
+        let a = forward.find("\"a\": {").unwrap();
+        let c = forward.find("\"c\": {").unwrap();
+        assert!(a < c);
+    }
+
+    // 프론트엔드는 entities가 항상 있다고 가정함
+    #[test]
+    fn empty_diagram_keeps_entities() {
+        let value = serde_json::to_value(ErdDiagram::default()).unwrap();
+        assert_eq!(value["entities"], Value::Object(Map::new()));
+    }
+
+    #[test]
+    fn split_round_trip_removes_stale_files() {
+        let dir = temp_dir("file_format_split");
+        write_split(&diagram_with(&["a", "b"]), &dir).unwrap();
+        write_split(&diagram_with(&["a"]), &dir).unwrap();
+
+        let diagram_json: Value = serde_json::from_str(&fs::read_to_string(dir.join(SPLIT_DIAGRAM_FILE)).unwrap()).unwrap();
+        assert!(diagram_json.get("entities").is_none());
+        assert!(dir.join(SPLIT_ENTITY_DIR).join("a.json").exists());
+        assert!(!dir.join(SPLIT_ENTITY_DIR).join("b.json").exists());
+
+        let parsed = read_split(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(parsed.migrations.is_empty());
+        assert_eq!(to_json(&parsed.diagram).unwrap(), to_json(&diagram_with(&["a"])).unwrap());
+    }
+
+    #[test]
+    fn split_rejects_duplicate_entity_ids() {
+        let dir = temp_dir("file_format_duplicate");
+        write_split(&diagram_with(&["a"]), &dir).unwrap();
+        fs::copy(dir.join(SPLIT_ENTITY_DIR).join("a.json"), dir.join(SPLIT_ENTITY_DIR).join("copy.json")).unwrap();
+        let result = read_split(&dir);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(result.err().is_some_and(|e| e.contains("여러 파일")));
+    }
+}
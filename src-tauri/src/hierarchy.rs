@@ -0,0 +1,631 @@
+use crate::erd::{
+    Attribute, Cardinality, CheckConstraint, ColumnPair, Entity, ErdDiagram, Multiplicity, Relation, SubtypeHierarchy, SubtypeTransform,
+};
+use crate::relations;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// 단일 테이블 변환에서 구분자 속성이 지정되지 않았을 때 만드는 컬럼
+const DEFAULT_DISCRIMINATOR: &str = "subtype";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhysicalModel {
+    pub diagram: ErdDiagram,
+    pub warnings: Vec<String>,
+}
+
+// 계층 정의의 문제 목록 (없는 엔티티, 구분자, 다중 상속, 순환)
+pub fn problems(diagram: &ErdDiagram, hierarchy: &SubtypeHierarchy) -> Vec<String> {
+    let mut problems = Vec::new();
+    let Some(supertype) = diagram.entities.get(&hierarchy.supertype_id) else {
+        problems.push(format!("계층 '{}'의 슈퍼타입 '{}'이(가) 없습니다", hierarchy.id, hierarchy.supertype_id));
+        return problems;
+    };
+    if hierarchy.subtypes.is_empty() {
+        problems.push(format!("'{}'의 계층 '{}'에 서브타입이 없습니다", supertype.physical_name, hierarchy.id));
+    }
+    let mut seen: Vec<&str> = Vec::new();
+    for subtype in &hierarchy.subtypes {
+        if !diagram.entities.contains_key(&subtype.entity_id) {
+            problems.push(format!("계층 '{}'의 서브타입 '{}'이(가) 없습니다", hierarchy.id, subtype.entity_id));
+        } else if subtype.entity_id == hierarchy.supertype_id {
+            problems.push(format!("'{}'은(는) 자기 자신의 서브타입이 될 수 없습니다", supertype.physical_name));
+        } else if seen.contains(&subtype.entity_id.as_str()) {
+            problems.push(format!("계층 '{}'에 서브타입 '{}'이(가) 중복되어 있습니다", hierarchy.id, subtype.entity_id));
+        }
+        seen.push(&subtype.entity_id);
+
+        // 다중 상속은 물리 변환이 모호하므로 허용하지 않음
+        if let Some(other) = diagram
+            .hierarchies
+            .iter()
+            .find(|h| h.id != hierarchy.id && h.subtypes.iter().any(|s| s.entity_id == subtype.entity_id))
+        {
+            problems.push(format!("'{}'은(는) 이미 계층 '{}'의 서브타입입니다", subtype.entity_id, other.id));
+        }
+    }
+    if let Some(discriminator) = hierarchy.discriminator_name() {
+        if find(&supertype.attributes, discriminator).is_none() {
+            problems.push(format!("'{}'에 구분자 속성 '{}'이(가) 없습니다", supertype.physical_name, discriminator));
+        }
+    }
+    if is_descendant(diagram, &hierarchy.supertype_id, hierarchy, &mut Vec::new()) {
+        problems.push(format!("계층 '{}'이(가) 순환합니다", hierarchy.id));
+    }
+    problems
+}
+
+// 계층 추가/수정 (같은 id면 교체)
+pub fn save(diagram: &ErdDiagram, hierarchy: SubtypeHierarchy) -> Result<ErdDiagram, String> {
+    if hierarchy.id.trim().is_empty() {
+        return Err("계층 id가 비어 있습니다.".to_string());
+    }
+    if let Some(problem) = problems(diagram, &hierarchy).into_iter().next() {
+        return Err(format!("{}.", problem));
+    }
+    let mut diagram = diagram.clone();
+    match diagram.hierarchies.iter().position(|h| h.id == hierarchy.id) {
+        Some(position) => diagram.hierarchies[position] = hierarchy,
+        None => diagram.hierarchies.push(hierarchy),
+    }
+    Ok(diagram)
+}
+
+// 계층만 삭제 (엔티티는 그대로)
+pub fn remove(diagram: &ErdDiagram, id: &str) -> Result<ErdDiagram, String> {
+    let mut diagram = diagram.clone();
+    let position = diagram
+        .hierarchies
+        .iter()
+        .position(|h| h.id == id)
+        .ok_or_else(|| format!("계층 '{}'이(가) 없습니다.", id))?;
+    diagram.hierarchies.remove(position);
+    Ok(diagram)
+}
+
+// 계층마다 지정한 방식으로 테이블을 만든 물리 모델 (DDL/마이그레이션용)
+// 상위 계층부터 변환해서 슈퍼타입 키와 속성이 아래로 전달되게 함
+pub fn transform(diagram: &ErdDiagram) -> PhysicalModel {
+    let mut physical = diagram.clone();
+    physical.hierarchies.clear();
+    let mut warnings = Vec::new();
+
+    let mut hierarchies: Vec<(usize, SubtypeHierarchy)> = Vec::new();
+    for hierarchy in &diagram.hierarchies {
+        match problems(diagram, hierarchy).into_iter().next() {
+            Some(problem) => warnings.push(format!("{} - 계층을 변환하지 않았습니다", problem)),
+            None => hierarchies.push((depth(diagram, hierarchy), hierarchy.clone())),
+        }
+    }
+    hierarchies.sort_by_key(|(depth, _)| *depth);
+
+    // 단일 테이블로 합쳐진 서브타입 -> 합쳐진 테이블 (하위 계층의 슈퍼타입이면 그 테이블에 이어서 합침)
+    let mut merged: HashMap<String, String> = HashMap::new();
+    for (_, hierarchy) in hierarchies {
+        let mut supertype_id = hierarchy.supertype_id.clone();
+        while let Some(target) = merged.get(&supertype_id) {
+            supertype_id = target.clone();
+        }
+        let Some(supertype) = physical.entities.get(&supertype_id).cloned() else { continue };
+        let subtypes: Vec<(String, String)> = hierarchy
+            .subtypes
+            .iter()
+            .filter_map(|s| physical.entities.get(&s.entity_id).map(|e| (s.entity_id.clone(), s.value(e))))
+            .collect();
+
+        match hierarchy.transform {
+            SubtypeTransform::TablePerType => table_per_type(&mut physical, &hierarchy, &supertype, &subtypes, &mut warnings),
+            SubtypeTransform::SingleTable => {
+                single_table(&mut physical, &hierarchy, &supertype, &subtypes, &mut warnings);
+                for (entity_id, _) in subtypes {
+                    merged.insert(entity_id, supertype.id.clone());
+                }
+            }
+            SubtypeTransform::TablePerConcreteClass => table_per_concrete_class(&mut physical, &hierarchy, &supertype, &subtypes, &mut warnings),
+        }
+    }
+    PhysicalModel { diagram: physical, warnings }
+}
+
+// 슈퍼타입 PK를 서브타입의 PK 겸 FK로 두고 1:0..1 식별 관계로 연결
+fn table_per_type(
+    diagram: &mut ErdDiagram,
+    hierarchy: &SubtypeHierarchy,
+    supertype: &Entity,
+    subtypes: &[(String, String)],
+    warnings: &mut Vec<String>,
+) {
+    let keys: Vec<Attribute> = supertype.attributes.iter().filter(|a| a.is_primary_key).cloned().collect();
+    if keys.is_empty() {
+        warnings.push(format!("'{}'에 기본 키가 없어 서브타입 테이블을 연결하지 못했습니다", supertype.physical_name));
+    }
+    for (entity_id, _) in subtypes {
+        let Some(subtype) = diagram.entities.get_mut(entity_id) else { continue };
+        if keys.is_empty() {
+            continue;
+        }
+        let own_keys: Vec<String> = subtype
+            .attributes
+            .iter()
+            .filter(|a| a.is_primary_key && find(&keys, &a.physical_name).is_none())
+            .map(|a| a.physical_name.clone())
+            .collect();
+        if !own_keys.is_empty() {
+            warnings.push(format!(
+                "서브타입 '{}'의 기본 키({})를 슈퍼타입 '{}'의 키로 바꿨습니다",
+                subtype.physical_name,
+                own_keys.join(", "),
+                supertype.physical_name
+            ));
+            for attr in subtype.attributes.iter_mut() {
+                attr.is_primary_key = false;
+            }
+        }
+
+        let mut pairs = Vec::new();
+        for (position, key) in keys.iter().enumerate() {
+            let reference = relations::foreign_key_attribute(supertype, key, &key.physical_name);
+            match subtype.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(&key.physical_name)) {
+                Some(attr) => {
+                    attr.data_type = reference.data_type;
+                    attr.length = reference.length;
+                    attr.is_foreign_key = true;
+                    attr.foreign_key_reference = reference.foreign_key_reference;
+                    attr.is_primary_key = true;
+                    attr.is_nullable = false;
+                }
+                None => {
+                    let mut attr = reference;
+                    attr.logical_name = key.logical_name.clone();
+                    attr.is_primary_key = true;
+                    attr.is_nullable = false;
+                    subtype.attributes.insert(position, attr);
+                }
+            }
+            pairs.push(ColumnPair::new(key.physical_name.clone(), Some(key.physical_name.clone())));
+        }
+
+        let mut relation = Relation {
+            id: format!("{}_{}", hierarchy.id, entity_id),
+            from_entity_id: supertype.id.clone(),
+            from_attribute: String::new(),
+            to_entity_id: entity_id.clone(),
+            to_attribute: None,
+            columns: Vec::new(),
+            cardinality: Cardinality::OneToOne,
+            name: subtype.physical_name.clone(),
+            from_multiplicity: Some(Multiplicity::EXACTLY_ONE),
+            to_multiplicity: Some(Multiplicity::ZERO_OR_ONE),
+            is_identifying: true,
+        };
+        relation.set_pairs(pairs);
+        diagram.relations.push(relation);
+    }
+    if let Some(discriminator) = hierarchy.discriminator_name() {
+        add_discriminator_check(diagram, hierarchy, &supertype.id, discriminator, subtypes);
+    }
+}
+
+// 서브타입 속성을 슈퍼타입 테이블에 nullable 컬럼으로 합치고 서브타입 테이블은 없앰
+fn single_table(
+    diagram: &mut ErdDiagram,
+    hierarchy: &SubtypeHierarchy,
+    supertype: &Entity,
+    subtypes: &[(String, String)],
+    warnings: &mut Vec<String>,
+) {
+    let table = supertype.physical_name.clone();
+    let keys: Vec<String> = supertype.attributes.iter().filter(|a| a.is_primary_key).map(|a| a.physical_name.clone()).collect();
+
+    // 배타적 계층은 구분자 컬럼 하나로, 포괄적 계층은 서브타입마다 플래그 컬럼으로 구분
+    let discriminator = if hierarchy.is_exclusive {
+        let name = hierarchy.discriminator_name().unwrap_or(DEFAULT_DISCRIMINATOR).to_string();
+        let target = diagram.entities.get_mut(&supertype.id).unwrap();
+        if find(&target.attributes, &name).is_none() {
+            let longest = subtypes.iter().map(|(_, v)| v.chars().count()).max().unwrap_or(1).max(1);
+            let mut attr = Attribute::new(&name, "VARCHAR");
+            attr.logical_name = format!("{} 구분", supertype.logical_name);
+            attr.length = Some(longest.to_string());
+            target.attributes.push(attr);
+        }
+        add_discriminator_check(diagram, hierarchy, &supertype.id, &name, subtypes);
+        Some(name)
+    } else {
+        if let Some(name) = hierarchy.discriminator_name() {
+            warnings.push(format!("포괄적 계층 '{}'은(는) 구분자 '{}' 대신 서브타입별 플래그 컬럼을 씁니다", hierarchy.id, name));
+        }
+        None
+    };
+
+    for (entity_id, value) in subtypes {
+        let Some(subtype) = diagram.entities.remove(entity_id) else { continue };
+        let target = diagram.entities.get_mut(&supertype.id).unwrap();
+
+        let flag = match discriminator {
+            Some(_) => None,
+            None => {
+                let mut flag = Attribute::new(&unique_column(target, &format!("is_{}", subtype.physical_name)), "BOOLEAN");
+                flag.logical_name = format!("{} 여부", subtype.logical_name);
+                flag.is_nullable = false;
+                flag.default_value = Some("FALSE".to_string());
+                let name = flag.physical_name.clone();
+                target.attributes.push(flag);
+                Some(name)
+            }
+        };
+
+        // 슈퍼타입 키를 이어받은 컬럼은 건너뛰고, 이름이 겹치면 "서브타입_컬럼"으로 바꿈
+        let mut renames: HashMap<String, String> = HashMap::new();
+        let mut required = Vec::new();
+        for attr in &subtype.attributes {
+            if keys.iter().any(|k| k.eq_ignore_ascii_case(&attr.physical_name)) {
+                continue;
+            }
+            if attr.is_primary_key {
+                warnings.push(format!(
+                    "서브타입 '{}'의 기본 키 '{}'은(는) '{}'의 일반 컬럼이 됩니다",
+                    subtype.physical_name, attr.physical_name, table
+                ));
+            }
+            let mut column = attr.clone();
+            if find(&target.attributes, &column.physical_name).is_some() {
+                column.physical_name = unique_column(target, &format!("{}_{}", subtype.physical_name, attr.physical_name));
+            }
+            if !attr.is_nullable {
+                required.push(column.physical_name.clone());
+            }
+            column.is_primary_key = false;
+            column.is_nullable = true;
+            renames.insert(attr.physical_name.to_lowercase(), column.physical_name.clone());
+            target.attributes.push(column);
+        }
+        let rename = |name: &str| renames.get(&name.to_lowercase()).cloned().unwrap_or_else(|| name.to_string());
+
+        // 서브타입에서 NOT NULL이던 컬럼은 그 서브타입 행에서만 필수
+        if !required.is_empty() {
+            let condition = required.iter().map(|c| format!("{} IS NOT NULL", c)).collect::<Vec<_>>().join(" AND ");
+            let expression = match (&discriminator, &flag) {
+                (Some(name), _) => format!("{} <> '{}' OR ({})", name, value.replace('\'', "''"), condition),
+                (None, Some(flag)) => format!("NOT {} OR ({})", flag, condition),
+                (None, None) => condition,
+            };
+            target.check_constraints.push(CheckConstraint { name: format!("ck_{}_{}", table, subtype.physical_name), expression });
+        }
+        for mut index in subtype.indexes.clone() {
+            for column in index.columns.iter_mut() {
+                column.name = rename(&column.name);
+            }
+            target.indexes.push(index);
+        }
+        for mut unique in subtype.unique_constraints.clone() {
+            for column in unique.columns.iter_mut() {
+                *column = rename(column);
+            }
+            target.unique_constraints.push(unique);
+        }
+        if !subtype.check_constraints.is_empty() {
+            if renames.iter().any(|(old, new)| !old.eq_ignore_ascii_case(new)) {
+                warnings.push(format!("'{}'의 CHECK 제약조건은 바뀐 컬럼명을 직접 확인해야 합니다", subtype.physical_name));
+            }
+            target.check_constraints.extend(subtype.check_constraints.clone());
+        }
+
+        // 서브타입과 이어진 관계는 슈퍼타입 테이블로 옮김
+        for relation in diagram.relations.iter_mut() {
+            let mut pairs = relation.pairs();
+            // 다른 테이블로 옮긴 컬럼은 id가 겹칠 수 있어 이름으로만 가리킴
+            if relation.to_entity_id == *entity_id {
+                relation.to_entity_id = supertype.id.clone();
+                for pair in pairs.iter_mut() {
+                    pair.to = pair.to.as_deref().map(&rename);
+                    pair.to_id = None;
+                }
+            }
+            if relation.from_entity_id == *entity_id {
+                relation.from_entity_id = supertype.id.clone();
+                for pair in pairs.iter_mut() {
+                    pair.from = rename(&pair.from);
+                    pair.from_id = None;
+                }
+            }
+            relation.set_pairs(pairs);
+        }
+        for attr in diagram.entities.values_mut().flat_map(|e| e.attributes.iter_mut()) {
+            let Some((parent, column)) = attr.foreign_key_reference.as_deref().and_then(|r| r.split_once('.')) else { continue };
+            if parent.eq_ignore_ascii_case(&subtype.physical_name) {
+                attr.foreign_key_reference = Some(format!("{}.{}", table, rename(column)));
+            }
+        }
+    }
+}
+
+// 서브타입 테이블마다 슈퍼타입 속성/제약조건을 복사 (완전 계층이면 슈퍼타입 테이블을 없앰)
+fn table_per_concrete_class(
+    diagram: &mut ErdDiagram,
+    hierarchy: &SubtypeHierarchy,
+    supertype: &Entity,
+    subtypes: &[(String, String)],
+    warnings: &mut Vec<String>,
+) {
+    let skip = |attr: &Attribute| hierarchy.is_exclusive && hierarchy.discriminator_name().is_some_and(|d| d.eq_ignore_ascii_case(&attr.physical_name));
+    let inherited: Vec<Attribute> = supertype.attributes.iter().filter(|a| !skip(a)).cloned().collect();
+    let keys: Vec<&Attribute> = inherited.iter().filter(|a| a.is_primary_key).collect();
+
+    for (entity_id, _) in subtypes {
+        let Some(subtype) = diagram.entities.get_mut(entity_id) else { continue };
+        let rename = |name: &str| match name.find(&supertype.physical_name) {
+            Some(_) => name.replacen(&supertype.physical_name, &subtype.physical_name, 1),
+            None => format!("{}_{}", subtype.physical_name, name),
+        };
+
+        if !keys.is_empty() && subtype.attributes.iter().any(|a| a.is_primary_key && !keys.iter().any(|k| k.physical_name.eq_ignore_ascii_case(&a.physical_name))) {
+            warnings.push(format!("서브타입 '{}'의 기본 키를 슈퍼타입 '{}'의 키로 바꿨습니다", subtype.physical_name, supertype.physical_name));
+            for attr in subtype.attributes.iter_mut() {
+                attr.is_primary_key = false;
+            }
+        }
+        let mut position = 0;
+        for attr in &inherited {
+            match subtype.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(&attr.physical_name)) {
+                Some(existing) => existing.is_primary_key |= attr.is_primary_key,
+                None => {
+                    subtype.attributes.insert(position, attr.clone());
+                    position += 1;
+                }
+            }
+        }
+        for mut index in supertype.indexes.clone() {
+            index.name = rename(&index.name);
+            subtype.indexes.push(index);
+        }
+        for mut unique in supertype.unique_constraints.clone() {
+            unique.name = rename(&unique.name);
+            subtype.unique_constraints.push(unique);
+        }
+        for mut check in supertype.check_constraints.clone() {
+            check.name = rename(&check.name);
+            subtype.check_constraints.push(check);
+        }
+    }
+
+    // 슈퍼타입이 자식인 관계는 서브타입 테이블마다 복제
+    let mut copies = Vec::new();
+    for relation in diagram.relations.iter().filter(|r| r.to_entity_id == supertype.id) {
+        for (entity_id, _) in subtypes {
+            let mut copy = relation.clone();
+            copy.id = format!("{}_{}", relation.id, entity_id);
+            copy.to_entity_id = entity_id.clone();
+            copies.push(copy);
+        }
+    }
+
+    if !hierarchy.is_complete {
+        diagram.relations.extend(copies);
+        return;
+    }
+
+    // 슈퍼타입을 참조하던 FK는 여러 테이블을 가리킬 수 없으므로 관계를 뺌
+    let referencing: Vec<String> = diagram
+        .relations
+        .iter()
+        .filter(|r| r.from_entity_id == supertype.id && r.to_entity_id != supertype.id)
+        .map(|r| r.name.clone())
+        .collect();
+    if !referencing.is_empty() {
+        warnings.push(format!(
+            "'{}'이(가) 서브타입 테이블로 나뉘어 이를 참조하는 관계({})의 FK를 만들 수 없습니다",
+            supertype.physical_name,
+            referencing.join(", ")
+        ));
+    }
+    diagram.relations.retain(|r| r.from_entity_id != supertype.id && r.to_entity_id != supertype.id);
+    diagram.relations.extend(copies);
+    diagram.entities.remove(&supertype.id);
+}
+
+// 구분자 컬럼 값을 서브타입 값으로 제한 (완전 계층이면 NOT NULL)
+fn add_discriminator_check(diagram: &mut ErdDiagram, hierarchy: &SubtypeHierarchy, supertype_id: &str, column: &str, subtypes: &[(String, String)]) {
+    let Some(supertype) = diagram.entities.get_mut(supertype_id) else { return };
+    if let Some(attr) = supertype.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(column)) {
+        if hierarchy.is_complete {
+            attr.is_nullable = false;
+        }
+    }
+    let values: Vec<String> = subtypes.iter().map(|(_, v)| format!("'{}'", v.replace('\'', "''"))).collect();
+    supertype.check_constraints.push(CheckConstraint {
+        name: format!("ck_{}_{}", supertype.physical_name, column),
+        expression: format!("{} IN ({})", column, values.join(", ")),
+    });
+}
+
+// 루트 계층이 0, 슈퍼타입이 다른 계층의 서브타입이면 1씩 깊어짐
+fn depth(diagram: &ErdDiagram, hierarchy: &SubtypeHierarchy) -> usize {
+    let mut depth = 0;
+    let mut entity_id = hierarchy.supertype_id.as_str();
+    while let Some(parent) = diagram.hierarchies.iter().find(|h| h.subtypes.iter().any(|s| s.entity_id == entity_id)) {
+        depth += 1;
+        entity_id = &parent.supertype_id;
+        if depth > diagram.hierarchies.len() {
+            break;
+        }
+    }
+    depth
+}
+
+// entity_id가 hierarchy의 서브타입(또는 그 아래)인지
+fn is_descendant(diagram: &ErdDiagram, entity_id: &str, hierarchy: &SubtypeHierarchy, visited: &mut Vec<String>) -> bool {
+    for subtype in &hierarchy.subtypes {
+        if subtype.entity_id == entity_id {
+            return true;
+        }
+        if visited.contains(&subtype.entity_id) {
+            continue;
+        }
+        visited.push(subtype.entity_id.clone());
+        let children = diagram.hierarchies.iter().filter(|h| h.supertype_id == subtype.entity_id && h.id != hierarchy.id);
+        for child in children {
+            if is_descendant(diagram, entity_id, child, visited) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn find<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes.iter().find(|a| a.physical_name.eq_ignore_ascii_case(name))
+}
+
+// 엔티티에 없는 컬럼 이름 (겹치면 _2, _3 ...)
+fn unique_column(entity: &Entity, base: &str) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while find(&entity.attributes, &name).is_some() {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    // 당사자(party) ⊃ 개인(person), 법인(company). 계약(contract)이 당사자를 참조
+    fn parties(hierarchy: serde_json::Value) -> ErdDiagram {
+        let mut diagram = parse_sql(
+            "CREATE TABLE party (id INT PRIMARY KEY, name VARCHAR(50) NOT NULL, kind VARCHAR(10));
+             CREATE TABLE person (id INT PRIMARY KEY, birth DATE NOT NULL);
+             CREATE TABLE company (id INT PRIMARY KEY, reg_no VARCHAR(20));
+             CREATE TABLE contract (id INT PRIMARY KEY, party_id INT REFERENCES party (id));",
+        )
+        .diagram;
+        let mut value = serde_json::json!({
+            "id": "h", "supertype_id": "entity_party",
+            "subtypes": [{ "entity_id": "entity_person" }, { "entity_id": "entity_company" }],
+        });
+        value.as_object_mut().unwrap().extend(hierarchy.as_object().unwrap().clone());
+        diagram.hierarchies.push(serde_json::from_value(value).unwrap());
+        diagram
+    }
+
+    fn hierarchy(id: &str, supertype: &str, subtype: &str) -> SubtypeHierarchy {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "supertype_id": supertype, "subtypes": [{ "entity_id": subtype }]
+        }))
+        .unwrap()
+    }
+
+    fn attribute<'a>(diagram: &'a ErdDiagram, entity_id: &str, name: &str) -> Option<&'a Attribute> {
+        find(&diagram.entities[entity_id].attributes, name)
+    }
+
+    fn checks(diagram: &ErdDiagram, entity_id: &str) -> Vec<String> {
+        diagram.entities[entity_id].check_constraints.iter().map(|c| c.expression.clone()).collect()
+    }
+
+    #[test]
+    fn single_table_exclusive_complete() {
+        let model = transform(&parties(serde_json::json!({
+            "transform": "SingleTable", "is_exclusive": true, "is_complete": true, "discriminator": "kind"
+        })));
+        let diagram = &model.diagram;
+        assert!(model.warnings.is_empty(), "{:?}", model.warnings);
+        assert!(!diagram.entities.contains_key("entity_person") && !diagram.entities.contains_key("entity_company"));
+        assert!(attribute(diagram, "entity_party", "birth").unwrap().is_nullable);
+        assert!(attribute(diagram, "entity_party", "reg_no").is_some());
+        assert!(!attribute(diagram, "entity_party", "kind").unwrap().is_nullable);
+        assert_eq!(
+            checks(diagram, "entity_party"),
+            ["kind IN ('person', 'company')", "kind <> 'person' OR (birth IS NOT NULL)"]
+        );
+    }
+
+    #[test]
+    fn single_table_inclusive_incomplete() {
+        let model = transform(&parties(serde_json::json!({
+            "transform": "SingleTable", "is_exclusive": false, "is_complete": false, "discriminator": "kind"
+        })));
+        let diagram = &model.diagram;
+        // 포괄적 계층은 구분자 대신 서브타입별 플래그
+        assert_eq!(model.warnings.len(), 1);
+        let flag = attribute(diagram, "entity_party", "is_person").expect("플래그 컬럼이 없음");
+        assert_eq!((flag.data_type.as_str(), flag.default_value.as_deref()), ("BOOLEAN", Some("FALSE")));
+        assert!(attribute(diagram, "entity_party", "is_company").is_some());
+        assert!(attribute(diagram, "entity_party", "kind").unwrap().is_nullable);
+        assert_eq!(checks(diagram, "entity_party"), ["NOT is_person OR (birth IS NOT NULL)"]);
+    }
+
+    #[test]
+    fn table_per_type_links_subtypes_by_key() {
+        let model = transform(&parties(serde_json::json!({
+            "transform": "TablePerType", "is_exclusive": true, "is_complete": true, "discriminator": "kind"
+        })));
+        let diagram = &model.diagram;
+        assert!(model.warnings.is_empty(), "{:?}", model.warnings);
+        assert_eq!(diagram.entities.len(), 4);
+        let id = attribute(diagram, "entity_person", "id").unwrap();
+        assert!(id.is_primary_key && id.is_foreign_key);
+        assert_eq!(id.foreign_key_reference.as_deref(), Some("party.id"));
+
+        let subtype_relations: Vec<&Relation> = diagram.relations.iter().filter(|r| r.id.starts_with("h_")).collect();
+        assert_eq!(subtype_relations.len(), 2);
+        assert!(subtype_relations.iter().all(|r| r.is_identifying && r.from_entity_id == "entity_party"));
+        assert_eq!(checks(diagram, "entity_party"), ["kind IN ('person', 'company')"]);
+        assert!(!attribute(diagram, "entity_party", "kind").unwrap().is_nullable);
+
+        // 포괄적/불완전 계층은 구분자 제약이 없고 NULL 허용
+        let model = transform(&parties(serde_json::json!({
+            "transform": "TablePerType", "is_exclusive": false, "is_complete": false
+        })));
+        assert!(checks(&model.diagram, "entity_party").is_empty());
+        assert!(attribute(&model.diagram, "entity_party", "kind").unwrap().is_nullable);
+        assert_eq!(model.diagram.relations.len(), 3);
+    }
+
+    #[test]
+    fn table_per_concrete_class_complete_drops_the_supertype() {
+        let model = transform(&parties(serde_json::json!({
+            "transform": "TablePerConcreteClass", "is_exclusive": true, "is_complete": true, "discriminator": "kind"
+        })));
+        let diagram = &model.diagram;
+        assert!(!diagram.entities.contains_key("entity_party"));
+        assert!(attribute(diagram, "entity_person", "name").is_some());
+        // 배타적 계층의 구분자는 테이블 자체가 구분하므로 복사하지 않음
+        assert!(attribute(diagram, "entity_company", "kind").is_none());
+        // 슈퍼타입을 참조하던 계약 관계는 만들 수 없음
+        assert!(diagram.relations.is_empty());
+        assert_eq!(model.warnings.len(), 1);
+    }
+
+    #[test]
+    fn table_per_concrete_class_incomplete_keeps_the_supertype() {
+        let model = transform(&parties(serde_json::json!({
+            "transform": "TablePerConcreteClass", "is_exclusive": false, "is_complete": false, "discriminator": "kind"
+        })));
+        let diagram = &model.diagram;
+        assert!(model.warnings.is_empty(), "{:?}", model.warnings);
+        assert!(diagram.entities.contains_key("entity_party"));
+        assert!(attribute(diagram, "entity_person", "kind").is_some());
+        assert!(attribute(diagram, "entity_company", "name").is_some());
+        assert_eq!(diagram.relations.len(), 1);
+    }
+
+    #[test]
+    fn problems_reject_cycles_and_multiple_inheritance() {
+        let mut diagram = parties(serde_json::json!({}));
+        assert!(problems(&diagram, &diagram.hierarchies[0]).is_empty());
+
+        let cycle = hierarchy("back", "entity_person", "entity_party");
+        diagram.hierarchies.push(cycle.clone());
+        assert!(problems(&diagram, &cycle).iter().any(|p| p.contains("순환")));
+        diagram.hierarchies.pop();
+
+        let second_parent = hierarchy("second", "entity_contract", "entity_person");
+        assert!(save(&diagram, second_parent.clone()).err().is_some_and(|e| e.contains("이미 계층 'h'")));
+        diagram.hierarchies.push(second_parent);
+        let model = transform(&diagram);
+        assert!(model.warnings.iter().any(|w| w.contains("계층을 변환하지 않았습니다")));
+    }
+}
@@ -0,0 +1,619 @@
+use crate::erd::{
+    Attribute, CheckConstraint, DomainType, Entity, EnumType, ErdDiagram, Index, IndexColumn, IndexMethod, SortOrder, UniqueConstraint,
+    UserType,
+};
+use crate::sql::ForeignKey;
+use crate::sql_import::{self, apply_foreign_keys, SqlImportResult};
+use postgres::config::SslMode;
+use postgres::{Config, NoTls};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// 로컬 SQLite 파일의 스키마를 읽어 다이어그램 생성 (읽기 전용으로 열기)
+pub fn sqlite(path: &Path) -> Result<SqlImportResult, String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+        .map_err(|e| format!("SQLite 파일을 열 수 없습니다: {}", e))?;
+    sqlite_connection(&conn)
+}
+
+// 열린 SQLite 연결의 스키마를 읽어 다이어그램 생성
+pub fn sqlite_connection(conn: &Connection) -> Result<SqlImportResult, String> {
+    let mut diagram = ErdDiagram::default();
+    let mut warnings = Vec::new();
+    let mut foreign_keys = Vec::new();
+
+    let tables: Vec<(String, String)> = query(
+        conn,
+        "SELECT name, COALESCE(sql, '') FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    for (table, create_sql) in tables {
+        let mut entity = Entity::new(&format!("entity_{}", table), &table);
+
+        // cid, name, type, notnull, dflt_value, pk
+        let columns: Vec<(String, String, bool, Option<String>, i64)> = query(
+            conn,
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid",
+            [&table],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+        let pk_count = columns.iter().filter(|c| c.4 > 0).count();
+
+        for (name, declared_type, not_null, default_value, pk) in columns {
+            let (data_type, length) = split_type(&declared_type);
+            let mut attr = Attribute::new(&name, &data_type);
+            attr.length = length;
+            attr.default_value = default_value;
+            attr.is_primary_key = pk > 0;
+            attr.is_nullable = !not_null && pk == 0;
+            entity.attributes.push(attr);
+        }
+
+        // CREATE INDEX로 만든 인덱스는 Index로, UNIQUE 제약조건은 단일 컬럼만 컬럼 플래그로 표현
+        let indexes: Vec<(String, bool, String, bool)> = query(
+            conn,
+            "SELECT name, \"unique\", origin, partial FROM pragma_index_list(?1) ORDER BY name",
+            [&table],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        for (index, unique, origin, partial) in indexes {
+            if origin == "c" {
+                match sqlite_index(conn, &index, unique, partial)? {
+                    Some(index) => entity.indexes.push(index),
+                    None => warnings.push(format!("{}: 표현식 인덱스 '{}'는 건너뜀", table, index)),
+                }
+                continue;
+            }
+            if !unique || origin == "pk" {
+                continue;
+            }
+            let index_columns: Vec<Option<String>> = query(
+                conn,
+                "SELECT name FROM pragma_index_info(?1) ORDER BY seqno",
+                [&index],
+                |row| row.get(0),
+            )?;
+            match index_columns.into_iter().collect::<Option<Vec<String>>>() {
+                Some(columns) if columns.len() == 1 => {
+                    if let Some(attr) = entity.attributes.iter_mut().find(|a| a.physical_name == columns[0]) {
+                        attr.is_unique = true;
+                    }
+                }
+                // sqlite_autoindex_* 이름 대신 제약조건 이름 규칙을 따름
+                Some(columns) => entity.unique_constraints.push(UniqueConstraint {
+                    name: format!("uq_{}_{}", table, columns.join("_")),
+                    columns,
+                }),
+                None => warnings.push(format!("{}: 표현식 UNIQUE 인덱스 '{}'는 건너뜀", table, index)),
+            }
+        }
+
+        // CHECK 제약조건과 AUTOINCREMENT는 카탈로그에 없으므로 CREATE TABLE 문을 파싱해서 가져옴
+        if let Some(parsed) = sql_import::parse_sql(&create_sql).diagram.entities.into_values().next() {
+            entity.check_constraints = parsed.check_constraints;
+            for attr in entity.attributes.iter_mut() {
+                let definition = parsed.attributes.iter().find(|p| p.physical_name.eq_ignore_ascii_case(&attr.physical_name));
+                attr.check = definition.and_then(|p| p.check.clone());
+                // AUTOINCREMENT는 단일 INTEGER PRIMARY KEY 컬럼 정의에만 붙을 수 있음
+                attr.is_auto_increment = attr.is_primary_key
+                    && pk_count == 1
+                    && attr.data_type == "INTEGER"
+                    && definition.is_some_and(|p| p.is_auto_increment);
+            }
+        }
+
+        // id, seq, table, from, to - id가 같은 행들이 하나의 (복합) FK
+        let references: Vec<(i64, String, String, Option<String>)> = query(
+            conn,
+            "SELECT id, \"table\", \"from\", \"to\" FROM pragma_foreign_key_list(?1) ORDER BY id, seq",
+            [&table],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        let mut grouped: BTreeMap<i64, ForeignKey> = BTreeMap::new();
+        for (id, referenced_table, from, to) in references {
+            let fk = grouped.entry(id).or_insert_with(|| ForeignKey {
+                name: String::new(),
+                table: table.clone(),
+                columns: Vec::new(),
+                referenced_table,
+                referenced_columns: Vec::new(),
+            });
+            fk.columns.push(from);
+            // 참조 컬럼을 생략한 FK는 부모 PK를 참조
+            if let Some(to) = to {
+                fk.referenced_columns.push(to);
+            }
+        }
+        for mut fk in grouped.into_values() {
+            fk.name = format!("fk_{}_{}", fk.table, fk.columns.join("_"));
+            foreign_keys.push(fk);
+        }
+
+        diagram.entities.insert(entity.id.clone(), entity);
+    }
+
+    apply_foreign_keys(&mut diagram, foreign_keys, &mut warnings);
+    diagram.arrange_grid();
+    diagram.normalize();
+
+    Ok(SqlImportResult { diagram, warnings })
+}
+
+// 키 컬럼과 정렬 방향, 부분 인덱스 조건 (표현식 컬럼이 있으면 None)
+fn sqlite_index(conn: &Connection, name: &str, is_unique: bool, partial: bool) -> Result<Option<Index>, String> {
+    let keys: Vec<(Option<String>, bool)> = query(
+        conn,
+        "SELECT name, \"desc\" FROM pragma_index_xinfo(?1) WHERE key = 1 ORDER BY seqno",
+        [name],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let Some(columns) = keys
+        .into_iter()
+        .map(|(column, desc)| {
+            column.map(|name| IndexColumn { name, order: if desc { SortOrder::Desc } else { SortOrder::Asc } })
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+
+    let mut predicate = None;
+    if partial {
+        let create_sql: Vec<String> =
+            query(conn, "SELECT sql FROM sqlite_master WHERE type = 'index' AND name = ?1", [name], |row| row.get(0))?;
+        predicate = create_sql.first().and_then(|sql| sql_import::where_clause(sql));
+    }
+
+    Ok(Some(Index {
+        name: name.to_string(),
+        columns,
+        is_unique,
+        predicate,
+        method: IndexMethod::BTree,
+    }))
+}
+
+// PostgreSQL 스키마를 pg_catalog에서 읽어 다이어그램 생성
+pub fn postgres(connection_string: &str, schema: &str) -> Result<SqlImportResult, String> {
+    // TLS 연결은 지원하지 않음 (sslmode=require면 접속 시도 전에 안내)
+    let config: Config = connection_string.parse().map_err(|e| format!("접속 문자열이 올바르지 않습니다: {}", e))?;
+    if config.get_ssl_mode() == SslMode::Require {
+        return Err("TLS 연결(sslmode=require)은 지원하지 않습니다. sslmode=disable 또는 prefer로 접속해 주세요.".to_string());
+    }
+    let mut client = config
+        .connect(NoTls)
+        .map_err(|e| format!("PostgreSQL에 연결할 수 없습니다 (TLS 미지원): {}", e))?;
+
+    let mut diagram = ErdDiagram::default();
+    let mut warnings = Vec::new();
+    let mut foreign_keys = Vec::new();
+
+    let schema_exists = client
+        .query_opt("SELECT 1 FROM pg_namespace WHERE nspname = $1", &[&schema])
+        .map_err(|e| format!("스키마 조회 실패: {}", e))?;
+    if schema_exists.is_none() {
+        return Err(format!("스키마 '{}'이(가) 존재하지 않습니다.", schema));
+    }
+
+    let enums = client
+        .query(
+            "SELECT t.typname::text, ARRAY(SELECT e.enumlabel::text FROM pg_enum e WHERE e.enumtypid = t.oid ORDER BY e.enumsortorder)
+             FROM pg_type t
+             JOIN pg_namespace n ON n.oid = t.typnamespace
+             WHERE n.nspname = $1 AND t.typtype = 'e'
+             ORDER BY t.typname",
+            &[&schema],
+        )
+        .map_err(|e| format!("열거형 조회 실패: {}", e))?;
+    for row in enums {
+        diagram.user_types.push(UserType::Enum(EnumType { name: row.get(0), values: row.get(1) }));
+    }
+
+    // 도메인 CHECK가 여러 개면 AND로 묶음
+    let domains = client
+        .query(
+            "SELECT t.typname::text, format_type(t.typbasetype, t.typtypmod), t.typdefault,
+                    ARRAY(SELECT pg_get_constraintdef(con.oid) FROM pg_constraint con
+                          WHERE con.contypid = t.oid AND con.contype = 'c' ORDER BY con.conname)
+             FROM pg_type t
+             JOIN pg_namespace n ON n.oid = t.typnamespace
+             WHERE n.nspname = $1 AND t.typtype = 'd'
+             ORDER BY t.typname",
+            &[&schema],
+        )
+        .map_err(|e| format!("도메인 조회 실패: {}", e))?;
+    for row in domains {
+        let name: String = row.get(0);
+        let (base_type, length) = split_type(&row.get::<_, String>(1));
+        let checks: Vec<String> = row.get::<_, Vec<String>>(3).iter().map(|c| check_expression(c)).collect();
+        let check = match checks.as_slice() {
+            [] => None,
+            [single] => Some(single.clone()),
+            many => Some(many.iter().map(|c| format!("({})", c)).collect::<Vec<_>>().join(" AND ")),
+        };
+        diagram.user_types.push(UserType::Domain(DomainType {
+            name,
+            base_type,
+            length,
+            default_value: row.get(2),
+            check,
+        }));
+    }
+
+    // 일반/파티션 테이블의 컬럼 (삭제된 컬럼, 시스템 컬럼 제외)
+    let columns = client
+        .query(
+            "SELECT c.relname::text, a.attname::text, format_type(a.atttypid, a.atttypmod), a.attnotnull,
+                    pg_get_expr(d.adbin, d.adrelid), a.attidentity::text, col_description(c.oid, a.attnum),
+                    CASE WHEN ty.typtype IN ('e', 'd') AND ty.typnamespace = n.oid THEN ty.typname::text END
+             FROM pg_class c
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             LEFT JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped
+             LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+             LEFT JOIN pg_type ty ON ty.oid = a.atttypid
+             WHERE n.nspname = $1 AND c.relkind IN ('r', 'p') AND NOT c.relispartition
+             ORDER BY c.relname, a.attnum",
+            &[&schema],
+        )
+        .map_err(|e| format!("컬럼 조회 실패: {}", e))?;
+
+    for row in columns {
+        let table: String = row.get(0);
+        let entity_id = format!("entity_{}", table);
+        let entity = diagram
+            .entities
+            .entry(entity_id.clone())
+            .or_insert_with(|| Entity::new(&entity_id, &table));
+
+        let Some(name) = row.get::<_, Option<String>>(1) else { continue };
+        // 같은 스키마의 열거형/도메인은 타입 이름으로 참조
+        let (data_type, length) = match row.get::<_, Option<String>>(7) {
+            Some(user_type) => (user_type, None),
+            None => split_type(&row.get::<_, String>(2)),
+        };
+        let default_value: Option<String> = row.get(4);
+        let identity: String = row.get(5);
+
+        let mut attr = Attribute::new(&name, &data_type);
+        attr.length = length;
+        attr.is_nullable = !row.get::<_, bool>(3);
+        attr.remark = row.get(6);
+        // serial/bigserial은 nextval() 기본값, IDENTITY는 attidentity로 판별
+        let is_serial = default_value.as_deref().is_some_and(|d| d.starts_with("nextval("));
+        attr.is_auto_increment = is_serial || !identity.is_empty();
+        attr.default_value = default_value.filter(|_| !is_serial);
+        entity.attributes.push(attr);
+    }
+
+    // PK / UNIQUE / FK 제약조건 (컬럼 순서 유지)
+    let constraints = client
+        .query(
+            "SELECT con.conname::text, con.contype::text, c.relname::text,
+                    ARRAY(SELECT a.attname::text FROM unnest(con.conkey) WITH ORDINALITY k(attnum, ord)
+                          JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum ORDER BY k.ord),
+                    rc.relname::text,
+                    ARRAY(SELECT a.attname::text FROM unnest(con.confkey) WITH ORDINALITY k(attnum, ord)
+                          JOIN pg_attribute a ON a.attrelid = con.confrelid AND a.attnum = k.attnum ORDER BY k.ord),
+                    rn.nspname::text, pg_get_constraintdef(con.oid)
+             FROM pg_constraint con
+             JOIN pg_class c ON c.oid = con.conrelid
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             LEFT JOIN pg_class rc ON rc.oid = con.confrelid
+             LEFT JOIN pg_namespace rn ON rn.oid = rc.relnamespace
+             WHERE n.nspname = $1 AND con.contype IN ('p', 'u', 'f', 'c') AND NOT c.relispartition
+             ORDER BY c.relname, con.conname",
+            &[&schema],
+        )
+        .map_err(|e| format!("제약조건 조회 실패: {}", e))?;
+
+    for row in constraints {
+        let name: String = row.get(0);
+        let kind: String = row.get(1);
+        let table: String = row.get(2);
+        let columns: Vec<String> = row.get(3);
+        let Some(entity) = diagram.entities.get_mut(&format!("entity_{}", table)) else { continue };
+
+        match kind.as_str() {
+            "p" => {
+                for attr in entity.attributes.iter_mut().filter(|a| columns.contains(&a.physical_name)) {
+                    attr.is_primary_key = true;
+                    attr.is_nullable = false;
+                }
+            }
+            "u" if columns.len() == 1 => {
+                if let Some(attr) = entity.attributes.iter_mut().find(|a| a.physical_name == columns[0]) {
+                    attr.is_unique = true;
+                }
+            }
+            "u" => entity.unique_constraints.push(UniqueConstraint { name, columns }),
+            "c" => {
+                let definition: String = row.get(7);
+                let expression = check_expression(&definition);
+                // 컬럼에 선언한 CHECK는 기본 이름(테이블_컬럼_check)으로 구분
+                let column_check = match columns.as_slice() {
+                    [column] if name == format!("{}_{}_check", table, column) || name == format!("ck_{}_{}", table, column) => {
+                        entity.attributes.iter_mut().find(|a| &a.physical_name == column)
+                    }
+                    _ => None,
+                };
+                match column_check {
+                    Some(attr) => attr.check = Some(expression),
+                    None => entity.check_constraints.push(CheckConstraint { name, expression }),
+                }
+            }
+            _ => {
+                let referenced_schema: Option<String> = row.get(6);
+                if referenced_schema.as_deref() != Some(schema) {
+                    warnings.push(format!("{}: FK '{}'는 다른 스키마를 참조하여 건너뜀", table, name));
+                    continue;
+                }
+                foreign_keys.push(ForeignKey {
+                    name,
+                    table,
+                    columns,
+                    referenced_table: row.get::<_, Option<String>>(4).unwrap_or_default(),
+                    referenced_columns: row.get(5),
+                });
+            }
+        }
+    }
+
+    // 제약조건이 만든 인덱스를 제외한 인덱스 (키 컬럼은 식일 수 있어 pg_get_indexdef 사용)
+    let indexes = client
+        .query(
+            "SELECT c.relname::text, i.relname::text, ix.indisunique, am.amname::text,
+                    ARRAY(SELECT pg_get_indexdef(ix.indexrelid, k.ord::int, true)
+                          FROM generate_series(1, ix.indnkeyatts) k(ord) ORDER BY k.ord),
+                    ARRAY(SELECT (ix.indoption[k.ord - 1] & 1) = 1
+                          FROM generate_series(1, ix.indnkeyatts) k(ord) ORDER BY k.ord),
+                    pg_get_expr(ix.indpred, ix.indrelid)
+             FROM pg_index ix
+             JOIN pg_class i ON i.oid = ix.indexrelid
+             JOIN pg_class c ON c.oid = ix.indrelid
+             JOIN pg_namespace n ON n.oid = c.relnamespace
+             JOIN pg_am am ON am.oid = i.relam
+             WHERE n.nspname = $1 AND NOT ix.indisprimary AND NOT c.relispartition
+               AND NOT EXISTS (SELECT 1 FROM pg_constraint con WHERE con.conindid = ix.indexrelid)
+             ORDER BY c.relname, i.relname",
+            &[&schema],
+        )
+        .map_err(|e| format!("인덱스 조회 실패: {}", e))?;
+
+    for row in indexes {
+        let table: String = row.get(0);
+        let name: String = row.get(1);
+        let Some(entity) = diagram.entities.get_mut(&format!("entity_{}", table)) else { continue };
+
+        let method = match row.get::<_, String>(3).as_str() {
+            "btree" => IndexMethod::BTree,
+            "hash" => IndexMethod::Hash,
+            "gin" => IndexMethod::Gin,
+            other => {
+                warnings.push(format!("{}: 인덱스 '{}'의 방식 '{}'은(는) 지원하지 않아 건너뜀", table, name, other));
+                continue;
+            }
+        };
+        let columns: Vec<String> = row.get(4);
+        let descending: Vec<bool> = row.get(5);
+        entity.indexes.push(Index {
+            name,
+            columns: columns
+                .into_iter()
+                .zip(descending)
+                .map(|(name, desc)| IndexColumn { name, order: if desc { SortOrder::Desc } else { SortOrder::Asc } })
+                .collect(),
+            is_unique: row.get(2),
+            predicate: row.get(6),
+            method,
+        });
+    }
+
+    apply_foreign_keys(&mut diagram, foreign_keys, &mut warnings);
+    diagram.arrange_grid();
+    diagram.normalize();
+
+    Ok(SqlImportResult { diagram, warnings })
+}
+
+// "CHECK ((amount >= 0)) NOT VALID" -> "amount >= 0"
+fn check_expression(definition: &str) -> String {
+    let mut expression = definition.trim();
+    expression = expression.strip_prefix("CHECK").unwrap_or(expression).trim();
+    expression = expression.strip_suffix("NOT VALID").unwrap_or(expression).trim();
+    while let Some(inner) = expression.strip_prefix('(').and_then(|e| e.strip_suffix(')')) {
+        // "(a > 0) AND (b > 0)"처럼 바깥 괄호가 한 쌍이 아니면 그대로 둠
+        let mut depth = 0;
+        let balanced = inner.chars().all(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        });
+        if !balanced {
+            break;
+        }
+        expression = inner.trim();
+    }
+    expression.to_string()
+}
+
+// "VARCHAR(255)" -> ("VARCHAR", Some("255"))
+pub fn split_type(declared: &str) -> (String, Option<String>) {
+    let declared = declared.trim();
+    match (declared.find('('), declared.rfind(')')) {
+        (Some(open), Some(close)) if open < close => {
+            let base = format!("{}{}", declared[..open].trim(), &declared[close + 1..]);
+            let length = declared[open + 1..close].replace(' ', "");
+            (base.trim().to_uppercase(), Some(length).filter(|l| !l.is_empty()))
+        }
+        _ => (declared.to_uppercase(), None),
+    }
+}
+
+fn query<T, P: rusqlite::Params>(
+    conn: &Connection,
+    sql: &str,
+    params: P,
+    map: impl FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> Result<Vec<T>, String> {
+    let mut statement = conn.prepare(sql).map_err(|e| format!("스키마 조회 실패: {}", e))?;
+    let rows = statement
+        .query_map(params, map)
+        .map_err(|e| format!("스키마 조회 실패: {}", e))?
+        .collect::<rusqlite::Result<Vec<T>>>()
+        .map_err(|e| format!("스키마 조회 실패: {}", e))?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(sql: &str) -> SqlImportResult {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(sql).unwrap();
+        sqlite_connection(&conn).unwrap()
+    }
+
+    fn attribute<'a>(result: &'a SqlImportResult, table: &str, column: &str) -> &'a Attribute {
+        let entity = &result.diagram.entities[&format!("entity_{}", table)];
+        entity.attributes.iter().find(|a| a.physical_name == column).expect("컬럼이 없음")
+    }
+
+    fn predicate(result: &SqlImportResult) -> Option<&str> {
+        let index = result.diagram.entities.values().flat_map(|e| e.indexes.iter()).next().expect("인덱스가 없음");
+        index.predicate.as_deref()
+    }
+
+    #[test]
+    fn sqlite_primary_keys() {
+        let result = import(
+            "CREATE TABLE a (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE line (order_id INT, line_no INT, qty INT, PRIMARY KEY (order_id, line_no));",
+        );
+        assert!(attribute(&result, "a", "id").is_primary_key);
+        assert!(!attribute(&result, "a", "name").is_primary_key);
+        assert!(attribute(&result, "line", "order_id").is_primary_key);
+        assert!(attribute(&result, "line", "line_no").is_primary_key);
+        assert!(!attribute(&result, "line", "qty").is_primary_key);
+    }
+
+    #[test]
+    fn sqlite_not_null_and_defaults() {
+        let result = import("CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT NOT NULL DEFAULT 'x', n INT DEFAULT 0, note VARCHAR(20));");
+        let name = attribute(&result, "t", "name");
+        assert!(!name.is_nullable);
+        assert_eq!(name.default_value.as_deref(), Some("'x'"));
+        assert_eq!(attribute(&result, "t", "n").default_value.as_deref(), Some("0"));
+        let note = attribute(&result, "t", "note");
+        assert!(note.is_nullable);
+        assert_eq!((note.data_type.as_str(), note.length.as_deref()), ("VARCHAR", Some("20")));
+        assert!(!attribute(&result, "t", "id").is_nullable);
+    }
+
+    #[test]
+    fn sqlite_foreign_keys_become_relations() {
+        let result = import(
+            "CREATE TABLE orders (id INT, line INT, PRIMARY KEY (id, line));
+             CREATE TABLE ship (id INTEGER PRIMARY KEY, order_id INT, order_line INT,
+                 FOREIGN KEY (order_id, order_line) REFERENCES orders (id, line));
+             CREATE TABLE note (id INTEGER PRIMARY KEY, ship_id INT REFERENCES ship);",
+        );
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.diagram.relations.len(), 2);
+
+        let composite = result.diagram.relations.iter().find(|r| r.to_entity_id == "entity_ship").unwrap();
+        assert_eq!(composite.from_entity_id, "entity_orders");
+        let pairs: Vec<(String, Option<String>)> = composite.pairs().into_iter().map(|p| (p.from, p.to)).collect();
+        assert_eq!(
+            pairs,
+            [("id".to_string(), Some("order_id".to_string())), ("line".to_string(), Some("order_line".to_string()))]
+        );
+
+        // 참조 컬럼을 생략하면 부모 PK
+        let implicit = result.diagram.relations.iter().find(|r| r.to_entity_id == "entity_note").unwrap();
+        assert_eq!(implicit.pairs()[0].from, "id");
+        assert!(attribute(&result, "note", "ship_id").is_foreign_key);
+    }
+
+    // 다른 곳(기본값 문자열 등)에 AUTOINCREMENT가 있어도 PK 컬럼 정의에 없으면 자동 증가가 아님
+    #[test]
+    fn sqlite_autoincrement_only_from_the_key_definition() {
+        let result = import(
+            "CREATE TABLE a (id INTEGER PRIMARY KEY AUTOINCREMENT, note TEXT);
+             CREATE TABLE b (id INTEGER PRIMARY KEY, note TEXT DEFAULT 'AUTOINCREMENT');",
+        );
+        assert!(attribute(&result, "a", "id").is_auto_increment);
+        assert!(!attribute(&result, "b", "id").is_auto_increment);
+        assert!(!attribute(&result, "b", "note").is_auto_increment);
+    }
+
+    // ı(2바이트)는 대문자로 I(1바이트)가 되어 to_uppercase한 문자열의 위치가 원문과 어긋남
+    #[test]
+    fn sqlite_partial_index_predicate_with_non_ascii_names() {
+        let result = import(
+            "CREATE TABLE kısıt (id INTEGER PRIMARY KEY, değer INTEGER);
+             CREATE INDEX idx_kısıt_değer ON kısıt (değer) WHERE değer > 0;",
+        );
+        assert_eq!(predicate(&result), Some("değer > 0"));
+    }
+
+    // WHERE 앞이 줄바꿈/탭이고 조건 안의 문자열에도 WHERE가 있는 경우
+    #[test]
+    fn sqlite_multi_line_partial_index_predicate() {
+        let result = import(
+            "CREATE TABLE t (id INTEGER PRIMARY KEY, b INTEGER, note TEXT);
+             CREATE INDEX idx_t_b\n  ON t (b)\nWHERE\tb > 0\n  AND note <> ' WHERE x';",
+        );
+        assert_eq!(predicate(&result), Some("b > 0\n  AND note <> ' WHERE x'"));
+    }
+
+    #[test]
+    fn postgres_rejects_required_tls() {
+        let result = postgres("host=127.0.0.1 user=postgres sslmode=require", "public");
+        assert!(result.err().is_some_and(|e| e.contains("TLS")));
+    }
+
+    // 실제 서버가 필요: ERD_TEST_POSTGRES_URL="host=127.0.0.1 port=5432 user=postgres password=..." cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn postgres_reads_a_schema() {
+        let url = std::env::var("ERD_TEST_POSTGRES_URL").expect("ERD_TEST_POSTGRES_URL이 없음");
+        let schema = format!("erd_introspect_{}", std::process::id());
+        let mut client = postgres::Client::connect(&url, NoTls).unwrap();
+        client
+            .batch_execute(&format!(
+                "DROP SCHEMA IF EXISTS {0} CASCADE;
+                 CREATE SCHEMA {0};
+                 CREATE TYPE {0}.status AS ENUM ('open', 'closed');
+                 CREATE TABLE {0}.orders (id SERIAL PRIMARY KEY, status {0}.status NOT NULL DEFAULT 'open', title VARCHAR(50) UNIQUE);
+                 CREATE TABLE {0}.lines (order_id INT REFERENCES {0}.orders (id), line_no INT, qty INT CHECK (qty > 0),
+                     PRIMARY KEY (order_id, line_no));
+                 CREATE INDEX idx_lines_qty ON {0}.lines (qty) WHERE qty > 10;",
+                schema
+            ))
+            .unwrap();
+        let result = postgres(&url, &schema);
+        client.batch_execute(&format!("DROP SCHEMA {} CASCADE", schema)).unwrap();
+        let result = result.unwrap();
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+        let id = attribute(&result, "orders", "id");
+        assert!(id.is_primary_key && id.is_auto_increment);
+        assert_eq!(attribute(&result, "orders", "status").data_type, "status");
+        assert!(attribute(&result, "orders", "title").is_unique);
+        assert!(attribute(&result, "lines", "order_id").is_primary_key && attribute(&result, "lines", "line_no").is_primary_key);
+        assert_eq!(attribute(&result, "lines", "qty").check.as_deref(), Some("qty > 0"));
+        assert_eq!(predicate(&result), Some("(qty > 10)"));
+        assert!(matches!(&result.diagram.user_types[..], [UserType::Enum(e)] if e.values == ["open", "closed"]));
+
+        let relation = &result.diagram.relations[0];
+        assert_eq!((relation.from_entity_id.as_str(), relation.to_entity_id.as_str()), ("entity_orders", "entity_lines"));
+        assert_eq!(relation.pairs()[0].to.as_deref(), Some("order_id"));
+    }
+}
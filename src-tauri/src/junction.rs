@@ -0,0 +1,237 @@
+use crate::erd::{Cardinality, ColumnPair, Entity, ErdDiagram, Index, IndexColumn, IndexMethod, Multiplicity, Relation, SortOrder};
+use crate::relations;
+
+// N:M 관계를 연결 엔티티와 두 개의 1:N 관계로 바꿈
+// 연결 엔티티의 PK는 양쪽 PK를 참조하는 FK 컬럼이고, 원래 관계는 되돌리기용으로 엔티티에 보관
+pub fn resolve(diagram: &ErdDiagram, relation_id: &str) -> Result<ErdDiagram, String> {
+    let position = diagram
+        .relations
+        .iter()
+        .position(|r| r.id == relation_id)
+        .ok_or_else(|| format!("관계 '{}'이(가) 없습니다.", relation_id))?;
+    let relation = &diagram.relations[position];
+    if !matches!(relation.cardinality, Cardinality::ManyToMany) {
+        return Err(format!("관계 '{}'는 N:M 관계가 아닙니다.", relation.name));
+    }
+    let from = diagram
+        .entities
+        .get(&relation.from_entity_id)
+        .ok_or_else(|| format!("관계 '{}'의 엔티티 '{}'이(가) 없습니다.", relation.name, relation.from_entity_id))?;
+    let to = diagram
+        .entities
+        .get(&relation.to_entity_id)
+        .ok_or_else(|| format!("관계 '{}'의 엔티티 '{}'이(가) 없습니다.", relation.name, relation.to_entity_id))?;
+    for entity in [from, to] {
+        if !entity.attributes.iter().any(|a| a.is_primary_key) {
+            return Err(format!("'{}'에 기본 키가 없어 연결 엔티티를 만들 수 없습니다.", entity.physical_name));
+        }
+    }
+
+    let physical_name = unique_table_name(diagram, from.schema_name(), &format!("{}_{}", from.physical_name, to.physical_name));
+    let mut id = format!("entity_{}", physical_name);
+    while diagram.entities.contains_key(&id) {
+        id.push('_');
+    }
+    let mut junction = Entity::new(&id, &physical_name);
+    junction.logical_name = format!("{}_{}", from.logical_name, to.logical_name);
+    junction.schema = from.schema.clone();
+    junction.resolved_relation = Some(relation.clone());
+
+    // 두 엔티티 중심의 가운데에 배치
+    let center_x = (from.x + from.width / 2.0 + to.x + to.width / 2.0) / 2.0;
+    let center_y = (from.y + from.height / 2.0 + to.y + to.height / 2.0) / 2.0;
+    junction.x = center_x - junction.width / 2.0;
+    junction.y = center_y - junction.height / 2.0;
+
+    // 원래 관계에서 A 하나가 B를 최소 몇 개 가지는지가 A → 연결 엔티티의 최소 참여 수가 됨
+    let (from_end, to_end) = relation.multiplicities();
+    let from_pairs = add_key_columns(&mut junction, from);
+    let to_pairs = add_key_columns(&mut junction, to);
+    // PK가 from 쪽 컬럼으로 시작하므로 to 쪽 FK로 찾는 조회용 인덱스 추가
+    let columns: Vec<String> = to_pairs.iter().filter_map(|p| p.to.clone()).collect();
+    junction.indexes.push(Index {
+        name: format!("ix_{}_{}", physical_name, columns.join("_")),
+        columns: columns.into_iter().map(|name| IndexColumn { name, order: SortOrder::Asc }).collect(),
+        is_unique: false,
+        predicate: None,
+        method: IndexMethod::BTree,
+    });
+    let from_relation = junction_relation(relation, "from", from, &junction, from_pairs, to_end.min);
+    let to_relation = junction_relation(relation, "to", to, &junction, to_pairs, from_end.min);
+
+    let mut diagram = diagram.clone();
+    diagram.relations.remove(position);
+    diagram.relations.insert(position, to_relation);
+    diagram.relations.insert(position, from_relation);
+    diagram.entities.insert(id, junction);
+    Ok(diagram)
+}
+
+// resolve로 만든 연결 엔티티를 지우고 원래 N:M 관계를 복원
+pub fn restore(diagram: &ErdDiagram, entity_id: &str) -> Result<ErdDiagram, String> {
+    let junction = diagram
+        .entities
+        .get(entity_id)
+        .ok_or_else(|| format!("엔티티 '{}'이(가) 없습니다.", entity_id))?;
+    let original = junction
+        .resolved_relation
+        .clone()
+        .ok_or_else(|| format!("'{}'는 N:M 관계로 만든 연결 엔티티가 아닙니다.", junction.physical_name))?;
+    for id in [&original.from_entity_id, &original.to_entity_id] {
+        if !diagram.entities.contains_key(id) {
+            return Err(format!("원래 관계의 엔티티 '{}'이(가) 없어 되돌릴 수 없습니다.", id));
+        }
+    }
+
+    // 생성 후 추가된 컬럼이나 관계가 있으면 지워지지 않도록 거부
+    let generated = [format!("{}_from", original.id), format!("{}_to", original.id)];
+    let attached: Vec<&Relation> = diagram
+        .relations
+        .iter()
+        .filter(|r| r.from_entity_id == entity_id || r.to_entity_id == entity_id)
+        .collect();
+    if attached.iter().any(|r| !generated.contains(&r.id)) {
+        return Err(format!("'{}'에 다른 관계가 연결되어 있어 되돌릴 수 없습니다.", junction.physical_name));
+    }
+    if let Some(extra) = junction.attributes.iter().find(|a| !a.is_foreign_key) {
+        return Err(format!("'{}'에 추가된 속성 '{}'이(가) 있어 되돌릴 수 없습니다.", junction.physical_name, extra.physical_name));
+    }
+
+    let mut diagram = diagram.clone();
+    let position = diagram.relations.iter().position(|r| generated.contains(&r.id)).unwrap_or(diagram.relations.len());
+    diagram.relations.retain(|r| r.from_entity_id != entity_id && r.to_entity_id != entity_id);
+    diagram.relations.insert(position.min(diagram.relations.len()), original);
+    diagram.entities.remove(entity_id);
+    Ok(diagram)
+}
+
+// 물리 DDL용으로 모든 N:M 관계를 연결 엔티티로 풀어낸 사본 (풀 수 없는 관계는 그대로 둠)
+pub fn expand_all(diagram: &ErdDiagram) -> ErdDiagram {
+    let ids: Vec<String> = diagram
+        .relations
+        .iter()
+        .filter(|r| matches!(r.cardinality, Cardinality::ManyToMany))
+        .map(|r| r.id.clone())
+        .collect();
+    let mut expanded = diagram.clone();
+    for id in ids {
+        if let Ok(resolved) = resolve(&expanded, &id) {
+            expanded = resolved;
+        }
+    }
+    expanded
+}
+
+// 부모 PK마다 "부모테이블_컬럼" FK 컬럼을 추가하고 (부모 컬럼, FK 컬럼) 쌍을 반환
+fn add_key_columns(junction: &mut Entity, parent: &Entity) -> Vec<ColumnPair> {
+    let mut pairs = Vec::new();
+    for pk in parent.attributes.iter().filter(|a| a.is_primary_key) {
+        let mut name = format!("{}_{}", parent.physical_name, pk.physical_name);
+        while junction.attributes.iter().any(|a| a.physical_name.eq_ignore_ascii_case(&name)) {
+            name.push_str("_2");
+        }
+        let mut attr = relations::foreign_key_attribute(parent, pk, &name);
+        attr.is_primary_key = true;
+        attr.is_nullable = false;
+        junction.attributes.push(attr);
+        pairs.push(ColumnPair::new(pk.physical_name.clone(), Some(name)));
+    }
+    pairs
+}
+
+fn junction_relation(original: &Relation, side: &str, parent: &Entity, junction: &Entity, pairs: Vec<ColumnPair>, min: u32) -> Relation {
+    let mut relation = Relation {
+        id: format!("{}_{}", original.id, side),
+        from_entity_id: parent.id.clone(),
+        from_attribute: String::new(),
+        to_entity_id: junction.id.clone(),
+        to_attribute: None,
+        columns: Vec::new(),
+        cardinality: Cardinality::OneToMany,
+        name: format!("{}_{}", original.name, parent.physical_name),
+        from_multiplicity: Some(Multiplicity::EXACTLY_ONE),
+        to_multiplicity: Some(Multiplicity { min, max: None }),
+        is_identifying: true,
+    };
+    relation.set_pairs(pairs);
+    relation
+}
+
+// 같은 스키마에 없는 테이블 이름 (겹치면 _2, _3 ...)
+fn unique_table_name(diagram: &ErdDiagram, schema: Option<&str>, base: &str) -> String {
+    let taken = |name: &str| {
+        diagram
+            .entities
+            .values()
+            .any(|e| e.schema_name() == schema && e.physical_name.eq_ignore_ascii_case(name))
+    };
+    let mut name = base.to_string();
+    let mut n = 2;
+    while taken(&name) {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    // 학생 N:M 강의
+    fn enrollment() -> ErdDiagram {
+        let mut diagram = parse_sql(
+            "CREATE TABLE student (id INT PRIMARY KEY, name VARCHAR(20));
+             CREATE TABLE course (id INT PRIMARY KEY, title VARCHAR(50));",
+        )
+        .diagram;
+        let relation: Relation = serde_json::from_value(serde_json::json!({
+            "id": "rel_enroll", "name": "enrolls", "from_entity_id": "entity_student",
+            "to_entity_id": "entity_course", "cardinality": "ManyToMany"
+        }))
+        .unwrap();
+        diagram.relations.push(relation);
+        diagram
+    }
+
+    #[test]
+    fn resolve_creates_a_junction_entity() {
+        let diagram = resolve(&enrollment(), "rel_enroll").unwrap();
+        assert_eq!(diagram.entities.len(), 3);
+        assert!(!diagram.relations.iter().any(|r| r.id == "rel_enroll"));
+        assert_eq!(diagram.relations.len(), 2);
+        assert!(diagram.relations.iter().all(|r| matches!(r.cardinality, Cardinality::OneToMany)));
+
+        let junction = diagram.entities.values().find(|e| e.resolved_relation.is_some()).unwrap();
+        let keys = junction.attributes.iter().filter(|a| a.is_primary_key && a.is_foreign_key).count();
+        assert_eq!(keys, 2);
+    }
+
+    #[test]
+    fn restore_brings_back_the_relation() {
+        let resolved = resolve(&enrollment(), "rel_enroll").unwrap();
+        let junction_id = resolved.entities.values().find(|e| e.resolved_relation.is_some()).unwrap().id.clone();
+        let restored = restore(&resolved, &junction_id).unwrap();
+        assert_eq!(restored.entities.len(), 2);
+        assert_eq!(restored.relations.len(), 1);
+        assert_eq!(restored.relations[0].id, "rel_enroll");
+        assert!(matches!(restored.relations[0].cardinality, Cardinality::ManyToMany));
+    }
+
+    #[test]
+    fn only_many_to_many_can_be_resolved() {
+        let mut diagram = enrollment();
+        diagram.relations[0].cardinality = Cardinality::OneToMany;
+        assert!(resolve(&diagram, "rel_enroll").is_err());
+        assert!(restore(&diagram, "entity_student").is_err());
+    }
+
+    #[test]
+    fn expand_all_leaves_the_diagram_alone() {
+        let diagram = enrollment();
+        let expanded = expand_all(&diagram);
+        assert_eq!(diagram.entities.len(), 2);
+        assert_eq!(expanded.entities.len(), 3);
+    }
+}
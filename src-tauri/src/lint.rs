@@ -0,0 +1,456 @@
+use crate::erd::{Attribute, ColumnPair, Entity, ErdDiagram, Relation};
+use crate::hierarchy;
+use crate::naming::{self, NamingPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const MAX_ENTITIES: usize = 1000;
+const MAX_RELATIONS: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Rule {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: &'static str,
+}
+
+// 규칙 카탈로그 (UI에서 목록/설명 표시용)
+pub const RULES: &[Rule] = &[
+    Rule { id: "missing-primary-key", severity: Severity::Warning, description: "기본 키가 없는 엔티티" },
+    Rule { id: "dangling-relation", severity: Severity::Error, description: "존재하지 않는 엔티티를 가리키는 관계" },
+    Rule { id: "unknown-relation-attribute", severity: Severity::Error, description: "관계의 from/to 속성이 엔티티에 없음" },
+    Rule { id: "duplicate-table-name", severity: Severity::Error, description: "같은 스키마 안에서 중복된 엔티티 물리명" },
+    Rule { id: "duplicate-column-name", severity: Severity::Error, description: "엔티티 안에서 중복된 속성 물리명" },
+    Rule { id: "invalid-subtype-hierarchy", severity: Severity::Error, description: "슈퍼타입/서브타입 계층의 엔티티나 구분자가 없거나 순환함" },
+    Rule { id: "invalid-view", severity: Severity::Error, description: "정의 SQL이 비었거나 참조하는 엔티티가 없는 뷰" },
+    Rule { id: "fk-type-mismatch", severity: Severity::Warning, description: "FK 컬럼 타입이 참조하는 PK 타입과 다름" },
+    Rule { id: "missing-fk-index", severity: Severity::Warning, description: "FK 컬럼으로 시작하는 인덱스가 없음" },
+    Rule { id: "naming-case", severity: Severity::Warning, description: "명명 규칙의 대소문자 형식(snake_case 등)과 다른 물리명" },
+    Rule { id: "naming-table-prefix", severity: Severity::Warning, description: "명명 규칙의 테이블 접두사가 없는 테이블명" },
+    Rule { id: "naming-fk-suffix", severity: Severity::Warning, description: "명명 규칙의 FK 접미사가 없는 FK 컬럼명" },
+    Rule { id: "naming-max-length", severity: Severity::Warning, description: "명명 규칙의 최대 길이를 넘는 물리명" },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub entity_id: Option<String>,
+    pub attribute: Option<String>,
+    pub relation_id: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(rule: &str, message: String) -> Self {
+        let severity = RULES.iter().find(|r| r.id == rule).map(|r| r.severity).unwrap_or(Severity::Warning);
+        Diagnostic {
+            rule: rule.to_string(),
+            severity,
+            entity_id: None,
+            attribute: None,
+            relation_id: None,
+            message,
+        }
+    }
+
+    pub(crate) fn entity(mut self, entity: &Entity) -> Self {
+        self.entity_id = Some(entity.id.clone());
+        self
+    }
+
+    pub(crate) fn attribute(mut self, attr: &Attribute) -> Self {
+        self.attribute = Some(attr.physical_name.clone());
+        self
+    }
+
+    pub(crate) fn relation(mut self, relation_id: &str) -> Self {
+        self.relation_id = Some(relation_id.to_string());
+        self
+    }
+}
+
+// 불러오기 자체를 막아야 하는 크기 제한 (진단과 별개)
+pub fn check_limits(diagram: &ErdDiagram) -> Result<(), String> {
+    if diagram.entities.len() > MAX_ENTITIES {
+        return Err("엔티티가 너무 많습니다.".to_string());
+    }
+    if diagram.relations.len() > MAX_RELATIONS {
+        return Err("관계가 너무 많습니다.".to_string());
+    }
+    Ok(())
+}
+
+// 명명 규칙은 다이어그램에 저장된 것을 우선하고, 없으면 전달된 프로젝트 설정을 사용
+pub fn lint(diagram: &ErdDiagram, project_policy: Option<&NamingPolicy>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut entities: Vec<&Entity> = diagram.entities.values().collect();
+    entities.sort_by_key(|e| e.physical_name.to_lowercase());
+
+    check_primary_keys(&entities, &mut diagnostics);
+    check_duplicate_names(&entities, &mut diagnostics);
+    check_relations(diagram, &mut diagnostics);
+    check_hierarchies(diagram, &mut diagnostics);
+    check_views(diagram, &entities, &mut diagnostics);
+    if let Some(policy) = diagram.naming_policy.as_ref().or(project_policy) {
+        naming::check(diagram, policy, &mut diagnostics);
+    }
+
+    diagnostics.sort_by_key(|d| d.severity);
+    diagnostics
+}
+
+fn check_primary_keys(entities: &[&Entity], diagnostics: &mut Vec<Diagnostic>) {
+    for entity in entities.iter().filter(|e| !e.is_view()) {
+        if !entity.attributes.iter().any(|a| a.is_primary_key) {
+            diagnostics.push(
+                Diagnostic::new("missing-primary-key", format!("'{}'에 기본 키가 없습니다", entity.physical_name)).entity(entity),
+            );
+        }
+    }
+}
+
+fn check_duplicate_names(entities: &[&Entity], diagnostics: &mut Vec<Diagnostic>) {
+    // 스키마가 다르면 같은 물리명을 써도 됨
+    let mut tables: HashMap<String, Vec<&Entity>> = HashMap::new();
+    for entity in entities {
+        tables.entry(entity.qualified_name().to_lowercase()).or_default().push(entity);
+    }
+    for entity in entities {
+        let same = &tables[&entity.qualified_name().to_lowercase()];
+        if same.len() > 1 {
+            diagnostics.push(
+                Diagnostic::new(
+                    "duplicate-table-name",
+                    format!("물리명 '{}'을(를) 쓰는 엔티티가 {}개 있습니다", entity.qualified_name(), same.len()),
+                )
+                .entity(entity),
+            );
+        }
+
+        let mut columns: HashMap<String, usize> = HashMap::new();
+        for attr in &entity.attributes {
+            *columns.entry(attr.physical_name.to_lowercase()).or_default() += 1;
+        }
+        let mut reported: Vec<String> = Vec::new();
+        for attr in &entity.attributes {
+            let key = attr.physical_name.to_lowercase();
+            if columns[&key] > 1 && !reported.contains(&key) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "duplicate-column-name",
+                        format!("'{}'에 물리명 '{}' 속성이 {}개 있습니다", entity.physical_name, attr.physical_name, columns[&key]),
+                    )
+                    .entity(entity)
+                    .attribute(attr),
+                );
+                reported.push(key);
+            }
+        }
+    }
+}
+
+fn check_relations(diagram: &ErdDiagram, diagnostics: &mut Vec<Diagnostic>) {
+    for relation in &diagram.relations {
+        let parent = diagram.entities.get(&relation.from_entity_id);
+        let child = diagram.entities.get(&relation.to_entity_id);
+
+        for (end, id, entity) in [("from", &relation.from_entity_id, parent), ("to", &relation.to_entity_id, child)] {
+            if entity.is_none() {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "dangling-relation",
+                        format!("관계 '{}'의 {} 엔티티 '{}'이(가) 없습니다", relation.name, end, id),
+                    )
+                    .relation(&relation.id),
+                );
+            }
+        }
+        let (Some(parent), Some(child)) = (parent, child) else { continue };
+
+        let mut pairs = relation.pairs();
+        if pairs.is_empty() {
+            pairs.push(ColumnPair::new(String::new(), None));
+        }
+        for pair in &pairs {
+            check_column_pair(relation, pair, parent, child, diagnostics);
+        }
+        check_foreign_key_index(relation, child, diagnostics);
+    }
+}
+
+fn check_hierarchies(diagram: &ErdDiagram, diagnostics: &mut Vec<Diagnostic>) {
+    for hierarchy in &diagram.hierarchies {
+        for problem in hierarchy::problems(diagram, hierarchy) {
+            let mut diagnostic = Diagnostic::new("invalid-subtype-hierarchy", problem);
+            if let Some(supertype) = diagram.entities.get(&hierarchy.supertype_id) {
+                diagnostic = diagnostic.entity(supertype);
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+fn check_views(diagram: &ErdDiagram, entities: &[&Entity], diagnostics: &mut Vec<Diagnostic>) {
+    for view in entities.iter().filter(|e| e.is_view()) {
+        if view.view_query().is_none() {
+            diagnostics.push(
+                Diagnostic::new("invalid-view", format!("뷰 '{}'의 정의 SQL이 비어 있습니다", view.physical_name)).entity(view),
+            );
+        }
+        for id in view.view_dependencies.iter().filter(|id| !diagram.entities.contains_key(*id)) {
+            diagnostics.push(
+                Diagnostic::new("invalid-view", format!("뷰 '{}'이(가) 참조하는 엔티티 '{}'이(가) 없습니다", view.physical_name, id))
+                    .entity(view),
+            );
+        }
+    }
+}
+
+// FK 컬럼 집합이 인덱스(PK 포함)의 앞부분 컬럼과 일치하면 인덱스가 있는 것으로 봄
+fn check_foreign_key_index(relation: &Relation, child: &Entity, diagnostics: &mut Vec<Diagnostic>) {
+    if child.is_view() {
+        return;
+    }
+    let Some((_, columns)) = relation.foreign_key_columns() else { return };
+    let Some(columns) = columns
+        .iter()
+        .map(|c| find_attribute(child, c).map(|a| a.physical_name.to_lowercase()))
+        .collect::<Option<Vec<String>>>()
+    else {
+        return;
+    };
+
+    if let [column] = columns.as_slice() {
+        if child.attributes.iter().any(|a| a.is_unique && a.physical_name.eq_ignore_ascii_case(column)) {
+            return;
+        }
+    }
+
+    let primary_key: Vec<String> =
+        child.attributes.iter().filter(|a| a.is_primary_key).map(|a| a.physical_name.to_lowercase()).collect();
+    let mut candidates: Vec<Vec<String>> = vec![primary_key];
+    candidates.extend(child.indexes.iter().map(|i| i.column_names().iter().map(|c| c.to_lowercase()).collect()));
+
+    let covered = candidates.iter().any(|index| {
+        index.len() >= columns.len() && {
+            let mut leading = index[..columns.len()].to_vec();
+            let mut wanted = columns.clone();
+            leading.sort();
+            wanted.sort();
+            leading == wanted
+        }
+    });
+    if !covered {
+        diagnostics.push(
+            Diagnostic::new(
+                "missing-fk-index",
+                format!("'{}'의 FK 컬럼 ({})로 시작하는 인덱스가 없습니다", child.physical_name, columns.join(", ")),
+            )
+            .entity(child)
+            .relation(&relation.id),
+        );
+    }
+}
+
+fn check_column_pair(relation: &Relation, pair: &ColumnPair, parent: &Entity, child: &Entity, diagnostics: &mut Vec<Diagnostic>) {
+    let referenced = find_attribute(parent, &pair.from);
+    if referenced.is_none() {
+        diagnostics.push(
+            Diagnostic::new(
+                "unknown-relation-attribute",
+                format!("관계 '{}'의 from 속성 '{}'이(가) '{}'에 없습니다", relation.name, pair.from, parent.physical_name),
+            )
+            .entity(parent)
+            .relation(&relation.id),
+        );
+    }
+
+    let Some(to_attribute) = pair.to.as_deref().filter(|a| !a.trim().is_empty()) else { return };
+    let fk = find_attribute(child, to_attribute);
+    if fk.is_none() {
+        diagnostics.push(
+            Diagnostic::new(
+                "unknown-relation-attribute",
+                format!("관계 '{}'의 to 속성 '{}'이(가) '{}'에 없습니다", relation.name, to_attribute, child.physical_name),
+            )
+            .entity(child)
+            .relation(&relation.id),
+        );
+    }
+
+    if let (Some(pk), Some(fk)) = (referenced, fk) {
+        if type_signature(pk) != type_signature(fk) {
+            diagnostics.push(
+                Diagnostic::new(
+                    "fk-type-mismatch",
+                    format!(
+                        "'{}.{}'({})의 타입이 참조하는 '{}.{}'({})와 다릅니다",
+                        child.physical_name,
+                        fk.physical_name,
+                        type_signature(fk),
+                        parent.physical_name,
+                        pk.physical_name,
+                        type_signature(pk)
+                    ),
+                )
+                .entity(child)
+                .attribute(fk)
+                .relation(&relation.id),
+            );
+        }
+    }
+}
+
+// 관계는 물리명으로 속성을 참조하지만 예전 파일은 논리명을 쓰기도 함
+fn find_attribute<'a>(entity: &'a Entity, name: &str) -> Option<&'a Attribute> {
+    entity
+        .attributes
+        .iter()
+        .find(|a| a.physical_name.eq_ignore_ascii_case(name))
+        .or_else(|| entity.attributes.iter().find(|a| a.logical_name == name))
+}
+
+fn type_signature(attr: &Attribute) -> String {
+    let data_type = attr.data_type.trim().to_uppercase();
+    match attr.length.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+        Some(length) => format!("{}({})", data_type, length),
+        None => data_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    fn rules(sql: &str) -> Vec<String> {
+        lint(&parse_sql(sql).diagram, None).into_iter().map(|d| d.rule).collect()
+    }
+
+    fn has(sql: &str, rule: &str) -> bool {
+        rules(sql).iter().any(|r| r == rule)
+    }
+
+    const POSTS: &str = "CREATE TABLE users (id INT PRIMARY KEY);
+        CREATE TABLE posts (id INT PRIMARY KEY, user_id INT REFERENCES users (id), INDEX (user_id));";
+
+    #[test]
+    fn clean_diagram_has_no_diagnostics() {
+        assert_eq!(rules(POSTS), Vec::<String>::new());
+    }
+
+    #[test]
+    fn missing_primary_key() {
+        assert!(has("CREATE TABLE a (x INT);", "missing-primary-key"));
+        assert!(!has("CREATE TABLE a (x INT PRIMARY KEY);", "missing-primary-key"));
+        // 뷰는 PK가 없어도 됨
+        assert!(!has("CREATE TABLE a (x INT PRIMARY KEY); CREATE VIEW v AS SELECT x FROM a;", "missing-primary-key"));
+    }
+
+    #[test]
+    fn dangling_relation() {
+        let mut diagram = parse_sql(POSTS).diagram;
+        diagram.relations[0].from_entity_id = "entity_missing".to_string();
+        let diagnostics = lint(&diagram, None);
+        let dangling: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.rule == "dangling-relation").collect();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].severity, Severity::Error);
+        assert!(dangling[0].message.contains("entity_missing"));
+    }
+
+    #[test]
+    fn unknown_relation_attribute() {
+        let mut diagram = parse_sql(POSTS).diagram;
+        let mut pairs = diagram.relations[0].pairs();
+        pairs[0].to = Some("author_id".to_string());
+        pairs[0].to_id = None;
+        diagram.relations[0].set_pairs(pairs);
+        let unknown: Vec<Diagnostic> = lint(&diagram, None).into_iter().filter(|d| d.rule == "unknown-relation-attribute").collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].entity_id.as_deref(), Some("entity_posts"));
+    }
+
+    #[test]
+    fn duplicate_table_and_column_names() {
+        let mut diagram = parse_sql(POSTS).diagram;
+        let mut copy = diagram.entities["entity_users"].clone();
+        copy.id = "entity_users_2".to_string();
+        copy.physical_name = "USERS".to_string();
+        diagram.entities.insert(copy.id.clone(), copy);
+        let posts = diagram.entities.get_mut("entity_posts").unwrap();
+        posts.attributes.push(Attribute::new("User_Id", "INT"));
+
+        let diagnostics = lint(&diagram, None);
+        assert_eq!(diagnostics.iter().filter(|d| d.rule == "duplicate-table-name").count(), 2);
+        let columns: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.rule == "duplicate-column-name").collect();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0].attribute.as_deref(), Some("user_id"));
+    }
+
+    #[test]
+    fn foreign_key_type_mismatch() {
+        assert!(has(
+            "CREATE TABLE users (id BIGINT PRIMARY KEY); CREATE TABLE posts (id INT PRIMARY KEY, user_id INT REFERENCES users (id));",
+            "fk-type-mismatch"
+        ));
+        assert!(has(
+            "CREATE TABLE users (id VARCHAR(20) PRIMARY KEY); CREATE TABLE posts (id INT PRIMARY KEY, user_id VARCHAR(10) REFERENCES users (id));",
+            "fk-type-mismatch"
+        ));
+        // 대소문자만 다른 타입은 같은 타입
+        assert!(!has(
+            "CREATE TABLE users (id bigint PRIMARY KEY); CREATE TABLE posts (id INT PRIMARY KEY, user_id BIGINT REFERENCES users (id));",
+            "fk-type-mismatch"
+        ));
+    }
+
+    // 복합 FK (a, b)는 인덱스 앞부분이 같은 컬럼 집합이면 순서와 관계없이 인정
+    #[test]
+    fn missing_fk_index_matches_index_prefixes() {
+        let child = |extra: &str| {
+            format!(
+                "CREATE TABLE p (a INT, b INT, PRIMARY KEY (a, b));
+                 CREATE TABLE c (id INT PRIMARY KEY, a INT, b INT, x INT, FOREIGN KEY (a, b) REFERENCES p (a, b){});",
+                extra
+            )
+        };
+        assert!(has(&child(""), "missing-fk-index"));
+        assert!(!has(&child(", INDEX (b, a, x)"), "missing-fk-index"));
+        assert!(!has(&child(", INDEX (a, b)"), "missing-fk-index"));
+        assert!(has(&child(", INDEX (a, x, b)"), "missing-fk-index"));
+        assert!(has(&child(", INDEX (a)"), "missing-fk-index"));
+        assert!(has(&child(", INDEX (x, a, b)"), "missing-fk-index"));
+
+        // PK 앞부분이나 UNIQUE 단일 컬럼도 인덱스로 인정
+        assert!(!has(
+            "CREATE TABLE p (a INT PRIMARY KEY);
+             CREATE TABLE c (a INT, n INT, PRIMARY KEY (a, n), FOREIGN KEY (a) REFERENCES p (a));",
+            "missing-fk-index"
+        ));
+        assert!(!has("CREATE TABLE p (a INT PRIMARY KEY); CREATE TABLE c (id INT PRIMARY KEY, a INT UNIQUE REFERENCES p (a));", "missing-fk-index"));
+    }
+
+    // 같은 이름이라도 스키마가 다르면 중복이 아님
+    #[test]
+    fn duplicate_table_name_within_a_schema() {
+        let mut diagram = parse_sql("CREATE TABLE sales.orders (id INT PRIMARY KEY); CREATE TABLE hr.orders (id INT PRIMARY KEY);").diagram;
+        assert!(!lint(&diagram, None).iter().any(|d| d.rule == "duplicate-table-name"));
+
+        diagram.entities.get_mut("entity_hr.orders").unwrap().schema = Some("sales".to_string());
+        let duplicates = lint(&diagram, None).into_iter().filter(|d| d.rule == "duplicate-table-name").count();
+        assert_eq!(duplicates, 2);
+        assert!(!has(
+            "CREATE TABLE \"sales.orders\" (id INT PRIMARY KEY); CREATE TABLE sales.orders (id INT PRIMARY KEY);",
+            "duplicate-table-name"
+        ));
+    }
+}
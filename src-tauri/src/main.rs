@@ -2,8 +2,27 @@
 
 mod erd;
 mod commands;
+mod sql;
+mod sql_import;
+mod introspect;
+mod diff;
+mod migration;
+mod lint;
+mod naming;
+mod dictionary;
+mod romanize;
+mod user_types;
+mod junction;
+mod relations;
+mod hierarchy;
+mod views;
+mod file_format;
+mod backup;
+mod recovery;
+#[cfg(test)]
+mod test_util;
 
-use commands::{save_diagram_to_file, save_diagram_to_path, load_diagram_from_file, export_markdown, export_mermaid, export_xlsx, import_xlsx};
+use commands::{save_diagram_to_file, save_diagram_to_path, load_diagram_from_file, export_markdown, export_mermaid, export_xlsx, import_xlsx, export_sql, import_sql, import_sqlite, import_postgres, diff_diagrams, diff_diagram_files, generate_migration, validate_diagram, list_lint_rules, fix_naming, get_project_naming_policy, save_project_naming_policy, import_term_dictionary, get_term_dictionary, save_term_dictionary, apply_term_dictionary, regenerate_physical_names, save_user_type, remove_user_type, resolve_many_to_many, restore_many_to_many, create_relation, delete_relation, update_entity, save_subtype_hierarchy, remove_subtype_hierarchy, preview_physical_model, refresh_view, save_diagram_to_directory, load_diagram_from_directory, list_backups, restore_backup, get_backup_settings, save_backup_settings, autosave_diagram, list_recovery_sessions, restore_recovery_session, discard_recovery_session};
 
 fn main() {
     tauri::Builder::default()
@@ -17,7 +36,45 @@ fn main() {
             export_markdown,
             export_mermaid,
             export_xlsx,
-            import_xlsx
+            import_xlsx,
+            export_sql,
+            import_sql,
+            import_sqlite,
+            import_postgres,
+            diff_diagrams,
+            diff_diagram_files,
+            generate_migration,
+            validate_diagram,
+            list_lint_rules,
+            fix_naming,
+            get_project_naming_policy,
+            save_project_naming_policy,
+            import_term_dictionary,
+            get_term_dictionary,
+            save_term_dictionary,
+            apply_term_dictionary,
+            regenerate_physical_names,
+            save_user_type,
+            remove_user_type,
+            resolve_many_to_many,
+            restore_many_to_many,
+            create_relation,
+            delete_relation,
+            update_entity,
+            save_subtype_hierarchy,
+            remove_subtype_hierarchy,
+            preview_physical_model,
+            refresh_view,
+            save_diagram_to_directory,
+            load_diagram_from_directory,
+            list_backups,
+            restore_backup,
+            get_backup_settings,
+            save_backup_settings,
+            autosave_diagram,
+            list_recovery_sessions,
+            restore_recovery_session,
+            discard_recovery_session
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,591 @@
+use crate::diff::{attribute_pairs, diff_diagrams, DiagramDiff};
+use crate::erd::{Attribute, Entity, EntityKind, ErdDiagram, Index, UserType};
+use crate::sql::{self, column_name, table_name, ConstraintKind, Dialect, ForeignKey, SqlDialect};
+use crate::views;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Migration {
+    pub forward: String,
+    pub rollback: String,
+    pub warnings: Vec<String>,
+}
+
+// old → new 적용 스크립트와 되돌리기 스크립트 (되돌리기는 new → old 차이로 생성)
+pub fn migration(old: &ErdDiagram, new: &ErdDiagram, dialect: SqlDialect) -> Migration {
+    let (forward, warnings) = migration_script(old, new, dialect.dialect());
+    let (rollback, _) = migration_script(new, old, dialect.dialect());
+    Migration { forward, rollback, warnings }
+}
+
+struct Script {
+    lines: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Script {
+    fn push(&mut self, statement: String) {
+        self.lines.push(statement);
+    }
+
+    fn warn(&mut self, warning: String) {
+        self.lines.push(format!("-- 경고: {}", warning));
+        self.warnings.push(warning);
+    }
+}
+
+pub fn migration_script(old: &ErdDiagram, new: &ErdDiagram, dialect: &dyn Dialect) -> (String, Vec<String>) {
+    // 도메인/열거형을 펼친 뒤 비교하면 타입 정의 변경이 참조하는 컬럼 변경으로 나타남
+    let old = &sql::resolve_user_types(old, dialect);
+    let new = &sql::resolve_user_types(new, dialect);
+    // 뷰는 테이블 비교에서 빼고 따로 삭제/생성
+    let (old_views, new_views) = (old, new);
+    let old = &tables_only(old_views);
+    let new = &tables_only(new_views);
+    let diff = diff_diagrams(old, new);
+    let mut script = Script { lines: Vec::new(), warnings: Vec::new() };
+
+    // 바뀌는 테이블을 참조하는 뷰는 테이블 변경 전에 삭제
+    let (dropped_views, created_views) = view_changes(old_views, new_views, &diff);
+    for view in &dropped_views {
+        script.push(dialect.drop_view(&sql::qualified_table(view), view.kind == EntityKind::MaterializedView));
+    }
+
+    if dialect.supports_user_types() {
+        alter_user_types(&mut script, old, new, dialect);
+    }
+
+    // 새 스키마는 테이블보다 먼저 생성
+    if dialect.supports_schemas() {
+        let old_schemas = sql::schemas(old);
+        for schema in sql::schemas(new).into_iter().filter(|s| !old_schemas.contains(s)) {
+            script.push(dialect.create_schema(schema));
+        }
+    }
+
+    let old_fks = sql::foreign_keys(old);
+    let new_fks = sql::foreign_keys(new);
+
+    if dialect.supports_alter_constraints() {
+        // 1. 바뀌거나 사라진 FK 먼저 삭제 (이전 테이블명 기준)
+        for fk in old_fks.iter().filter(|fk| !new_fks.iter().any(|n| same_foreign_key(fk, n))) {
+            script.push(dialect.drop_constraint(&fk.table, &fk.name, ConstraintKind::ForeignKey));
+        }
+
+        // 2. 기존 테이블 변경
+        for entity_diff in &diff.changed_entities {
+            let (Some(old_entity), Some(new_entity)) = (old.entities.get(&entity_diff.old_id), new.entities.get(&entity_diff.new_id)) else {
+                continue;
+            };
+            alter_entity(&mut script, old_entity, new_entity, dialect);
+        }
+
+        // 3. 새 테이블
+        for entity in &diff.added_entities {
+            script.push(sql::create_table(entity, &[], dialect));
+            create_indexes(&mut script, &sql::qualified_table(entity), &[], &entity.indexes, dialect);
+        }
+
+        // 4. 삭제된 테이블
+        for entity in &diff.removed_entities {
+            script.warn(format!("테이블 '{}' 삭제 시 데이터가 손실됩니다", table_name(entity)));
+            script.push(format!("DROP TABLE {};", dialect.quote_table(&sql::qualified_table(entity))));
+        }
+
+        // 5. 새로 생기거나 바뀐 FK 추가
+        for fk in new_fks.iter().filter(|fk| !old_fks.iter().any(|o| same_foreign_key(fk, o))) {
+            script.push(dialect.add_constraint(&fk.table, &sql::foreign_key_clause(fk, dialect)));
+        }
+    } else {
+        sqlite_script(&mut script, old, new, &diff, &old_fks, &new_fks, dialect);
+    }
+
+    for view in &created_views {
+        script.push(sql::create_view(view, dialect));
+        if view.kind == EntityKind::MaterializedView && dialect.supports_materialized_views() {
+            for statement in sql::create_indexes(view, dialect) {
+                script.push(statement);
+            }
+        }
+    }
+
+    // 사용하던 컬럼이 모두 바뀐 뒤 타입 삭제
+    if dialect.supports_user_types() {
+        for user_type in old.user_types.iter().filter(|o| new.user_type(o.name()).is_none()) {
+            script.push(sql::drop_type(user_type, dialect));
+        }
+    }
+
+    let mut text = format!("-- ERD Migration ({})\n\n", dialect.name());
+    if script.lines.is_empty() {
+        text.push_str("-- 변경 사항 없음\n");
+    } else {
+        text.push_str(&script.lines.join("\n"));
+        text.push('\n');
+    }
+    (text, script.warnings)
+}
+
+fn tables_only(diagram: &ErdDiagram) -> ErdDiagram {
+    let mut tables = diagram.clone();
+    tables.entities.retain(|_, e| !e.is_view());
+    tables
+}
+
+// (삭제할 이전 뷰, 만들 새 뷰). 정의가 바뀌었거나 바뀐/삭제된 테이블이나 다시 만드는 뷰를 참조하면 다시 만듦
+// 삭제는 참조하는 뷰부터, 생성은 참조되는 뷰부터
+fn view_changes<'a>(old: &'a ErdDiagram, new: &'a ErdDiagram, diff: &DiagramDiff) -> (Vec<&'a Entity>, Vec<&'a Entity>) {
+    let mut touched: Vec<&str> = diff.changed_entities.iter().map(|e| e.old_id.as_str()).collect();
+    touched.extend(diff.removed_entities.iter().map(|e| e.id.as_str()));
+
+    let new_views = views::ordered_views(new);
+    let matching = |view: &Entity| -> Option<&'a Entity> {
+        new_views
+            .iter()
+            .copied()
+            .find(|n| n.id == view.id)
+            .or_else(|| new_views.iter().copied().find(|n| n.qualified_name().eq_ignore_ascii_case(&view.qualified_name())))
+    };
+
+    let mut dropped: Vec<&'a Entity> = Vec::new();
+    for view in views::ordered_views(old) {
+        let unchanged = matching(view).is_some_and(|n| view_signature(n) == view_signature(view));
+        let depends_on_change = view
+            .view_dependencies
+            .iter()
+            .any(|id| touched.contains(&id.as_str()) || dropped.iter().any(|d| &d.id == id));
+        if !unchanged || depends_on_change {
+            dropped.push(view);
+        }
+    }
+
+    let created: Vec<&'a Entity> = new_views
+        .iter()
+        .copied()
+        .filter(|n| {
+            let previous = old
+                .entities
+                .values()
+                .filter(|e| e.is_view())
+                .find(|o| o.id == n.id || o.qualified_name().eq_ignore_ascii_case(&n.qualified_name()));
+            !matches!(previous, Some(o) if !dropped.iter().any(|d| d.id == o.id))
+        })
+        .collect();
+    dropped.reverse();
+    (dropped, created)
+}
+
+// 뷰를 다시 만들어야 하는지 비교할 값 (공백 차이는 무시)
+fn view_signature(view: &Entity) -> String {
+    let query: Vec<&str> = view.view_query().unwrap_or_default().split_whitespace().collect();
+    let columns: Vec<&str> = view.attributes.iter().map(column_name).collect();
+    let indexes: Vec<String> = view.indexes.iter().map(|i| format!("{}({})", i.name, i.column_list())).collect();
+    format!("{:?} {} {:?} {:?} {}", view.kind, view.qualified_name(), columns, indexes, query.join(" ")).to_lowercase()
+}
+
+// 새 타입 생성과 바뀐 타입 변경 (테이블 변경 전에 실행)
+fn alter_user_types(script: &mut Script, old: &ErdDiagram, new: &ErdDiagram, dialect: &dyn Dialect) {
+    for user_type in &new.user_types {
+        let name = dialect.quote_ident(user_type.name());
+        match (old.user_type(user_type.name()), user_type) {
+            (None, _) => script.push(sql::create_type(user_type, dialect)),
+            (Some(o), n) if o == n => {}
+            (Some(UserType::Enum(o)), UserType::Enum(n)) => {
+                for value in n.values.iter().filter(|v| !o.values.contains(v)) {
+                    script.push(format!("ALTER TYPE {} ADD VALUE {};", name, sql::quote_literals(std::slice::from_ref(value))));
+                }
+                if o.values.iter().any(|v| !n.values.contains(v)) {
+                    script.warn(format!("열거형 '{}'의 값 삭제는 자동으로 처리할 수 없습니다", n.name));
+                }
+            }
+            (Some(UserType::Domain(o)), UserType::Domain(n)) => {
+                if !o.base_type.eq_ignore_ascii_case(&n.base_type) || o.length != n.length {
+                    script.warn(format!("도메인 '{}'의 기반 타입 변경은 자동으로 처리할 수 없습니다", n.name));
+                }
+                if o.default_value != n.default_value {
+                    match n.default_value.as_deref().filter(|v| !v.trim().is_empty()) {
+                        Some(default) => script.push(format!("ALTER DOMAIN {} SET DEFAULT {};", name, default)),
+                        None => script.push(format!("ALTER DOMAIN {} DROP DEFAULT;", name)),
+                    }
+                }
+                if o.check != n.check {
+                    let check_name = dialect.quote_ident(&sql::domain_check_name(&n.name));
+                    if o.check.as_deref().is_some_and(|c| !c.trim().is_empty()) {
+                        script.push(format!("ALTER DOMAIN {} DROP CONSTRAINT {};", name, check_name));
+                    }
+                    if let Some(check) = n.check.as_deref().filter(|c| !c.trim().is_empty()) {
+                        script.push(format!("ALTER DOMAIN {} ADD CONSTRAINT {} CHECK ({});", name, check_name, check));
+                    }
+                }
+            }
+            (Some(_), n) => script.warn(format!("타입 '{}'의 종류(열거형/도메인) 변경은 자동으로 처리할 수 없습니다", n.name())),
+        }
+    }
+}
+
+fn alter_entity(script: &mut Script, old: &Entity, new: &Entity, dialect: &dyn Dialect) {
+    // 제약조건 이름은 스키마 없는 테이블명, 문의 대상은 "스키마.테이블"
+    let old_table = table_name(old);
+    let table = table_name(new);
+    let target = sql::qualified_table(new);
+    if old_table != table {
+        script.push(dialect.rename_table(&sql::qualified_table(old), table));
+    }
+    if old.schema_name() != new.schema_name() {
+        let renamed = sql::qualify(old.schema_name(), table);
+        match new.schema_name() {
+            Some(schema) => script.lines.extend(dialect.set_schema(&renamed, schema)),
+            None => script.warn(format!("테이블 '{}'을(를) 기본 스키마로 옮기는 문은 생성하지 않습니다", renamed)),
+        }
+    }
+
+    // 컬럼 변경 전에 바뀐 인덱스/테이블 제약조건 삭제
+    drop_indexes(script, &target, &old.indexes, &new.indexes, dialect);
+    let old_constraints = table_constraints(old, dialect);
+    let new_constraints = table_constraints(new, dialect);
+    for (name, _, kind) in old_constraints.iter().filter(|c| !new_constraints.contains(c)) {
+        script.push(dialect.drop_constraint(&target, name, *kind));
+    }
+
+    let pairs = attribute_pairs(old, new);
+    let pk_changed = primary_key_changed(&pairs);
+
+    if pk_changed && old.attributes.iter().any(|a| a.is_primary_key) {
+        script.push(dialect.drop_constraint(&target, &sql::primary_key_name(old_table), ConstraintKind::PrimaryKey));
+    }
+
+    for (old_attr, new_attr) in &pairs {
+        if let Some(o) = old_attr {
+            if is_unique_column(o) && !unique_kept(old_table, table, *old_attr, *new_attr) {
+                script.push(dialect.drop_constraint(&target, &sql::unique_name(old_table, column_name(o)), ConstraintKind::Unique));
+            }
+        }
+    }
+
+    for (old_attr, new_attr) in &pairs {
+        match (old_attr, new_attr) {
+            (Some(o), Some(n)) => {
+                if column_name(o) != column_name(n) {
+                    script.push(dialect.rename_column(&target, column_name(o), column_name(n)));
+                }
+                if column_changed(o, n) {
+                    match dialect.alter_column(&target, o, n) {
+                        Ok(statements) => statements.into_iter().for_each(|s| script.push(s)),
+                        Err(warning) => script.warn(warning),
+                    }
+                }
+            }
+            (None, Some(n)) => {
+                if !n.is_nullable && n.default_value.is_none() {
+                    script.warn(format!("{}.{}: 기본값 없는 NOT NULL 컬럼은 기존 행이 있으면 추가에 실패합니다", table, column_name(n)));
+                }
+                script.push(dialect.add_column(&target, &sql::column_definition(n, dialect, false)));
+            }
+            (Some(o), None) => {
+                script.warn(format!("컬럼 '{}.{}' 삭제 시 데이터가 손실됩니다", table, column_name(o)));
+                script.push(dialect.drop_column(&target, column_name(o)));
+            }
+            (None, None) => {}
+        }
+    }
+
+    if pk_changed {
+        if let Some(clause) = sql::primary_key_clause(new, dialect) {
+            script.push(dialect.add_constraint(&target, &clause));
+        }
+    }
+
+    let unique_clauses: HashMap<String, String> = sql::unique_clauses(new, dialect).into_iter().collect();
+    for (old_attr, new_attr) in &pairs {
+        if let Some(n) = new_attr {
+            if is_unique_column(n) && !unique_kept(old_table, table, *old_attr, *new_attr) {
+                if let Some(clause) = unique_clauses.get(&sql::unique_name(table, column_name(n))) {
+                    script.push(dialect.add_constraint(&target, clause));
+                }
+            }
+        }
+    }
+
+    for (_, clause, _) in new_constraints.iter().filter(|c| !old_constraints.contains(c)) {
+        script.push(dialect.add_constraint(&target, clause));
+    }
+
+    create_indexes(script, &target, &old.indexes, &new.indexes, dialect);
+}
+
+// 이름 있는 UNIQUE와 CHECK 제약조건 (정의가 바뀌면 삭제 후 다시 추가)
+fn table_constraints(entity: &Entity, dialect: &dyn Dialect) -> Vec<(String, String, ConstraintKind)> {
+    let uniques = sql::named_unique_clauses(entity, dialect).into_iter().map(|(n, c)| (n, c, ConstraintKind::Unique));
+    let checks = sql::check_clauses(entity, dialect).into_iter().map(|(n, c)| (n, c, ConstraintKind::Check));
+    uniques.chain(checks).collect()
+}
+
+// old에 없거나 정의가 바뀐 인덱스 생성
+fn create_indexes(script: &mut Script, table: &str, old: &[Index], new: &[Index], dialect: &dyn Dialect) {
+    for index in new.iter().filter(|n| !old.contains(n)) {
+        let (statement, warnings) = dialect.create_index(table, index);
+        warnings.into_iter().for_each(|w| script.warn(w));
+        script.push(statement);
+    }
+}
+
+// new에 없거나 정의가 바뀐 인덱스 삭제
+fn drop_indexes(script: &mut Script, table: &str, old: &[Index], new: &[Index], dialect: &dyn Dialect) {
+    for index in old.iter().filter(|o| !new.contains(o)) {
+        script.push(dialect.drop_index(table, &index.name));
+    }
+}
+
+// SQLite는 제약조건/컬럼 변경을 지원하지 않으므로 바뀐 테이블은 새로 만들어 데이터를 옮김
+fn sqlite_script(
+    script: &mut Script,
+    old: &ErdDiagram,
+    new: &ErdDiagram,
+    diff: &DiagramDiff,
+    old_fks: &[ForeignKey],
+    new_fks: &[ForeignKey],
+    dialect: &dyn Dialect,
+) {
+    // FK의 테이블 이름은 "스키마.테이블"이므로 엔티티에서 다시 구함
+    let renames: HashMap<String, String> = diff
+        .changed_entities
+        .iter()
+        .filter_map(|e| Some((old.entities.get(&e.old_id)?, new.entities.get(&e.new_id)?)))
+        .map(|(o, n)| (sql::qualified_table(o).to_lowercase(), sql::qualified_table(n)))
+        .collect();
+    // 테이블 이름 변경을 반영해 FK 정의 비교 (이름은 무시)
+    let fk_signature = |fk: &ForeignKey, renamed: bool| {
+        let referenced = if renamed {
+            renames.get(&fk.referenced_table.to_lowercase()).cloned().unwrap_or_else(|| fk.referenced_table.clone())
+        } else {
+            fk.referenced_table.clone()
+        };
+        format!("{:?}->{}{:?}", fk.columns, referenced.to_lowercase(), fk.referenced_columns)
+    };
+
+    let mut body = Script { lines: Vec::new(), warnings: Vec::new() };
+    let mut rebuilt = false;
+
+    for entity in &diff.added_entities {
+        let inline: Vec<&ForeignKey> = new_fks.iter().filter(|fk| fk.table == sql::qualified_table(entity)).collect();
+        body.push(sql::create_table(entity, &inline, dialect));
+        create_indexes(&mut body, &sql::qualified_table(entity), &[], &entity.indexes, dialect);
+    }
+
+    // 변경 없는 엔티티도 참조하는 FK만 바뀌었을 수 있으므로 모두 확인
+    let mut pairs_to_check: Vec<(&Entity, &Entity)> = diff
+        .changed_entities
+        .iter()
+        .filter_map(|e| Some((old.entities.get(&e.old_id)?, new.entities.get(&e.new_id)?)))
+        .collect();
+    let mut unchanged: Vec<&Entity> = new
+        .entities
+        .values()
+        .filter(|n| !diff.added_entities.iter().any(|a| a.id == n.id) && !diff.changed_entities.iter().any(|c| c.new_id == n.id))
+        .collect();
+    unchanged.sort_by_key(|e| sql::qualified_table(e).to_lowercase());
+    for new_entity in unchanged {
+        let old_entity = old.entities.get(&new_entity.id).or_else(|| {
+            old.entities.values().find(|o| o.qualified_name().eq_ignore_ascii_case(&new_entity.qualified_name()))
+        });
+        if let Some(old_entity) = old_entity {
+            pairs_to_check.push((old_entity, new_entity));
+        }
+    }
+
+    for (old_entity, new_entity) in pairs_to_check {
+        let old_table = table_name(old_entity);
+        let table = table_name(new_entity);
+        let old_target = sql::qualified_table(old_entity);
+        let target = sql::qualified_table(new_entity);
+        let pairs = attribute_pairs(old_entity, new_entity);
+
+        let mut old_signatures: Vec<String> = old_fks.iter().filter(|fk| fk.table == old_target).map(|fk| fk_signature(fk, true)).collect();
+        let mut new_signatures: Vec<String> = new_fks.iter().filter(|fk| fk.table == target).map(|fk| fk_signature(fk, false)).collect();
+        old_signatures.sort();
+        new_signatures.sort();
+
+        let needs_rebuild = old_signatures != new_signatures
+            || primary_key_changed(&pairs)
+            || table_constraints(old_entity, dialect) != table_constraints(new_entity, dialect)
+            || pairs.iter().any(|(o, n)| match (o, n) {
+                (Some(o), Some(n)) => column_changed(o, n) || is_unique_column(o) != is_unique_column(n),
+                (None, Some(n)) => is_unique_column(n) || n.is_primary_key,
+                _ => false,
+            });
+
+        if !needs_rebuild {
+            // 이름 변경, 컬럼 추가/삭제는 ALTER TABLE로 가능
+            if old_table != table {
+                body.push(dialect.rename_table(old_table, table));
+            }
+            drop_indexes(&mut body, table, &old_entity.indexes, &new_entity.indexes, dialect);
+            for (old_attr, new_attr) in &pairs {
+                match (old_attr, new_attr) {
+                    (Some(o), Some(n)) if column_name(o) != column_name(n) => {
+                        body.push(dialect.rename_column(table, column_name(o), column_name(n)));
+                    }
+                    (None, Some(n)) => {
+                        if !n.is_nullable && n.default_value.is_none() {
+                            body.warn(format!("{}.{}: SQLite는 기본값 없는 NOT NULL 컬럼을 추가할 수 없습니다", table, column_name(n)));
+                        }
+                        body.push(dialect.add_column(table, &sql::column_definition(n, dialect, false)));
+                    }
+                    (Some(o), None) => {
+                        body.warn(format!("컬럼 '{}.{}' 삭제 시 데이터가 손실됩니다", table, column_name(o)));
+                        body.push(dialect.drop_column(table, column_name(o)));
+                    }
+                    _ => {}
+                }
+            }
+            create_indexes(&mut body, table, &old_entity.indexes, &new_entity.indexes, dialect);
+            continue;
+        }
+
+        rebuilt = true;
+        // 제약조건 이름은 최종 테이블명 기준으로 유지하고 테이블명만 임시로 바꿈
+        let temp_table = format!("{}__new", table);
+        let inline: Vec<&ForeignKey> = new_fks.iter().filter(|fk| fk.table == target).collect();
+        let create = sql::create_table(new_entity, &inline, dialect).replacen(
+            &format!("CREATE TABLE {} (", dialect.quote_ident(table)),
+            &format!("CREATE TABLE {} (", dialect.quote_ident(&temp_table)),
+            1,
+        );
+
+        let copied: Vec<(String, String)> = pairs
+            .iter()
+            .filter_map(|(o, n)| Some((column_name((*o)?).to_string(), column_name((*n)?).to_string())))
+            .collect();
+        for (old_attr, new_attr) in &pairs {
+            match (old_attr, new_attr) {
+                (Some(o), None) => body.warn(format!("컬럼 '{}.{}' 삭제 시 데이터가 손실됩니다", old_table, column_name(o))),
+                (None, Some(n)) if !n.is_nullable && n.default_value.is_none() => {
+                    body.warn(format!("{}.{}: 기본값 없는 NOT NULL 컬럼은 기존 행이 있으면 복사에 실패합니다", table, column_name(n)))
+                }
+                _ => {}
+            }
+        }
+
+        body.push(format!("-- {} 테이블 재생성", table));
+        body.push(create);
+        if !copied.is_empty() {
+            let (source, destination): (Vec<String>, Vec<String>) = copied.into_iter().unzip();
+            body.push(format!(
+                "INSERT INTO {} ({}) SELECT {} FROM {};",
+                dialect.quote_ident(&temp_table),
+                sql::quote_list(&destination, dialect),
+                sql::quote_list(&source, dialect),
+                dialect.quote_ident(old_table)
+            ));
+        }
+        body.push(format!("DROP TABLE {};", dialect.quote_ident(old_table)));
+        body.push(dialect.rename_table(&temp_table, table));
+        // 기존 인덱스는 테이블과 함께 삭제되므로 모두 다시 생성
+        create_indexes(&mut body, table, &[], &new_entity.indexes, dialect);
+    }
+
+    for entity in &diff.removed_entities {
+        body.warn(format!("테이블 '{}' 삭제 시 데이터가 손실됩니다", table_name(entity)));
+        body.push(format!("DROP TABLE {};", dialect.quote_ident(table_name(entity))));
+    }
+
+    // 테이블 재생성 중에는 FK 검사를 끔
+    if rebuilt {
+        script.push("PRAGMA foreign_keys = OFF;".to_string());
+    }
+    script.lines.extend(body.lines);
+    script.warnings.extend(body.warnings);
+    if rebuilt {
+        script.push("PRAGMA foreign_keys = ON;".to_string());
+    }
+}
+
+fn same_foreign_key(a: &ForeignKey, b: &ForeignKey) -> bool {
+    a.name == b.name
+        && a.table == b.table
+        && a.columns == b.columns
+        && a.referenced_table == b.referenced_table
+        && a.referenced_columns == b.referenced_columns
+}
+
+// 타입/길이/NULL/기본값/자동 증가 중 하나라도 바뀌면 컬럼 변경
+fn column_changed(old: &Attribute, new: &Attribute) -> bool {
+    old.data_type != new.data_type
+        || old.length != new.length
+        || old.is_nullable != new.is_nullable
+        || old.default_value != new.default_value
+        || old.is_auto_increment != new.is_auto_increment
+}
+
+fn primary_key_changed(pairs: &[(Option<&Attribute>, Option<&Attribute>)]) -> bool {
+    pairs.iter().any(|(o, n)| {
+        let old_pk = o.is_some_and(|a| a.is_primary_key);
+        let new_pk = n.is_some_and(|a| a.is_primary_key);
+        old_pk != new_pk
+    })
+}
+
+fn is_unique_column(attr: &Attribute) -> bool {
+    attr.is_unique && !attr.is_primary_key
+}
+
+// 테이블/컬럼 이름이 그대로인 UNIQUE는 제약조건 이름도 같으므로 유지
+fn unique_kept(old_table: &str, table: &str, old: Option<&Attribute>, new: Option<&Attribute>) -> bool {
+    match (old, new) {
+        (Some(o), Some(n)) => {
+            is_unique_column(o) && is_unique_column(n) && old_table == table && column_name(o) == column_name(n)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    fn renamed() -> (ErdDiagram, ErdDiagram) {
+        let old = parse_sql("CREATE TABLE users (id INT PRIMARY KEY, mail VARCHAR(100));").diagram;
+        let mut new = old.clone();
+        let entity = new.entities.get_mut("entity_users").unwrap();
+        entity.physical_name = "members".to_string();
+        entity.attributes[1].physical_name = "email".to_string();
+        (old, new)
+    }
+
+    // 이름 변경은 DROP/ADD가 아니라 RENAME으로 (데이터 유지)
+    #[test]
+    fn renames_are_not_drop_and_add() {
+        let (old, new) = renamed();
+        for dialect in [SqlDialect::PostgreSql, SqlDialect::MySql, SqlDialect::Sqlite, SqlDialect::SqlServer] {
+            let result = migration(&old, &new, dialect);
+            assert!(result.warnings.is_empty(), "{:?}: {:?}", dialect, result.warnings);
+            for script in [&result.forward, &result.rollback] {
+                assert!(!script.contains("DROP") && !script.contains("ADD"), "{:?}: {}", dialect, script);
+            }
+        }
+    }
+
+    #[test]
+    fn rename_statements_per_dialect() {
+        let (old, new) = renamed();
+        let postgres = migration(&old, &new, SqlDialect::PostgreSql);
+        assert!(postgres.forward.contains("ALTER TABLE \"users\" RENAME TO \"members\";"));
+        assert!(postgres.forward.contains("ALTER TABLE \"members\" RENAME COLUMN \"mail\" TO \"email\";"));
+        assert!(postgres.rollback.contains("ALTER TABLE \"users\" RENAME COLUMN \"email\" TO \"mail\";"));
+
+        let mysql = migration(&old, &new, SqlDialect::MySql);
+        assert!(mysql.forward.contains("RENAME TABLE `users` TO `members`;"));
+
+        let sql_server = migration(&old, &new, SqlDialect::SqlServer);
+        assert!(sql_server.forward.contains("EXEC sp_rename 'members.mail', 'email', 'COLUMN';"));
+    }
+
+    #[test]
+    fn unchanged_diagram_has_no_statements() {
+        let (old, _) = renamed();
+        let result = migration(&old, &old, SqlDialect::PostgreSql);
+        assert!(!result.forward.contains("ALTER") && !result.forward.contains("CREATE"));
+    }
+}
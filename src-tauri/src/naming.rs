@@ -0,0 +1,391 @@
+use crate::erd::{Entity, ErdDiagram};
+use crate::lint::Diagnostic;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum NameCase {
+    #[default]
+    Any,
+    SnakeCase,      // tb_member
+    UpperSnakeCase, // TB_MEMBER
+}
+
+// 물리명 명명 규칙 (다이어그램에 저장하거나 프로젝트 설정으로 사용)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NamingPolicy {
+    #[serde(default)]
+    pub case: NameCase,
+    #[serde(default)]
+    pub table_prefix: String, // 예: "tb_"
+    #[serde(default)]
+    pub foreign_key_suffix: String, // 예: "_id"
+    #[serde(default)]
+    pub max_length: Option<usize>, // 예: 30 (Oracle)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameChange {
+    pub entity_id: String,
+    pub attribute: Option<String>, // 컬럼이면 변경 전 물리명, 테이블이면 None
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamingFixResult {
+    pub diagram: ErdDiagram,
+    pub changes: Vec<NameChange>,
+}
+
+pub fn check(diagram: &ErdDiagram, policy: &NamingPolicy, diagnostics: &mut Vec<Diagnostic>) {
+    let foreign_keys = foreign_key_columns(diagram);
+
+    let mut entities: Vec<&Entity> = diagram.entities.values().collect();
+    entities.sort_by_key(|e| e.physical_name.to_lowercase());
+
+    for entity in entities {
+        let table = &entity.physical_name;
+        check_common(policy, table, diagnostics, |d| d.entity(entity));
+        if !policy.table_prefix.is_empty() && !has_prefix(table, &policy.table_prefix) {
+            diagnostics.push(
+                Diagnostic::new("naming-table-prefix", format!("테이블 '{}'이(가) '{}'(으)로 시작하지 않습니다", table, policy.table_prefix))
+                    .entity(entity),
+            );
+        }
+
+        for attr in &entity.attributes {
+            let column = &attr.physical_name;
+            check_common(policy, column, diagnostics, |d| d.entity(entity).attribute(attr));
+            let is_fk = foreign_keys.contains(&(entity.id.as_str(), column.to_lowercase()));
+            if is_fk && !policy.foreign_key_suffix.is_empty() && !has_suffix(column, &policy.foreign_key_suffix) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "naming-fk-suffix",
+                        format!("FK 컬럼 '{}.{}'이(가) '{}'(으)로 끝나지 않습니다", table, column, policy.foreign_key_suffix),
+                    )
+                    .entity(entity)
+                    .attribute(attr),
+                );
+            }
+        }
+    }
+}
+
+fn check_common(policy: &NamingPolicy, name: &str, diagnostics: &mut Vec<Diagnostic>, at: impl Fn(Diagnostic) -> Diagnostic) {
+    if !matches_case(name, policy.case) {
+        let expected = match policy.case {
+            NameCase::UpperSnakeCase => "UPPER_SNAKE_CASE",
+            _ => "snake_case",
+        };
+        diagnostics.push(at(Diagnostic::new("naming-case", format!("'{}'이(가) {} 형식이 아닙니다", name, expected))));
+    }
+    if let Some(max) = policy.max_length {
+        let length = name.chars().count();
+        if length > max {
+            diagnostics.push(at(Diagnostic::new("naming-max-length", format!("'{}'의 길이({})가 최대 {}자를 넘습니다", name, length, max))));
+        }
+    }
+}
+
+// 규칙에 맞게 물리명을 고치고 관계/FK 참조도 함께 갱신
+pub fn fix(diagram: &ErdDiagram, policy: &NamingPolicy) -> NamingFixResult {
+    let mut diagram = diagram.clone();
+    let mut changes = Vec::new();
+    let foreign_keys: HashSet<(String, String)> = foreign_key_columns(&diagram)
+        .into_iter()
+        .map(|(id, column)| (id.to_string(), column))
+        .collect();
+
+    let mut ids: Vec<String> = diagram.entities.keys().cloned().collect();
+    ids.sort_by_key(|id| diagram.entities[id].physical_name.to_lowercase());
+
+    // 테이블명
+    let mut used_tables: HashSet<String> = HashSet::new();
+    for id in &ids {
+        let entity = &diagram.entities[id];
+        let fixed = fix_name(&entity.physical_name, &policy.table_prefix, "", policy);
+        let fixed = unique_name(fixed, &mut used_tables, policy);
+        if fixed != entity.physical_name {
+            changes.push(NameChange {
+                entity_id: id.clone(),
+                attribute: None,
+                old_name: entity.physical_name.clone(),
+                new_name: fixed,
+            });
+        }
+    }
+
+    // 컬럼명
+    for id in &ids {
+        let entity = &diagram.entities[id];
+        let mut used_columns = HashSet::new();
+        for attr in &entity.attributes {
+            let is_fk = foreign_keys.contains(&(id.clone(), attr.physical_name.to_lowercase()));
+            let suffix = if is_fk { policy.foreign_key_suffix.as_str() } else { "" };
+            let fixed = unique_name(fix_name(&attr.physical_name, "", suffix, policy), &mut used_columns, policy);
+            if fixed != attr.physical_name {
+                changes.push(NameChange {
+                    entity_id: id.clone(),
+                    attribute: Some(attr.physical_name.clone()),
+                    old_name: attr.physical_name.clone(),
+                    new_name: fixed,
+                });
+            }
+        }
+    }
+
+    for change in &changes {
+        let entity = diagram.entities.get_mut(&change.entity_id).expect("entity exists");
+        match &change.attribute {
+            None => entity.physical_name = change.new_name.clone(),
+            Some(old) => {
+                if let Some(attr) = entity.attributes.iter_mut().find(|a| &a.physical_name == old) {
+                    attr.physical_name = change.new_name.clone();
+                }
+            }
+        }
+    }
+    update_references(&mut diagram, &changes);
+
+    NamingFixResult { diagram, changes }
+}
+
+// 이미 적용된 물리명 변경을 관계의 from/to 속성과 "table.column" FK 참조에 반영
+pub fn update_references(diagram: &mut ErdDiagram, changes: &[NameChange]) {
+    let mut table_renames: HashMap<String, String> = HashMap::new();
+    // 엔티티 id -> 변경 전 소문자 컬럼명 -> 새 이름
+    let mut column_renames: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    for change in changes.iter().filter(|c| !c.old_name.is_empty()) {
+        match change.attribute {
+            None => {
+                table_renames.insert(change.old_name.to_lowercase(), change.new_name.clone());
+            }
+            Some(_) => {
+                column_renames
+                    .entry(change.entity_id.as_str())
+                    .or_default()
+                    .insert(change.old_name.to_lowercase(), change.new_name.clone());
+            }
+        }
+    }
+
+    let rename_column = |entity_id: &str, name: &str| -> Option<String> {
+        column_renames.get(entity_id)?.get(&name.to_lowercase()).cloned()
+    };
+
+    for relation in diagram.relations.iter_mut() {
+        let mut pairs = relation.pairs();
+        for pair in pairs.iter_mut() {
+            if let Some(new) = rename_column(&relation.from_entity_id, &pair.from) {
+                pair.from = new;
+            }
+            if let Some(to) = pair.to.as_mut() {
+                if let Some(new) = rename_column(&relation.to_entity_id, to) {
+                    *to = new;
+                }
+            }
+        }
+        relation.set_pairs(pairs);
+    }
+
+    let entity_ids: HashMap<String, String> = diagram
+        .entities
+        .values()
+        .map(|e| (e.physical_name.to_lowercase(), e.id.clone()))
+        .collect();
+    for entity in diagram.entities.values_mut() {
+        for attr in entity.attributes.iter_mut() {
+            let Some((table, column)) = attr.foreign_key_reference.as_deref().and_then(|r| r.split_once('.')) else { continue };
+            let new_table = table_renames.get(&table.to_lowercase()).cloned().unwrap_or_else(|| table.to_string());
+            let new_column = entity_ids
+                .get(&new_table.to_lowercase())
+                .and_then(|id| rename_column(id, column))
+                .unwrap_or_else(|| column.to_string());
+            attr.foreign_key_reference = Some(format!("{}.{}", new_table, new_column));
+        }
+
+        // 인덱스와 UNIQUE 제약조건의 컬럼 목록 (CHECK 식은 건드리지 않음)
+        let id = entity.id.clone();
+        let index_columns = entity.indexes.iter_mut().flat_map(|i| i.columns.iter_mut().map(|c| &mut c.name));
+        let unique_columns = entity.unique_constraints.iter_mut().flat_map(|u| u.columns.iter_mut());
+        for column in index_columns.chain(unique_columns) {
+            if let Some(new) = rename_column(&id, column) {
+                *column = new;
+            }
+        }
+    }
+}
+
+// 관계의 to 속성이거나 FK로 표시된 컬럼 (엔티티 id, 소문자 컬럼명)
+fn foreign_key_columns(diagram: &ErdDiagram) -> HashSet<(&str, String)> {
+    let mut columns: HashSet<(&str, String)> = diagram
+        .relations
+        .iter()
+        .flat_map(|r| r.pairs().into_iter().filter_map(|p| Some((r.to_entity_id.as_str(), p.to?.to_lowercase()))))
+        .collect();
+    for entity in diagram.entities.values() {
+        for attr in entity.attributes.iter().filter(|a| a.is_foreign_key) {
+            columns.insert((entity.id.as_str(), attr.physical_name.to_lowercase()));
+        }
+    }
+    columns
+}
+
+fn matches_case(name: &str, case: NameCase) -> bool {
+    let valid = |c: char| match case {
+        NameCase::Any => true,
+        NameCase::SnakeCase => c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_',
+        NameCase::UpperSnakeCase => c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_',
+    };
+    name.chars().all(valid) && (case == NameCase::Any || !(name.starts_with('_') || name.ends_with('_') || name.contains("__")))
+}
+
+fn has_prefix(name: &str, prefix: &str) -> bool {
+    name.to_lowercase().starts_with(&prefix.to_lowercase())
+}
+
+fn has_suffix(name: &str, suffix: &str) -> bool {
+    name.to_lowercase().ends_with(&suffix.to_lowercase())
+}
+
+fn fix_name(name: &str, prefix: &str, suffix: &str, policy: &NamingPolicy) -> String {
+    let mut body = match policy.case {
+        NameCase::Any => name.to_string(),
+        NameCase::SnakeCase => to_snake_case(name),
+        NameCase::UpperSnakeCase => to_snake_case(name).to_uppercase(),
+    };
+    let prefix = apply_case(prefix, policy.case);
+    let suffix = apply_case(suffix, policy.case);
+
+    if let Some(rest) = body.get(prefix.len()..).filter(|_| has_prefix(&body, &prefix)) {
+        body = rest.to_string();
+    }
+    if let Some(rest) = body.get(..body.len().saturating_sub(suffix.len())).filter(|_| has_suffix(&body, &suffix)) {
+        body = rest.to_string();
+    }
+    // 접두사/접미사를 유지한 채 본문만 잘라 최대 길이에 맞춤
+    if let Some(max) = policy.max_length {
+        let room = max.saturating_sub(prefix.chars().count() + suffix.chars().count());
+        body = body.chars().take(room).collect::<String>();
+        body = body.trim_end_matches('_').to_string();
+    }
+    format!("{}{}{}", prefix, body, suffix)
+}
+
+fn apply_case(affix: &str, case: NameCase) -> String {
+    match case {
+        NameCase::UpperSnakeCase => affix.to_uppercase(),
+        NameCase::SnakeCase => affix.to_lowercase(),
+        NameCase::Any => affix.to_string(),
+    }
+}
+
+// "MemberID", "member-name" -> "member_id", "member_name"
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev_lower = i > 0 && (chars[i - 1].is_ascii_lowercase() || chars[i - 1].is_ascii_digit());
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            let prev_upper = i > 0 && chars[i - 1].is_ascii_uppercase();
+            if prev_lower || (prev_upper && next_lower) {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else {
+            out.push('_');
+        }
+    }
+    out.split('_').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+// 고친 이름이 겹치면 숫자를 붙여 구분 (최대 길이 안에서)
+fn unique_name(name: String, used: &mut HashSet<String>, policy: &NamingPolicy) -> String {
+    if used.insert(name.to_lowercase()) {
+        return name;
+    }
+    for n in 2.. {
+        let tag = format!("_{}", n);
+        let keep = policy
+            .max_length
+            .map(|max| max.saturating_sub(tag.len()))
+            .unwrap_or(usize::MAX);
+        let candidate = format!("{}{}", name.chars().take(keep).collect::<String>(), tag);
+        if used.insert(candidate.to_lowercase()) {
+            return candidate;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    fn policy() -> NamingPolicy {
+        NamingPolicy {
+            case: NameCase::SnakeCase,
+            table_prefix: "tb_".to_string(),
+            foreign_key_suffix: "_id".to_string(),
+            max_length: Some(30),
+        }
+    }
+
+    fn shop() -> ErdDiagram {
+        parse_sql(
+            "CREATE TABLE Member (ID INT PRIMARY KEY, UserName VARCHAR(10));
+             CREATE TABLE Orders (id INT PRIMARY KEY, Member INT REFERENCES Member (ID));",
+        )
+        .diagram
+    }
+
+    fn column<'a>(diagram: &'a ErdDiagram, entity_id: &str, index: usize) -> &'a crate::erd::Attribute {
+        &diagram.entities[entity_id].attributes[index]
+    }
+
+    #[test]
+    fn fix_applies_case_prefix_and_fk_suffix() {
+        let result = fix(&shop(), &policy());
+        let diagram = &result.diagram;
+        assert_eq!(diagram.entities["entity_Member"].physical_name, "tb_member");
+        assert_eq!(diagram.entities["entity_Orders"].physical_name, "tb_orders");
+        assert_eq!(column(diagram, "entity_Member", 1).physical_name, "user_name");
+        assert_eq!(column(diagram, "entity_Orders", 1).physical_name, "member_id");
+        assert_eq!(result.changes.len(), 5);
+    }
+
+    #[test]
+    fn fix_updates_relation_columns() {
+        let diagram = fix(&shop(), &policy()).diagram;
+        let pair = &diagram.relations[0].columns[0];
+        assert_eq!((pair.from.as_str(), pair.to.as_deref()), ("id", Some("member_id")));
+    }
+
+    #[test]
+    fn fix_is_idempotent() {
+        let fixed = fix(&shop(), &policy()).diagram;
+        assert!(fix(&fixed, &policy()).changes.is_empty());
+
+        let mut diagnostics = Vec::new();
+        check(&fixed, &policy(), &mut diagnostics);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>());
+    }
+
+    // 길이를 줄여 겹치면 번호를 붙여 구분
+    #[test]
+    fn truncated_names_stay_unique() {
+        let diagram = parse_sql("CREATE TABLE very_long_name_a (x INT); CREATE TABLE very_long_name_b (x INT);").diagram;
+        let policy = NamingPolicy { case: NameCase::UpperSnakeCase, max_length: Some(8), ..Default::default() };
+        let fixed = fix(&diagram, &policy).diagram;
+        let a = &fixed.entities["entity_very_long_name_a"].physical_name;
+        let b = &fixed.entities["entity_very_long_name_b"].physical_name;
+        assert_ne!(a, b);
+        assert!(a.chars().count() <= 8 && b.chars().count() <= 8);
+        assert_eq!(column(&fixed, "entity_very_long_name_a", 0).physical_name, "X");
+    }
+}
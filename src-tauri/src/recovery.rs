@@ -0,0 +1,202 @@
+use crate::backup;
+use crate::erd::ErdDiagram;
+use crate::file_format::{self, ParsedDiagram, FORMAT_VERSION};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 아직 파일로 저장하지 않은 다이어그램을 담는 복구 슬롯 (열린 파일 경로마다 하나)
+#[derive(Serialize, Deserialize)]
+struct RecoverySlot {
+    file_path: Option<String>, // None이면 아직 저장한 적 없는 다이어그램
+    saved_at: u64, // UNIX 밀리초
+    diagram: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverySession {
+    pub key: String,
+    pub file_path: Option<String>,
+    pub saved_at: u64, // UNIX 밀리초
+    pub file_modified: Option<u64>, // 디스크 파일 수정 시각 (파일이 없으면 None)
+    pub entity_count: usize,
+}
+
+// 자동 저장. 슬롯 키를 돌려줌
+pub fn write(dir: &Path, file_path: Option<&str>, diagram: &ErdDiagram) -> Result<String, String> {
+    let mut diagram = diagram.clone();
+    diagram.format_version = FORMAT_VERSION;
+    let slot = RecoverySlot {
+        file_path: file_path.map(str::to_string),
+        saved_at: now_millis(),
+        diagram: serde_json::to_value(&diagram).map_err(|e| format!("직렬화 실패: {}", e))?,
+    };
+    let json = serde_json::to_string(&slot).map_err(|e| format!("직렬화 실패: {}", e))?;
+
+    fs::create_dir_all(dir).map_err(|e| format!("폴더 생성 실패 '{}': {}", dir.display(), e))?;
+    let key = key(file_path);
+    backup::write_atomic(&slot_path(dir, &key)?, &json)?;
+    Ok(key)
+}
+
+// 디스크의 파일보다 새로운 복구 슬롯 (최근 것부터). 파일이 더 새로우면 이미 저장된 것이므로 슬롯을 지움
+pub fn sessions(dir: &Path) -> Result<Vec<RecoverySession>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", dir.display(), e))? {
+        let entry = entry.map_err(|e| format!("폴더를 읽을 수 없습니다 '{}': {}", dir.display(), e))?;
+        let path = entry.path();
+        let Some(key) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".json"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let slot = match read_slot(&path) {
+            Ok(slot) => slot,
+            Err(e) => {
+                println!("복구 슬롯 건너뜀: {}", e);
+                continue;
+            }
+        };
+
+        let file_modified = slot.file_path.as_deref().and_then(|p| modified_millis(Path::new(p)));
+        if file_modified.is_some_and(|modified| modified >= slot.saved_at) {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+
+        let entity_count = slot.diagram.get("entities").and_then(Value::as_object).map_or(0, |e| e.len());
+        sessions.push(RecoverySession {
+            key,
+            file_path: slot.file_path,
+            saved_at: slot.saved_at,
+            file_modified,
+            entity_count,
+        });
+    }
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.saved_at));
+    Ok(sessions)
+}
+
+// 슬롯의 다이어그램을 현재 형식으로 읽음. 슬롯은 파일로 저장하거나 버릴 때까지 남김
+pub fn restore(dir: &Path, key: &str) -> Result<(Option<String>, ParsedDiagram), String> {
+    let slot = read_slot(&slot_path(dir, key)?)?;
+    Ok((slot.file_path, file_format::parse_value(slot.diagram)?))
+}
+
+pub fn discard(dir: &Path, key: &str) -> Result<(), String> {
+    let path = slot_path(dir, key)?;
+    if !path.exists() {
+        return Ok(());
+    }
+    fs::remove_file(&path).map_err(|e| format!("복구 슬롯 삭제 실패 '{}': {}", path.display(), e))
+}
+
+// 파일 이름 + 전체 경로 해시 (같은 이름의 다른 폴더 파일과 구분)
+pub fn key(file_path: Option<&str>) -> String {
+    let Some(file_path) = file_path else {
+        return "untitled".to_string();
+    };
+    let name: String = Path::new(file_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}-{:016x}", name, fnv1a(file_path))
+}
+
+fn read_slot(path: &Path) -> Result<RecoverySlot, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("파일을 읽을 수 없습니다 '{}': {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("복구 슬롯을 파싱할 수 없습니다 '{}': {}", path.display(), e))
+}
+
+// 키는 프론트엔드에서 넘어오므로 복구 폴더 밖을 가리키지 못하게 함
+fn slot_path(dir: &Path, key: &str) -> Result<PathBuf, String> {
+    if key.is_empty() || key.contains(['/', '\\']) || key.contains("..") {
+        return Err(format!("잘못된 복구 슬롯 키입니다: '{}'", key));
+    }
+    Ok(dir.join(format!("{}.json", key)))
+}
+
+fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
+
+// 실행마다 같은 값이 나와야 하므로 DefaultHasher 대신 FNV-1a
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    // 파일 경로로 자동 저장한 슬롯은 저장 후 같은 경로의 키로 지워져야 함 (untitled와 섞이지 않음)
+    #[test]
+    fn slots_are_keyed_by_file_path() {
+        let dir = temp_dir("recovery_keys");
+        let path = "/tmp/erd/a.json";
+        let key_a = write(&dir, Some(path), &ErdDiagram::default()).unwrap();
+        write(&dir, None, &ErdDiagram::default()).unwrap();
+        assert_eq!(key_a, key(Some(path)));
+        assert_ne!(key(Some(path)), key(Some("/tmp/other/a.json")));
+
+        discard(&dir, &key(Some(path))).unwrap();
+        let keys: Vec<String> = sessions(&dir).unwrap().into_iter().map(|s| s.key).collect();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(keys, ["untitled"]);
+    }
+
+    #[test]
+    fn slot_older_than_the_file_is_dropped() {
+        let dir = temp_dir("recovery_stale");
+        let file = dir.join("saved.json");
+        let file_path = file.to_string_lossy().to_string();
+        write(&dir, Some(&file_path), &ErdDiagram::default()).unwrap();
+        assert_eq!(sessions(&dir).unwrap().len(), 1);
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(&file, "{}").unwrap();
+        let remaining = sessions(&dir).unwrap();
+        let slot_exists = slot_path(&dir, &key(Some(&file_path))).unwrap().exists();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(remaining.is_empty());
+        assert!(!slot_exists);
+    }
+
+    #[test]
+    fn restore_returns_the_diagram_and_path() {
+        let dir = temp_dir("recovery_restore");
+        let mut diagram = ErdDiagram::default();
+        diagram.entities.insert("e".to_string(), crate::erd::Entity::new("e", "e"));
+        let key = write(&dir, Some("/missing/file.json"), &diagram).unwrap();
+        let (path, parsed) = restore(&dir, &key).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(path.as_deref(), Some("/missing/file.json"));
+        assert!(parsed.diagram.entities.contains_key("e"));
+    }
+
+    #[test]
+    fn keys_cannot_escape_the_recovery_folder() {
+        let dir = temp_dir("recovery_escape");
+        assert!(restore(&dir, "../secret").is_err());
+        assert!(discard(&dir, "a/b").is_err());
+        assert!(discard(&dir, "").is_err());
+    }
+}
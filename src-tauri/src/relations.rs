@@ -0,0 +1,403 @@
+use crate::diff::attribute_pairs;
+use crate::erd::{Attribute, Cardinality, ColumnPair, Entity, ErdDiagram, Relation};
+
+// 관계를 추가하고 FK 컬럼이 비어 있는 쌍에는 자식 엔티티에 FK 속성을 만들어 채움
+// (컬럼 쌍이 없으면 부모 PK 전체를 참조)
+pub fn create(diagram: &ErdDiagram, relation: Relation) -> Result<ErdDiagram, String> {
+    if diagram.relations.iter().any(|r| r.id == relation.id) {
+        return Err(format!("관계 '{}'이(가) 이미 있습니다.", relation.id));
+    }
+    let parent = diagram
+        .entities
+        .get(&relation.from_entity_id)
+        .ok_or_else(|| format!("엔티티 '{}'이(가) 없습니다.", relation.from_entity_id))?
+        .clone();
+    if !diagram.entities.contains_key(&relation.to_entity_id) {
+        return Err(format!("엔티티 '{}'이(가) 없습니다.", relation.to_entity_id));
+    }
+
+    let mut diagram = diagram.clone();
+    let mut relation = relation;
+    // N:M은 FK로 표현하지 않음 (연결 엔티티로 풀 때 생성)
+    if matches!(relation.cardinality, Cardinality::ManyToMany) {
+        diagram.relations.push(relation);
+        return Ok(diagram);
+    }
+
+    let mut pairs = relation.pairs();
+    if pairs.is_empty() {
+        pairs = primary_key_names(&parent).into_iter().map(|from| ColumnPair::new(from, None)).collect();
+        if pairs.is_empty() {
+            return Err(format!("'{}'에 기본 키가 없어 FK를 만들 수 없습니다.", parent.physical_name));
+        }
+    }
+
+    let child_id = relation.to_entity_id.clone();
+    let old_child = diagram.entities[&child_id].clone();
+    let (from_end, _) = relation.multiplicities();
+    for pair in pairs.iter_mut() {
+        let pk = find(&parent.attributes, &pair.from)
+            .ok_or_else(|| format!("'{}'에 속성 '{}'이(가) 없습니다.", parent.physical_name, pair.from))?
+            .clone();
+        let child = diagram.entities.get_mut(&child_id).unwrap();
+        let column = match pair.to.as_deref().filter(|c| !c.trim().is_empty()) {
+            Some(column) => column.to_string(),
+            None => {
+                let mut attr = foreign_key_attribute(&parent, &pk, &foreign_key_name(child, &parent, &pk));
+                attr.is_primary_key = relation.is_identifying;
+                attr.is_nullable = from_end.is_optional() && !relation.is_identifying;
+                let name = attr.physical_name.clone();
+                child.attributes.push(attr);
+                pair.generated = true;
+                name
+            }
+        };
+        let attr = child
+            .attributes
+            .iter_mut()
+            .find(|a| a.physical_name.eq_ignore_ascii_case(&column))
+            .ok_or_else(|| format!("'{}'에 속성 '{}'이(가) 없습니다.", old_child.physical_name, column))?;
+        attr.is_foreign_key = true;
+        attr.foreign_key_reference = Some(format!("{}.{}", parent.physical_name, pk.physical_name));
+        pair.to = Some(column);
+    }
+    relation.set_pairs(pairs);
+    diagram.relations.push(relation);
+
+    // 식별 관계로 자식 PK가 바뀌었으면 손자 엔티티의 FK도 맞춤
+    sync_children(&mut diagram, &old_child, &child_id, &mut Vec::new());
+    diagram.link_attributes();
+    Ok(diagram)
+}
+
+// 관계를 삭제하고, 관계를 만들 때 생성한 FK 속성 중 다른 관계가 쓰지 않는 것은 자식 엔티티에서 제거
+// (사용자가 지정한 기존 컬럼은 남기고 FK 표시만 해제)
+pub fn delete(diagram: &ErdDiagram, relation_id: &str) -> Result<ErdDiagram, String> {
+    let position = diagram
+        .relations
+        .iter()
+        .position(|r| r.id == relation_id)
+        .ok_or_else(|| format!("관계 '{}'이(가) 없습니다.", relation_id))?;
+    let mut diagram = diagram.clone();
+    let relation = diagram.relations.remove(position);
+    let Some(old_child) = diagram.entities.get(&relation.to_entity_id).cloned() else {
+        return Ok(diagram);
+    };
+
+    if release_columns(&mut diagram, &relation.to_entity_id, relation.pairs()) {
+        sync_children(&mut diagram, &old_child, &relation.to_entity_id, &mut Vec::new());
+    }
+    diagram.link_attributes();
+    Ok(diagram)
+}
+
+// 엔티티를 수정하고 그 변경(PK 추가/삭제, 타입, 이름)을 관계와 자식 FK 속성에 반영
+pub fn update_entity(diagram: &ErdDiagram, entity: Entity) -> Result<ErdDiagram, String> {
+    let old = diagram
+        .entities
+        .get(&entity.id)
+        .ok_or_else(|| format!("엔티티 '{}'이(가) 없습니다.", entity.id))?
+        .clone();
+    let entity_id = entity.id.clone();
+    let mut diagram = diagram.clone();
+
+    // 자식 쪽 FK 컬럼 이름 변경은 관계의 컬럼 쌍에 반영
+    let renames = renamed_columns(&old, &entity);
+    for relation in diagram.relations.iter_mut().filter(|r| r.to_entity_id == entity_id) {
+        let mut pairs = relation.pairs();
+        for to in pairs.iter_mut().filter_map(|p| p.to.as_mut()) {
+            if let Some((_, new)) = renames.iter().find(|(old, _)| old.eq_ignore_ascii_case(to)) {
+                *to = new.clone();
+            }
+        }
+        relation.set_pairs(pairs);
+    }
+
+    diagram.entities.insert(entity_id.clone(), entity);
+    sync_children(&mut diagram, &old, &entity_id, &mut Vec::new());
+    diagram.link_attributes();
+    Ok(diagram)
+}
+
+// 부모 PK 이름 변경/추가/삭제와 타입을 자식 FK에 반영 (식별 관계로 자식 PK가 바뀌면 재귀)
+fn sync_children(diagram: &mut ErdDiagram, old_parent: &Entity, parent_id: &str, visited: &mut Vec<String>) {
+    if visited.iter().any(|id| id == parent_id) {
+        return;
+    }
+    visited.push(parent_id.to_string());
+    let Some(parent) = diagram.entities.get(parent_id).cloned() else { return };
+    let renames = renamed_columns(old_parent, &parent);
+    let old_pk = primary_key_names(old_parent);
+    let new_pk = primary_key_names(&parent);
+
+    let relation_indexes: Vec<usize> = (0..diagram.relations.len())
+        .filter(|&i| {
+            let r = &diagram.relations[i];
+            r.from_entity_id == parent_id && !matches!(r.cardinality, Cardinality::ManyToMany)
+        })
+        .collect();
+
+    for index in relation_indexes {
+        let relation = diagram.relations[index].clone();
+        let Some(old_child) = diagram.entities.get(&relation.to_entity_id).cloned() else { continue };
+        let mut pairs = relation.pairs();
+
+        // PK 전체를 참조하던 관계는 새 PK 구성을 따름
+        let referenced_pk = !pairs.is_empty() && same_columns(pairs.iter().map(|p| p.from.as_str()), &old_pk);
+        for pair in pairs.iter_mut() {
+            if let Some((_, new)) = renames.iter().find(|(old, _)| old.eq_ignore_ascii_case(&pair.from)) {
+                pair.from = new.clone();
+            }
+        }
+
+        let mut removed = Vec::new();
+        if referenced_pk {
+            pairs.retain(|p| {
+                let keep = new_pk.iter().any(|pk| pk.eq_ignore_ascii_case(&p.from));
+                if !keep {
+                    removed.push(p.clone());
+                }
+                keep
+            });
+            for pk in &new_pk {
+                if !pairs.iter().any(|p| p.from.eq_ignore_ascii_case(pk)) {
+                    pairs.push(ColumnPair::new(pk.clone(), None));
+                }
+            }
+        }
+
+        // 식별 관계라도 기존 FK 컬럼이 자식 PK에 들어 있을 때만 새 FK를 PK로 (식별 여부를 따로 지정하지 않은 예전 관계 보호)
+        let identifying = relation.is_identifying
+            && pairs
+                .iter()
+                .filter_map(|p| p.to.as_deref())
+                .all(|c| find(&old_child.attributes, c).is_some_and(|a| a.is_primary_key));
+        let child = diagram.entities.get_mut(&relation.to_entity_id).unwrap();
+        let (from_end, _) = relation.multiplicities();
+        for pair in pairs.iter_mut() {
+            let Some(pk) = find(&parent.attributes, &pair.from) else { continue };
+            let column = match pair.to.clone() {
+                Some(column) => column,
+                None => {
+                    let mut attr = foreign_key_attribute(&parent, pk, &foreign_key_name(child, &parent, pk));
+                    attr.is_primary_key = identifying;
+                    attr.is_nullable = from_end.is_optional() && !identifying;
+                    let name = attr.physical_name.clone();
+                    child.attributes.push(attr);
+                    pair.to = Some(name.clone());
+                    pair.generated = true;
+                    name
+                }
+            };
+            if let Some(attr) = child.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(&column)) {
+                attr.data_type = pk.data_type.clone();
+                attr.length = pk.length.clone();
+                attr.is_foreign_key = true;
+                attr.foreign_key_reference = Some(format!("{}.{}", parent.physical_name, pk.physical_name));
+            }
+        }
+        diagram.relations[index].set_pairs(pairs);
+
+        release_columns(diagram, &relation.to_entity_id, removed);
+
+        sync_children(diagram, &old_child, &relation.to_entity_id, visited);
+    }
+}
+
+// 부모 PK를 참조하는 FK 속성 (타입/길이 복사)
+pub fn foreign_key_attribute(parent: &Entity, pk: &Attribute, name: &str) -> Attribute {
+    let mut attr = Attribute::new(name, &pk.data_type);
+    attr.logical_name = if pk.logical_name.starts_with(&parent.logical_name) {
+        pk.logical_name.clone()
+    } else {
+        format!("{} {}", parent.logical_name, pk.logical_name)
+    };
+    attr.length = pk.length.clone();
+    attr.is_foreign_key = true;
+    attr.foreign_key_reference = Some(format!("{}.{}", parent.physical_name, pk.physical_name));
+    attr
+}
+
+// "부모테이블_PK" (PK 이름이 이미 테이블명으로 시작하면 그대로), 자식에 같은 이름이 있으면 _2, _3 ...
+fn foreign_key_name(child: &Entity, parent: &Entity, pk: &Attribute) -> String {
+    let prefix = format!("{}_", parent.physical_name.to_lowercase());
+    let base = if pk.physical_name.to_lowercase().starts_with(&prefix) {
+        pk.physical_name.clone()
+    } else {
+        format!("{}_{}", parent.physical_name, pk.physical_name)
+    };
+    let mut name = base.clone();
+    let mut n = 2;
+    while find(&child.attributes, &name).is_some() {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+fn primary_key_names(entity: &Entity) -> Vec<String> {
+    entity.attributes.iter().filter(|a| a.is_primary_key).map(|a| a.physical_name.clone()).collect()
+}
+
+fn find<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes.iter().find(|a| a.physical_name.eq_ignore_ascii_case(name))
+}
+
+// 순서와 대소문자를 무시하고 같은 컬럼 집합인지
+fn same_columns<'a>(columns: impl Iterator<Item = &'a str>, other: &[String]) -> bool {
+    let mut columns: Vec<String> = columns.map(str::to_lowercase).collect();
+    let mut other: Vec<String> = other.iter().map(|c| c.to_lowercase()).collect();
+    columns.sort();
+    other.sort();
+    columns == other
+}
+
+// (이전 물리명, 새 물리명)
+fn renamed_columns(old: &Entity, new: &Entity) -> Vec<(String, String)> {
+    attribute_pairs(old, new)
+        .into_iter()
+        .filter_map(|(o, n)| Some((o?, n?)))
+        .filter(|(o, n)| o.physical_name != n.physical_name)
+        .map(|(o, n)| (o.physical_name.clone(), n.physical_name.clone()))
+        .collect()
+}
+
+// 남은 관계 중 이 컬럼을 FK로 쓰는 관계가 있는지
+fn used_as_foreign_key(diagram: &ErdDiagram, entity_id: &str, column: &str) -> bool {
+    diagram
+        .relations
+        .iter()
+        .filter(|r| r.to_entity_id == entity_id)
+        .flat_map(|r| r.pairs())
+        .any(|p| p.to.is_some_and(|to| to.eq_ignore_ascii_case(column)))
+}
+
+// 빠진 컬럼 쌍의 FK 속성 정리 (남은 관계가 쓰는 컬럼은 그대로). 자식 속성을 지웠으면 true
+fn release_columns(diagram: &mut ErdDiagram, entity_id: &str, pairs: Vec<ColumnPair>) -> bool {
+    let mut removed = Vec::new();
+    for pair in pairs {
+        let Some(column) = pair.to else { continue };
+        if used_as_foreign_key(diagram, entity_id, &column) {
+            continue;
+        }
+        if pair.generated {
+            removed.push(column);
+        } else if let Some(attr) = diagram
+            .entities
+            .get_mut(entity_id)
+            .and_then(|e| e.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(&column)))
+        {
+            attr.is_foreign_key = false;
+            attr.foreign_key_reference = None;
+        }
+    }
+    if removed.is_empty() {
+        return false;
+    }
+    if let Some(entity) = diagram.entities.get_mut(entity_id) {
+        remove_columns(entity, &removed);
+    }
+    true
+}
+
+// 속성과 그 컬럼을 쓰는 인덱스/UNIQUE 제약조건 제거
+fn remove_columns(entity: &mut Entity, columns: &[String]) {
+    let removed = |name: &str| columns.iter().any(|c| c.eq_ignore_ascii_case(name));
+    entity.attributes.retain(|a| !removed(&a.physical_name));
+    entity.indexes.retain(|i| !i.columns.iter().any(|c| removed(&c.name)));
+    entity.unique_constraints.retain(|u| !u.columns.iter().any(|c| removed(c)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    fn orders() -> ErdDiagram {
+        parse_sql(
+            "CREATE TABLE orders (id VARCHAR(20) PRIMARY KEY, title VARCHAR(50));
+             CREATE TABLE items (no INT PRIMARY KEY, note VARCHAR(10));",
+        )
+        .diagram
+    }
+
+    fn relation(identifying: bool, columns: serde_json::Value) -> Relation {
+        serde_json::from_value(serde_json::json!({
+            "id": "rel_items", "name": "has", "from_entity_id": "entity_orders", "to_entity_id": "entity_items",
+            "cardinality": "OneToMany", "is_identifying": identifying, "columns": columns
+        }))
+        .unwrap()
+    }
+
+    fn attribute<'a>(diagram: &'a ErdDiagram, entity_id: &str, name: &str) -> Option<&'a Attribute> {
+        find(&diagram.entities[entity_id].attributes, name)
+    }
+
+    #[test]
+    fn create_copies_the_parent_key_type() {
+        let diagram = create(&orders(), relation(false, serde_json::json!([]))).unwrap();
+        let fk = attribute(&diagram, "entity_items", "orders_id").expect("FK 속성이 없음");
+        assert_eq!(fk.data_type, "VARCHAR");
+        assert_eq!(fk.length.as_deref(), Some("20"));
+        assert!(fk.is_foreign_key && !fk.is_primary_key);
+        assert_eq!(fk.foreign_key_reference.as_deref(), Some("orders.id"));
+        assert!(diagram.relations[0].pairs()[0].generated);
+
+        let identifying = create(&orders(), relation(true, serde_json::json!([]))).unwrap();
+        let fk = attribute(&identifying, "entity_items", "orders_id").unwrap();
+        assert!(fk.is_primary_key && !fk.is_nullable);
+    }
+
+    #[test]
+    fn delete_removes_only_generated_foreign_keys() {
+        let created = create(&orders(), relation(false, serde_json::json!([]))).unwrap();
+        let deleted = delete(&created, "rel_items").unwrap();
+        assert!(attribute(&deleted, "entity_items", "orders_id").is_none());
+        assert_eq!(deleted.entities["entity_items"].attributes.len(), 2);
+
+        // 사용자가 지정한 기존 컬럼은 남기고 FK 표시만 해제
+        let existing = create(&orders(), relation(false, serde_json::json!([{ "from": "id", "to": "note" }]))).unwrap();
+        assert!(attribute(&existing, "entity_items", "note").unwrap().is_foreign_key);
+        let deleted = delete(&existing, "rel_items").unwrap();
+        let note = attribute(&deleted, "entity_items", "note").expect("기존 컬럼이 지워짐");
+        assert!(!note.is_foreign_key);
+        assert!(note.foreign_key_reference.is_none());
+    }
+
+    #[test]
+    fn parent_key_rename_and_type_flow_to_the_child() {
+        let diagram = create(&orders(), relation(false, serde_json::json!([]))).unwrap();
+        let mut parent = diagram.entities["entity_orders"].clone();
+        parent.attributes[0].physical_name = "order_id".to_string();
+        parent.attributes[0].length = Some("30".to_string());
+        let updated = update_entity(&diagram, parent).unwrap();
+
+        let fk = attribute(&updated, "entity_items", "orders_id").unwrap();
+        assert_eq!(fk.length.as_deref(), Some("30"));
+        assert_eq!(fk.foreign_key_reference.as_deref(), Some("orders.order_id"));
+        assert_eq!(updated.relations[0].pairs()[0].from, "order_id");
+    }
+
+    #[test]
+    fn composite_key_growth_flows_through_identifying_relations() {
+        let diagram = create(&orders(), relation(true, serde_json::json!([]))).unwrap();
+        let mut parent = diagram.entities["entity_orders"].clone();
+        let mut line_no = Attribute::new("line_no", "INT");
+        line_no.is_primary_key = true;
+        parent.attributes.push(line_no);
+        let updated = update_entity(&diagram, parent).unwrap();
+
+        let pairs = updated.relations[0].pairs();
+        assert_eq!(pairs.iter().map(|p| p.from.as_str()).collect::<Vec<_>>(), ["id", "line_no"]);
+        let fk = attribute(&updated, "entity_items", "orders_line_no").expect("새 FK 속성이 없음");
+        assert!(fk.is_primary_key && fk.is_foreign_key);
+        assert_eq!(fk.data_type, "INT");
+
+        // 비식별 관계는 새 FK를 PK에 넣지 않음
+        let diagram = create(&orders(), relation(false, serde_json::json!([]))).unwrap();
+        let mut parent = diagram.entities["entity_orders"].clone();
+        parent.attributes[1].is_primary_key = true;
+        let updated = update_entity(&diagram, parent).unwrap();
+        assert!(!attribute(&updated, "entity_items", "orders_title").unwrap().is_primary_key);
+    }
+}
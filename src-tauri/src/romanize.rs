@@ -0,0 +1,101 @@
+// 국어의 로마자 표기법(Revised Romanization) 기반 한글 음역
+// 물리명 생성용이므로 음운 변화는 연음과 'ㄹㄹ' 정도만 반영
+
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p", "h",
+];
+
+const MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we", "wi", "yu", "eu", "ui", "i",
+];
+
+// 받침 (뒤에 자음이 오거나 단어 끝일 때)
+const FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p", "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+// 받침 뒤에 모음(초성 ㅇ)이 오면 다음 음절로 넘어가 소리남 (연음)
+const LINKED_FINALS: [&str; 28] = [
+    "", "g", "kk", "gs", "n", "nj", "n", "d", "r", "lg", "lm", "lb", "ls", "lt", "lp", "r", "m", "b", "bs", "s", "ss", "ng", "j", "ch", "k", "t", "p", "",
+];
+
+const SYLLABLE_BASE: u32 = 0xAC00;
+const SYLLABLE_LAST: u32 = 0xD7A3;
+const INITIAL_IEUNG: usize = 11; // 초성 ㅇ
+const INITIAL_RIEUL: usize = 5;
+const FINAL_RIEUL: usize = 8;
+
+fn decompose(c: char) -> Option<(usize, usize, usize)> {
+    let code = c as u32;
+    if !(SYLLABLE_BASE..=SYLLABLE_LAST).contains(&code) {
+        return None;
+    }
+    let index = (code - SYLLABLE_BASE) as usize;
+    Some((index / (21 * 28), (index % (21 * 28)) / 28, index % 28))
+}
+
+pub fn is_hangul(c: char) -> bool {
+    decompose(c).is_some()
+}
+
+// "회원" -> "hoewon", "한국어" -> "hangugeo" (한글 음절이 아닌 문자는 그대로 둠)
+pub fn romanize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        let Some((initial, medial, fin)) = decompose(c) else {
+            out.push(c);
+            continue;
+        };
+        let previous = i.checked_sub(1).and_then(|p| decompose(chars[p]));
+        let next = chars.get(i + 1).and_then(|&n| decompose(n));
+
+        // 앞 음절 받침이 넘어온 경우 초성 ㅇ은 이미 표기됨, ㄹㄹ은 'll'
+        let initial_text = match (initial, previous) {
+            (INITIAL_RIEUL, Some((_, _, FINAL_RIEUL))) => "l",
+            _ => INITIALS[initial],
+        };
+        out.push_str(initial_text);
+        out.push_str(MEDIALS[medial]);
+
+        let final_text = match next {
+            Some((INITIAL_IEUNG, _, _)) => LINKED_FINALS[fin],
+            _ => FINALS[fin],
+        };
+        out.push_str(final_text);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syllables() {
+        assert_eq!(romanize("회원"), "hoewon");
+        assert_eq!(romanize("학교"), "hakgyo");
+        assert_eq!(romanize("주문번호"), "jumunbeonho");
+    }
+
+    // 받침 뒤 모음은 연음, ㄹㄹ은 ll
+    #[test]
+    fn linking_and_double_rieul() {
+        assert_eq!(romanize("한국어"), "hangugeo");
+        assert_eq!(romanize("설렁탕"), "seolleongtang");
+    }
+
+    #[test]
+    fn double_finals_at_the_end() {
+        assert_eq!(romanize("값"), "gap");
+        assert_eq!(romanize("닭"), "dak");
+    }
+
+    #[test]
+    fn non_hangul_is_kept() {
+        assert_eq!(romanize("A회원1"), "Ahoewon1");
+        assert_eq!(romanize("user_id"), "user_id");
+        assert!(!is_hangul('A') && is_hangul('한'));
+    }
+}
@@ -0,0 +1,1064 @@
+use crate::erd::{Attribute, Cardinality, Entity, EntityKind, ErdDiagram, Index, IndexMethod, SortOrder, UserType};
+use crate::user_types;
+use crate::sql_import::{self, SelectColumn};
+use crate::views;
+use serde::{Deserialize, Serialize};
+
+// DDL 생성 대상 DBMS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SqlDialect {
+    PostgreSql,
+    MySql, // MariaDB 포함
+    Sqlite,
+    SqlServer,
+}
+
+impl SqlDialect {
+    pub fn dialect(&self) -> &'static dyn Dialect {
+        match self {
+            SqlDialect::PostgreSql => &PostgreSql,
+            SqlDialect::MySql => &MySql,
+            SqlDialect::Sqlite => &Sqlite,
+            SqlDialect::SqlServer => &SqlServer,
+        }
+    }
+}
+
+// DBMS별 문법 차이를 흡수하는 확장 지점
+pub trait Dialect {
+    fn name(&self) -> &'static str;
+
+    // 식별자 인용 (예약어/대소문자/공백 대응)
+    fn quote_ident(&self, ident: &str) -> String;
+
+    // 스키마(네임스페이스) 지원 여부 (없으면 테이블 이름에서 스키마를 뺌)
+    fn supports_schemas(&self) -> bool {
+        true
+    }
+
+    // "스키마.테이블" 형태의 테이블 이름 인용
+    fn quote_table(&self, table: &str) -> String {
+        match split_table(table) {
+            (Some(schema), name) if self.supports_schemas() => format!("{}.{}", self.quote_ident(schema), self.quote_ident(name)),
+            (_, name) => self.quote_ident(name),
+        }
+    }
+
+    fn create_schema(&self, schema: &str) -> String {
+        format!("CREATE SCHEMA IF NOT EXISTS {};", self.quote_ident(schema))
+    }
+
+    // 테이블을 다른 스키마로 옮김 (스키마를 지원하지 않으면 None)
+    fn set_schema(&self, table: &str, schema: &str) -> Option<String> {
+        self.supports_schemas()
+            .then(|| format!("ALTER TABLE {} SET SCHEMA {};", self.quote_table(table), self.quote_ident(schema)))
+    }
+
+    // 공통 타입명을 DBMS 고유 타입으로 변환 (모르는 타입은 그대로)
+    fn map_type(&self, data_type: &str) -> String {
+        data_type.to_string()
+    }
+
+    // 자동 증가 컬럼의 타입 + 키워드
+    fn auto_increment(&self, column_type: &str) -> String;
+
+    // SQLite처럼 자동 증가가 단일 컬럼 PK 선언에 묶여 있는 경우
+    fn auto_increment_is_inline_primary_key(&self) -> bool {
+        false
+    }
+
+    // ALTER TABLE로 제약조건 추가/삭제, 컬럼 변경이 가능한지 (불가하면 FK는 CREATE TABLE 안에 선언,
+    // 마이그레이션은 테이블 재생성)
+    fn supports_alter_constraints(&self) -> bool {
+        true
+    }
+
+    // 같은 스키마 안에서 이름 변경 (new의 스키마는 무시)
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        format!("ALTER TABLE {} RENAME TO {};", self.quote_table(old), self.quote_ident(split_table(new).1))
+    }
+
+    fn rename_column(&self, table: &str, old: &str, new: &str) -> String {
+        format!(
+            "ALTER TABLE {} RENAME COLUMN {} TO {};",
+            self.quote_table(table),
+            self.quote_ident(old),
+            self.quote_ident(new)
+        )
+    }
+
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        format!("ALTER TABLE {} ADD COLUMN {};", self.quote_table(table), definition)
+    }
+
+    fn drop_column(&self, table: &str, column: &str) -> String {
+        format!("ALTER TABLE {} DROP COLUMN {};", self.quote_table(table), self.quote_ident(column))
+    }
+
+    fn add_constraint(&self, table: &str, clause: &str) -> String {
+        format!("ALTER TABLE {} ADD {};", self.quote_table(table), clause)
+    }
+
+    fn drop_constraint(&self, table: &str, name: &str, _kind: ConstraintKind) -> String {
+        format!("ALTER TABLE {} DROP CONSTRAINT {};", self.quote_table(table), self.quote_ident(name))
+    }
+
+    // 타입/NULL/기본값/자동 증가 변경. 표현할 수 없으면 Err(경고)
+    fn alter_column(&self, table: &str, old: &Attribute, new: &Attribute) -> Result<Vec<String>, String>;
+
+    // CREATE INDEX 문과 표현할 수 없어 빠진 옵션에 대한 경고
+    // (기본: 부분 인덱스 WHERE 지원, 인덱스 방식 지정 불가)
+    fn create_index(&self, table: &str, index: &Index) -> (String, Vec<String>) {
+        let mut warnings = Vec::new();
+        if index.method != IndexMethod::BTree {
+            warnings.push(unsupported_method(self.name(), table, index));
+        }
+        let statement = format!(
+            "{} ({}){};",
+            create_index_head(self, table, index),
+            index_columns(index, self),
+            index_predicate(index)
+        );
+        (statement, warnings)
+    }
+
+    // 인덱스는 테이블과 같은 스키마에 있음
+    fn drop_index(&self, table: &str, name: &str) -> String {
+        let index = match split_table(table).0 {
+            Some(schema) => format!("{}.{}", schema, name),
+            None => name.to_string(),
+        };
+        format!("DROP INDEX {};", self.quote_table(&index))
+    }
+
+    // CREATE TYPE/CREATE DOMAIN 지원 여부 (없으면 속성에 타입 정의를 펼쳐서 출력)
+    fn supports_user_types(&self) -> bool {
+        false
+    }
+
+    // 열거형을 표현하는 고유 컬럼 타입 (없으면 VARCHAR + CHECK)
+    fn enum_type(&self, _values: &[String]) -> Option<String> {
+        None
+    }
+
+    // 구체화 뷰 지원 여부 (없으면 일반 뷰로 생성)
+    fn supports_materialized_views(&self) -> bool {
+        false
+    }
+
+    // columns가 비어 있으면 컬럼 목록 없이 SELECT의 이름을 씀
+    fn create_view(&self, view: &str, columns: &[String], query: &str, materialized: bool) -> String {
+        let kind = if materialized && self.supports_materialized_views() { "MATERIALIZED VIEW" } else { "VIEW" };
+        let columns = if columns.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", columns.iter().map(|c| self.quote_ident(c)).collect::<Vec<_>>().join(", "))
+        };
+        format!("CREATE {} {}{} AS\n{};", kind, self.quote_table(view), columns, query)
+    }
+
+    fn drop_view(&self, view: &str, materialized: bool) -> String {
+        let kind = if materialized && self.supports_materialized_views() { "MATERIALIZED VIEW" } else { "VIEW" };
+        format!("DROP {} {};", kind, self.quote_table(view))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+    Check,
+}
+
+pub struct PostgreSql;
+pub struct MySql;
+pub struct Sqlite;
+pub struct SqlServer;
+
+impl Dialect for PostgreSql {
+    fn name(&self) -> &'static str { "PostgreSQL" }
+
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    fn supports_user_types(&self) -> bool {
+        true
+    }
+
+    fn supports_materialized_views(&self) -> bool {
+        true
+    }
+
+    fn map_type(&self, data_type: &str) -> String {
+        match data_type.to_uppercase().as_str() {
+            "DATETIME" => "TIMESTAMP".to_string(),
+            "TINYINT" => "SMALLINT".to_string(),
+            "DOUBLE" => "DOUBLE PRECISION".to_string(),
+            "BLOB" => "BYTEA".to_string(),
+            _ => data_type.to_string(),
+        }
+    }
+
+    fn auto_increment(&self, column_type: &str) -> String {
+        format!("{} GENERATED BY DEFAULT AS IDENTITY", column_type)
+    }
+
+    fn create_index(&self, table: &str, index: &Index) -> (String, Vec<String>) {
+        let using = match index.method {
+            IndexMethod::BTree => String::new(),
+            method => format!(" USING {}", method.label()),
+        };
+        let statement = format!(
+            "{}{} ({}){};",
+            create_index_head(self, table, index),
+            using,
+            index_columns(index, self),
+            index_predicate(index)
+        );
+        (statement, Vec::new())
+    }
+
+    fn alter_column(&self, table: &str, old: &Attribute, new: &Attribute) -> Result<Vec<String>, String> {
+        let prefix = format!("ALTER TABLE {} ALTER COLUMN {}", self.quote_table(table), self.quote_ident(column_name(new)));
+        let mut statements = Vec::new();
+        let new_type = column_type(new, self);
+        if column_type(old, self) != new_type {
+            statements.push(format!("{} TYPE {};", prefix, new_type));
+        }
+        if old.is_nullable != new.is_nullable {
+            let action = if new.is_nullable { "DROP NOT NULL" } else { "SET NOT NULL" };
+            statements.push(format!("{} {};", prefix, action));
+        }
+        if old.default_value != new.default_value {
+            match new.default_value.as_deref().filter(|d| !d.trim().is_empty()) {
+                Some(default) => statements.push(format!("{} SET DEFAULT {};", prefix, default)),
+                None => statements.push(format!("{} DROP DEFAULT;", prefix)),
+            }
+        }
+        if old.is_auto_increment != new.is_auto_increment {
+            let action = if new.is_auto_increment { "ADD GENERATED BY DEFAULT AS IDENTITY" } else { "DROP IDENTITY IF EXISTS" };
+            statements.push(format!("{} {};", prefix, action));
+        }
+        Ok(statements)
+    }
+}
+
+impl Dialect for MySql {
+    fn name(&self) -> &'static str { "MySQL/MariaDB" }
+
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("`{}`", ident.replace('`', "``"))
+    }
+
+    fn enum_type(&self, values: &[String]) -> Option<String> {
+        Some(format!("ENUM({})", quote_literals(values)))
+    }
+
+    fn map_type(&self, data_type: &str) -> String {
+        match data_type.to_uppercase().as_str() {
+            "BYTEA" => "BLOB".to_string(),
+            "TIMESTAMPTZ" => "TIMESTAMP".to_string(),
+            _ => data_type.to_string(),
+        }
+    }
+
+    fn auto_increment(&self, column_type: &str) -> String {
+        format!("{} AUTO_INCREMENT", column_type)
+    }
+
+    // MySQL의 스키마는 데이터베이스이므로 RENAME TABLE로 이름 변경과 이동을 함
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        let target = qualify(split_table(old).0, split_table(new).1);
+        format!("RENAME TABLE {} TO {};", self.quote_table(old), self.quote_table(&target))
+    }
+
+    fn set_schema(&self, table: &str, schema: &str) -> Option<String> {
+        let target = qualify(Some(schema), split_table(table).1);
+        Some(format!("RENAME TABLE {} TO {};", self.quote_table(table), self.quote_table(&target)))
+    }
+
+    fn drop_constraint(&self, table: &str, name: &str, kind: ConstraintKind) -> String {
+        let target = match kind {
+            ConstraintKind::PrimaryKey => "PRIMARY KEY".to_string(),
+            ConstraintKind::Unique => format!("INDEX {}", self.quote_ident(name)),
+            ConstraintKind::ForeignKey => format!("FOREIGN KEY {}", self.quote_ident(name)),
+            ConstraintKind::Check => format!("CHECK {}", self.quote_ident(name)),
+        };
+        format!("ALTER TABLE {} DROP {};", self.quote_table(table), target)
+    }
+
+    // MODIFY COLUMN은 컬럼 정의 전체를 다시 씀
+    fn alter_column(&self, table: &str, _old: &Attribute, new: &Attribute) -> Result<Vec<String>, String> {
+        Ok(vec![format!(
+            "ALTER TABLE {} MODIFY COLUMN {};",
+            self.quote_table(table),
+            column_definition(new, self, false)
+        )])
+    }
+
+    // MySQL은 부분 인덱스와 GIN이 없고, 방식은 컬럼 목록 뒤에 지정
+    fn create_index(&self, table: &str, index: &Index) -> (String, Vec<String>) {
+        let mut warnings = Vec::new();
+        let using = match index.method {
+            IndexMethod::BTree => "",
+            IndexMethod::Hash => " USING HASH",
+            IndexMethod::Gin => {
+                warnings.push(unsupported_method(self.name(), table, index));
+                ""
+            }
+        };
+        if index.predicate.as_deref().is_some_and(|p| !p.trim().is_empty()) {
+            warnings.push(format!("{}: {}는 부분 인덱스를 지원하지 않아 '{}'의 WHERE 조건을 생략함", table, self.name(), index.name));
+        }
+        let statement = format!("{} ({}){};", create_index_head(self, table, index), index_columns(index, self), using);
+        (statement, warnings)
+    }
+
+    fn drop_index(&self, table: &str, name: &str) -> String {
+        format!("DROP INDEX {} ON {};", self.quote_ident(name), self.quote_table(table))
+    }
+}
+
+impl Dialect for Sqlite {
+    fn name(&self) -> &'static str { "SQLite" }
+
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    // SQLite는 INTEGER PRIMARY KEY 컬럼만 AUTOINCREMENT 가능
+    fn auto_increment(&self, _column_type: &str) -> String {
+        "INTEGER PRIMARY KEY AUTOINCREMENT".to_string()
+    }
+
+    fn auto_increment_is_inline_primary_key(&self) -> bool {
+        true
+    }
+
+    fn supports_schemas(&self) -> bool {
+        false
+    }
+
+    fn supports_alter_constraints(&self) -> bool {
+        false
+    }
+
+    fn alter_column(&self, table: &str, _old: &Attribute, new: &Attribute) -> Result<Vec<String>, String> {
+        Err(format!("SQLite는 컬럼 변경을 지원하지 않습니다 ({}.{}): 테이블을 재생성해야 합니다", table, column_name(new)))
+    }
+}
+
+impl Dialect for SqlServer {
+    fn name(&self) -> &'static str { "SQL Server" }
+
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("[{}]", ident.replace(']', "]]"))
+    }
+
+    fn map_type(&self, data_type: &str) -> String {
+        match data_type.to_uppercase().as_str() {
+            "BOOLEAN" | "BOOL" => "BIT".to_string(),
+            "DATETIME" | "TIMESTAMP" => "DATETIME2".to_string(),
+            "TEXT" => "NVARCHAR(MAX)".to_string(),
+            "DOUBLE" => "FLOAT".to_string(),
+            "BLOB" | "BYTEA" => "VARBINARY(MAX)".to_string(),
+            _ => data_type.to_string(),
+        }
+    }
+
+    fn auto_increment(&self, column_type: &str) -> String {
+        format!("{} IDENTITY(1,1)", column_type)
+    }
+
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        format!("EXEC sp_rename '{}', '{}';", old.replace('\'', "''"), split_table(new).1.replace('\'', "''"))
+    }
+
+    fn create_schema(&self, schema: &str) -> String {
+        format!(
+            "IF SCHEMA_ID(N'{}') IS NULL EXEC('CREATE SCHEMA {}');",
+            schema.replace('\'', "''"),
+            self.quote_ident(schema).replace('\'', "''")
+        )
+    }
+
+    fn set_schema(&self, table: &str, schema: &str) -> Option<String> {
+        Some(format!("ALTER SCHEMA {} TRANSFER {};", self.quote_ident(schema), self.quote_table(table)))
+    }
+
+    fn rename_column(&self, table: &str, old: &str, new: &str) -> String {
+        format!(
+            "EXEC sp_rename '{}.{}', '{}', 'COLUMN';",
+            table.replace('\'', "''"),
+            old.replace('\'', "''"),
+            new.replace('\'', "''")
+        )
+    }
+
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        format!("ALTER TABLE {} ADD {};", self.quote_table(table), definition)
+    }
+
+    fn drop_index(&self, table: &str, name: &str) -> String {
+        format!("DROP INDEX {} ON {};", self.quote_ident(name), self.quote_table(table))
+    }
+
+    // CREATE VIEW는 배치의 유일한 문이어야 하므로 GO로 나눔
+    fn create_view(&self, view: &str, columns: &[String], query: &str, _materialized: bool) -> String {
+        let columns = if columns.is_empty() { String::new() } else { format!(" ({})", quote_list(columns, self)) };
+        format!("GO\nCREATE VIEW {}{} AS\n{};\nGO", self.quote_table(view), columns, query)
+    }
+
+    fn alter_column(&self, table: &str, old: &Attribute, new: &Attribute) -> Result<Vec<String>, String> {
+        if old.is_auto_increment != new.is_auto_increment {
+            return Err(format!("SQL Server는 IDENTITY 속성을 변경할 수 없습니다 ({}.{})", table, column_name(new)));
+        }
+        let column = column_name(new);
+        let mut statements = Vec::new();
+        let default_changed = old.default_value != new.default_value;
+        if default_changed && old.default_value.is_some() {
+            // DEFAULT 제약조건 이름은 시스템이 만들므로 카탈로그에서 찾아 삭제
+            let variable: String = format!("@drop_default_{}_{}", table, column)
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '@' { c } else { '_' })
+                .collect();
+            let object = table.replace('\'', "''");
+            statements.push(format!(
+                "DECLARE {variable} NVARCHAR(MAX) = (SELECT 'ALTER TABLE {table} DROP CONSTRAINT ' + QUOTENAME(name) FROM sys.default_constraints \
+                 WHERE parent_object_id = OBJECT_ID(N'{object}') AND parent_column_id = COLUMNPROPERTY(OBJECT_ID(N'{object}'), N'{column}', 'ColumnId'));\n\
+                 EXEC sp_executesql {variable};",
+                variable = variable,
+                table = self.quote_table(table).replace('\'', "''"),
+                object = object,
+                column = column.replace('\'', "''"),
+            ));
+        }
+        if column_type(old, self) != column_type(new, self) || old.is_nullable != new.is_nullable {
+            let nullability = if new.is_nullable && !new.is_primary_key { "NULL" } else { "NOT NULL" };
+            statements.push(format!(
+                "ALTER TABLE {} ALTER COLUMN {} {} {};",
+                self.quote_table(table),
+                self.quote_ident(column),
+                column_type(new, self),
+                nullability
+            ));
+        }
+        if default_changed {
+            if let Some(default) = new.default_value.as_deref().filter(|d| !d.trim().is_empty()) {
+                statements.push(format!(
+                    "ALTER TABLE {} ADD DEFAULT {} FOR {};",
+                    self.quote_table(table),
+                    default,
+                    self.quote_ident(column)
+                ));
+            }
+        }
+        Ok(statements)
+    }
+}
+
+// 관계에서 도출한 FK 제약조건
+#[derive(Debug, Clone)]
+pub struct ForeignKey {
+    pub name: String,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+pub fn create_script(diagram: &ErdDiagram, dialect: &dyn Dialect) -> String {
+    let diagram = &resolve_user_types(diagram, dialect);
+    let mut sql = String::new();
+    sql.push_str(&format!("-- ERD Diagram DDL ({})\n\n", dialect.name()));
+
+    // 결정적 순서(물리명 기준), 뷰는 테이블과 FK 뒤에 의존 순서대로
+    let mut entities: Vec<&Entity> = diagram.entities.values().filter(|e| !e.is_view()).collect();
+    entities.sort_by_key(|e| qualified_table(e).to_lowercase());
+
+    let foreign_keys = foreign_keys(diagram);
+
+    let schemas = schemas(diagram);
+    if dialect.supports_schemas() {
+        for schema in &schemas {
+            sql.push_str(&dialect.create_schema(schema));
+            sql.push('\n');
+        }
+    } else if !schemas.is_empty() {
+        sql.push_str(&format!("-- {}는 스키마를 지원하지 않아 스키마 이름({})을 생략함\n", dialect.name(), schemas.join(", ")));
+    }
+    if !schemas.is_empty() {
+        sql.push('\n');
+    }
+
+    if dialect.supports_user_types() {
+        for user_type in &diagram.user_types {
+            sql.push_str(&create_type(user_type, dialect));
+            sql.push('\n');
+        }
+        if !diagram.user_types.is_empty() {
+            sql.push('\n');
+        }
+    }
+
+    for entity in &entities {
+        let inline_fks: Vec<&ForeignKey> = if dialect.supports_alter_constraints() {
+            Vec::new()
+        } else {
+            foreign_keys.iter().filter(|fk| fk.table == qualified_table(entity)).collect()
+        };
+        sql.push_str(&create_table(entity, &inline_fks, dialect));
+        sql.push('\n');
+    }
+
+    for entity in &entities {
+        let indexes = create_indexes(entity, dialect);
+        if !indexes.is_empty() {
+            sql.push_str(&indexes.join("\n"));
+            sql.push_str("\n\n");
+        }
+    }
+
+    if dialect.supports_alter_constraints() {
+        for fk in &foreign_keys {
+            sql.push_str(&format!(
+                "ALTER TABLE {} ADD {};\n",
+                dialect.quote_table(&fk.table),
+                foreign_key_clause(fk, dialect)
+            ));
+        }
+    }
+
+    let views = views::ordered_views(diagram);
+    if !views.is_empty() {
+        sql.push('\n');
+    }
+    for view in &views {
+        sql.push_str(&create_view(view, dialect));
+        sql.push_str("\n\n");
+        if view.kind == EntityKind::MaterializedView && dialect.supports_materialized_views() {
+            let indexes = create_indexes(view, dialect);
+            if !indexes.is_empty() {
+                sql.push_str(&indexes.join("\n"));
+                sql.push_str("\n\n");
+            }
+        }
+    }
+
+    for relation in &diagram.relations {
+        if matches!(relation.cardinality, Cardinality::ManyToMany) {
+            sql.push_str(&format!("-- N:M 관계 '{}'는 연결 테이블 없이 FK로 표현할 수 없어 생략됨\n", relation.name));
+        }
+    }
+
+    sql
+}
+
+// 정의가 없으면 주석만, 구체화 뷰를 지원하지 않으면 일반 뷰로 만들고 주석을 남김
+pub fn create_view(view: &Entity, dialect: &dyn Dialect) -> String {
+    let name = qualified_table(view);
+    let Some(query) = view.view_query() else {
+        return format!("-- 뷰 '{}'의 정의가 없어 생략됨", name);
+    };
+    // SELECT 결과 개수와 다르면 CREATE VIEW가 실패하므로 컬럼 목록을 생략 (* 는 개수를 알 수 없어 그대로 씀)
+    let select = sql_import::parse_view_query(query).columns;
+    let columns: Vec<String> = if select.iter().any(|c| matches!(c, SelectColumn::All(_))) || select.len() == view.attributes.len() {
+        view.attributes.iter().map(|a| column_name(a).to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    let statement = dialect.create_view(&name, &columns, query, view.kind == EntityKind::MaterializedView);
+    if view.kind == EntityKind::MaterializedView && !dialect.supports_materialized_views() {
+        format!("-- {}는 구체화 뷰를 지원하지 않아 '{}'을(를) 일반 뷰로 생성함\n{}", dialect.name(), name, statement)
+    } else {
+        statement
+    }
+}
+
+// 사용자 정의 타입 참조를 DBMS에 맞게 바꾼 사본 (CREATE TYPE을 지원하면 타입 이름, 아니면 정의를 펼침)
+pub fn resolve_user_types(diagram: &ErdDiagram, dialect: &dyn Dialect) -> ErdDiagram {
+    let mut resolved = diagram.clone();
+    for attr in resolved.entities.values_mut().flat_map(|e| e.attributes.iter_mut()) {
+        let Some(user_type) = diagram.user_type(&attr.data_type) else { continue };
+        if dialect.supports_user_types() {
+            attr.data_type = dialect.quote_ident(user_type.name());
+            attr.length = None;
+        } else {
+            let native_enum = match user_type {
+                UserType::Enum(e) => dialect.enum_type(&e.values),
+                UserType::Domain(_) => None,
+            };
+            user_types::inline_attribute(attr, user_type, native_enum);
+        }
+    }
+    resolved
+}
+
+pub fn create_type(user_type: &UserType, dialect: &dyn Dialect) -> String {
+    match user_type {
+        UserType::Enum(e) => format!("CREATE TYPE {} AS ENUM ({});", dialect.quote_ident(&e.name), quote_literals(&e.values)),
+        UserType::Domain(d) => {
+            let mut base = Attribute::new(&d.name, &d.base_type);
+            base.length = d.length.clone();
+            let mut statement = format!("CREATE DOMAIN {} AS {}", dialect.quote_ident(&d.name), column_type(&base, dialect));
+            if let Some(default) = d.default_value.as_deref().filter(|v| !v.trim().is_empty()) {
+                statement.push_str(&format!(" DEFAULT {}", default));
+            }
+            if let Some(check) = d.check.as_deref().filter(|c| !c.trim().is_empty()) {
+                statement.push_str(&format!(" CONSTRAINT {} CHECK ({})", dialect.quote_ident(&domain_check_name(&d.name)), check));
+            }
+            statement.push(';');
+            statement
+        }
+    }
+}
+
+pub fn drop_type(user_type: &UserType, dialect: &dyn Dialect) -> String {
+    let kind = match user_type {
+        UserType::Enum(_) => "TYPE",
+        UserType::Domain(_) => "DOMAIN",
+    };
+    format!("DROP {} {};", kind, dialect.quote_ident(user_type.name()))
+}
+
+pub fn domain_check_name(domain: &str) -> String {
+    format!("ck_{}", domain)
+}
+
+// 'a', 'b' (작은따옴표 이스케이프)
+pub fn quote_literals(values: &[String]) -> String {
+    values.iter().map(|v| format!("'{}'", v.replace('\'', "''"))).collect::<Vec<_>>().join(", ")
+}
+
+pub fn create_table(entity: &Entity, inline_fks: &[&ForeignKey], dialect: &dyn Dialect) -> String {
+    let inline_pk = has_inline_primary_key(entity, dialect);
+
+    let mut lines: Vec<String> = entity
+        .attributes
+        .iter()
+        .map(|attr| column_definition(attr, dialect, inline_pk))
+        .collect();
+
+    if !inline_pk {
+        lines.extend(primary_key_clause(entity, dialect));
+    }
+    lines.extend(unique_clauses(entity, dialect).into_iter().map(|(_, clause)| clause));
+    lines.extend(check_clauses(entity, dialect).into_iter().map(|(_, clause)| clause));
+
+    for fk in inline_fks {
+        lines.push(foreign_key_clause(fk, dialect));
+    }
+
+    format!(
+        "CREATE TABLE {} (\n    {}\n);\n",
+        dialect.quote_table(&qualified_table(entity)),
+        lines.join(",\n    ")
+    )
+}
+
+// 엔티티의 인덱스 생성문 (빠진 옵션은 주석으로 표시)
+pub fn create_indexes(entity: &Entity, dialect: &dyn Dialect) -> Vec<String> {
+    let mut lines = Vec::new();
+    for index in &entity.indexes {
+        let (statement, warnings) = dialect.create_index(&qualified_table(entity), index);
+        lines.extend(warnings.into_iter().map(|w| format!("-- 경고: {}", w)));
+        lines.push(statement);
+    }
+    lines
+}
+
+fn create_index_head(dialect: &(impl Dialect + ?Sized), table: &str, index: &Index) -> String {
+    format!(
+        "CREATE {}INDEX {} ON {}",
+        if index.is_unique { "UNIQUE " } else { "" },
+        dialect.quote_ident(&index.name),
+        dialect.quote_table(table)
+    )
+}
+
+fn index_columns(index: &Index, dialect: &(impl Dialect + ?Sized)) -> String {
+    index
+        .columns
+        .iter()
+        .map(|c| {
+            // 식 인덱스("lower(email)")는 그대로 출력
+            let column = if c.name.contains('(') { format!("({})", c.name) } else { dialect.quote_ident(&c.name) };
+            match c.order {
+                SortOrder::Asc => column,
+                SortOrder::Desc => format!("{} DESC", column),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn index_predicate(index: &Index) -> String {
+    match index.predicate.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(predicate) => format!(" WHERE {}", predicate),
+        None => String::new(),
+    }
+}
+
+fn unsupported_method(dialect: &str, table: &str, index: &Index) -> String {
+    format!("{}: {}는 '{}'의 {} 방식을 지원하지 않아 기본 인덱스로 생성함", table, dialect, index.name, index.method.label())
+}
+
+pub fn column_definition(attr: &Attribute, dialect: &dyn Dialect, inline_pk: bool) -> String {
+    let mut column_type = column_type(attr, dialect);
+    let auto_increment = attr.is_auto_increment
+        && (!dialect.auto_increment_is_inline_primary_key() || (inline_pk && attr.is_primary_key));
+    if auto_increment {
+        column_type = dialect.auto_increment(&column_type);
+    }
+
+    let mut definition = format!("{} {}", dialect.quote_ident(column_name(attr)), column_type);
+    if !attr.is_nullable || attr.is_primary_key {
+        definition.push_str(" NOT NULL");
+    }
+    if let Some(default) = attr.default_value.as_deref().filter(|d| !d.trim().is_empty()) {
+        // IDENTITY 컬럼은 DEFAULT와 함께 쓸 수 없음
+        if !auto_increment {
+            definition.push_str(&format!(" DEFAULT {}", default));
+        }
+    }
+    definition
+}
+
+// SQLite의 INTEGER PRIMARY KEY AUTOINCREMENT처럼 PK가 컬럼 정의에 포함되는지
+pub fn has_inline_primary_key(entity: &Entity, dialect: &dyn Dialect) -> bool {
+    let pk_columns: Vec<&Attribute> = entity.attributes.iter().filter(|a| a.is_primary_key).collect();
+    dialect.auto_increment_is_inline_primary_key() && pk_columns.len() == 1 && pk_columns[0].is_auto_increment
+}
+
+pub fn primary_key_name(table: &str) -> String {
+    format!("pk_{}", table)
+}
+
+pub fn unique_name(table: &str, column: &str) -> String {
+    format!("uq_{}_{}", table, column)
+}
+
+pub fn check_name(table: &str, column: &str) -> String {
+    format!("ck_{}_{}", table, column)
+}
+
+pub fn primary_key_clause(entity: &Entity, dialect: &dyn Dialect) -> Option<String> {
+    let columns: Vec<String> = entity
+        .attributes
+        .iter()
+        .filter(|a| a.is_primary_key)
+        .map(|a| column_name(a).to_string())
+        .collect();
+    if columns.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "CONSTRAINT {} PRIMARY KEY ({})",
+        dialect.quote_ident(&primary_key_name(table_name(entity))),
+        quote_list(&columns, dialect)
+    ))
+}
+
+// 이름 있는 UNIQUE 제약조건 (마이그레이션에서 이름으로 삭제할 수 있도록)
+pub fn unique_clauses(entity: &Entity, dialect: &dyn Dialect) -> Vec<(String, String)> {
+    entity
+        .attributes
+        .iter()
+        .filter(|a| a.is_unique && !a.is_primary_key)
+        .map(|a| {
+            let name = unique_name(table_name(entity), column_name(a));
+            let clause = format!(
+                "CONSTRAINT {} UNIQUE ({})",
+                dialect.quote_ident(&name),
+                dialect.quote_ident(column_name(a))
+            );
+            (name, clause)
+        })
+        .chain(named_unique_clauses(entity, dialect))
+        .collect()
+}
+
+// 엔티티에 직접 정의한 (복합) UNIQUE 제약조건
+pub fn named_unique_clauses(entity: &Entity, dialect: &dyn Dialect) -> Vec<(String, String)> {
+    entity
+        .unique_constraints
+        .iter()
+        .filter(|u| !u.columns.is_empty())
+        .map(|u| {
+            let clause = format!("CONSTRAINT {} UNIQUE ({})", dialect.quote_ident(&u.name), quote_list(&u.columns, dialect));
+            (u.name.clone(), clause)
+        })
+        .collect()
+}
+
+// 컬럼 CHECK(ck_테이블_컬럼)와 테이블 CHECK 제약조건
+pub fn check_clauses(entity: &Entity, dialect: &dyn Dialect) -> Vec<(String, String)> {
+    let column_checks = entity.attributes.iter().filter_map(|a| {
+        let expression = a.check.as_deref().map(str::trim).filter(|c| !c.is_empty())?;
+        Some((check_name(table_name(entity), column_name(a)), expression.to_string()))
+    });
+    let table_checks = entity
+        .check_constraints
+        .iter()
+        .filter(|c| !c.expression.trim().is_empty())
+        .map(|c| (c.name.clone(), c.expression.trim().to_string()));
+    column_checks
+        .chain(table_checks)
+        .map(|(name, expression)| {
+            let clause = format!("CONSTRAINT {} CHECK ({})", dialect.quote_ident(&name), expression);
+            (name, clause)
+        })
+        .collect()
+}
+
+pub fn column_type(attr: &Attribute, dialect: &dyn Dialect) -> String {
+    let mapped = dialect.map_type(attr.data_type.trim());
+    match attr.length.as_deref().map(str::trim).filter(|l| !l.is_empty()) {
+        Some(length) if !mapped.contains('(') => format!("{}({})", mapped, length),
+        _ => mapped,
+    }
+}
+
+pub fn foreign_key_clause(fk: &ForeignKey, dialect: &dyn Dialect) -> String {
+    format!(
+        "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+        dialect.quote_ident(&fk.name),
+        quote_list(&fk.columns, dialect),
+        dialect.quote_table(&fk.referenced_table),
+        quote_list(&fk.referenced_columns, dialect)
+    )
+}
+
+// 모든 컬럼 쌍에 FK 컬럼이 지정된 1:1, 1:N 관계만 제약조건이 됨
+pub fn foreign_keys(diagram: &ErdDiagram) -> Vec<ForeignKey> {
+    let mut foreign_keys = Vec::new();
+    for relation in &diagram.relations {
+        if matches!(relation.cardinality, Cardinality::ManyToMany) {
+            continue;
+        }
+        let (Some(parent), Some(child)) = (
+            diagram.entities.get(&relation.from_entity_id),
+            diagram.entities.get(&relation.to_entity_id),
+        ) else {
+            continue;
+        };
+        // 뷰에는 FK 제약조건을 걸 수 없음
+        if parent.is_view() || child.is_view() {
+            continue;
+        }
+        let Some((referenced_columns, columns)) = relation.foreign_key_columns() else {
+            continue;
+        };
+
+        foreign_keys.push(ForeignKey {
+            name: format!("fk_{}_{}", table_name(child), columns.join("_")),
+            table: qualified_table(child),
+            columns,
+            referenced_table: qualified_table(parent),
+            referenced_columns,
+        });
+    }
+    foreign_keys.sort_by(|a, b| a.table.cmp(&b.table).then_with(|| a.name.cmp(&b.name)));
+    foreign_keys
+}
+
+pub fn table_name(entity: &Entity) -> &str {
+    if entity.physical_name.trim().is_empty() { &entity.logical_name } else { &entity.physical_name }
+}
+
+// DDL 문에서 쓰는 "스키마.테이블" (제약조건 이름에는 table_name 사용)
+pub fn qualified_table(entity: &Entity) -> String {
+    qualify(entity.schema_name(), table_name(entity))
+}
+
+// 점이 들어간 이름은 큰따옴표로 감싸서 split_table로 되돌릴 수 있게 함
+pub fn qualify(schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quote_dotted(schema), quote_dotted(table)),
+        None => quote_dotted(table),
+    }
+}
+
+fn quote_dotted(name: &str) -> String {
+    if name.contains('.') {
+        format!("\"{}\"", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// "스키마.테이블"을 마지막 점에서 (스키마, 테이블)로 나눔. 따옴표("", ``, [])로 감싼 부분의 점은 이름의 일부
+pub fn split_table(table: &str) -> (Option<&str>, &str) {
+    let mut quote = None;
+    let mut split = None;
+    for (i, c) in table.char_indices() {
+        match (quote, c) {
+            (Some(close), _) if c == close => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (None, '.') => split = Some(i),
+            _ => {}
+        }
+    }
+    match split {
+        Some(i) => (Some(unquote(&table[..i])), unquote(&table[i + 1..])),
+        None => (None, unquote(table)),
+    }
+}
+
+fn unquote(name: &str) -> &str {
+    for (open, close) in [('"', '"'), ('`', '`'), ('[', ']')] {
+        if name.len() >= 2 && name.starts_with(open) && name.ends_with(close) {
+            return &name[1..name.len() - 1];
+        }
+    }
+    name
+}
+
+// 다이어그램에서 쓰는 스키마 목록 (이름순)
+pub fn schemas(diagram: &ErdDiagram) -> Vec<&str> {
+    let mut schemas: Vec<&str> = diagram.entities.values().filter_map(|e| e.schema_name()).collect();
+    schemas.sort();
+    schemas.dedup();
+    schemas
+}
+
+pub fn column_name(attr: &Attribute) -> &str {
+    if attr.physical_name.trim().is_empty() { &attr.logical_name } else { &attr.physical_name }
+}
+
+pub fn quote_list(columns: &[String], dialect: &dyn Dialect) -> String {
+    columns.iter().map(|c| dialect.quote_ident(c)).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    // sales.orders와 점이 들어간 "my.items", 다른 스키마의 같은 이름 hr.orders
+    const SCHEMAS: &str = "CREATE TABLE sales.orders (id INT PRIMARY KEY);
+        CREATE TABLE \"sales\".\"my.items\" (id INT PRIMARY KEY, order_id INT REFERENCES sales.orders (id));
+        CREATE TABLE hr.orders (id INT PRIMARY KEY);
+        CREATE TABLE \"no.schema\" (id INT PRIMARY KEY);";
+
+    #[test]
+    fn split_table_uses_the_last_unquoted_dot() {
+        assert_eq!(split_table("sales.orders"), (Some("sales"), "orders"));
+        assert_eq!(split_table("orders"), (None, "orders"));
+        assert_eq!(split_table("db.sales.orders"), (Some("db.sales"), "orders"));
+        assert_eq!(split_table("\"my.table\""), (None, "my.table"));
+        assert_eq!(split_table("sales.\"my.table\""), (Some("sales"), "my.table"));
+        assert_eq!(split_table("[dbo].[a.b]"), (Some("dbo"), "a.b"));
+        assert_eq!(split_table(&qualify(Some("s.x"), "t.y")), (Some("s.x"), "t.y"));
+    }
+
+    #[test]
+    fn schema_qualified_ddl() {
+        let result = parse_sql(SCHEMAS);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.diagram.entities.len(), 4);
+
+        let postgres = result.diagram.to_sql(SqlDialect::PostgreSql);
+        assert!(postgres.contains("CREATE SCHEMA IF NOT EXISTS \"sales\";"));
+        assert!(postgres.contains("CREATE TABLE \"sales\".\"my.items\" ("));
+        assert!(postgres.contains("CREATE TABLE \"hr\".\"orders\" ("));
+        assert!(postgres.contains("CREATE TABLE \"no.schema\" ("));
+        assert!(postgres.contains("REFERENCES \"sales\".\"orders\" (\"id\")"));
+
+        let sql_server = result.diagram.to_sql(SqlDialect::SqlServer);
+        assert!(sql_server.contains("CREATE TABLE [sales].[my.items] ("));
+
+        // 스키마를 지원하지 않으면 스키마만 뺌
+        let sqlite = result.diagram.to_sql(SqlDialect::Sqlite);
+        assert!(sqlite.contains("CREATE TABLE \"my.items\" ("));
+        assert!(!sqlite.contains("\"sales\""));
+    }
+
+    // 예약어 테이블, 자동 증가, 복합 PK, 단일/복합 FK
+    const SHOP: &str = "CREATE TABLE \"user\" (id INT NOT NULL AUTO_INCREMENT PRIMARY KEY, name VARCHAR(20) NOT NULL DEFAULT 'x',
+            flag BOOLEAN, at DATETIME, memo TEXT, data BLOB, price DECIMAL(10,2));
+        CREATE TABLE line (order_id BIGINT NOT NULL, line_no INT NOT NULL, user_id INT, PRIMARY KEY (order_id, line_no),
+            FOREIGN KEY (user_id) REFERENCES \"user\" (id));
+        CREATE TABLE ship (id INT PRIMARY KEY, order_id BIGINT, line_no INT,
+            FOREIGN KEY (order_id, line_no) REFERENCES line (order_id, line_no));";
+
+    fn script(dialect: SqlDialect) -> String {
+        let result = parse_sql(SHOP);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        create_script(&result.diagram, dialect.dialect())
+    }
+
+    #[test]
+    fn identifiers_are_quoted_per_dialect() {
+        assert_eq!(PostgreSql.quote_ident("a\"b"), "\"a\"\"b\"");
+        assert_eq!(MySql.quote_ident("a`b"), "`a``b`");
+        assert_eq!(Sqlite.quote_ident("a\"b"), "\"a\"\"b\"");
+        assert_eq!(SqlServer.quote_ident("a]b"), "[a]]b]");
+
+        assert!(script(SqlDialect::PostgreSql).contains("CREATE TABLE \"user\" (\n    \"id\" "));
+        assert!(script(SqlDialect::MySql).contains("CREATE TABLE `user` (\n    `id` "));
+        assert!(script(SqlDialect::Sqlite).contains("CREATE TABLE \"user\" (\n    \"id\" "));
+        assert!(script(SqlDialect::SqlServer).contains("CREATE TABLE [user] (\n    [id] "));
+    }
+
+    #[test]
+    fn types_are_mapped_per_dialect() {
+        let postgres = script(SqlDialect::PostgreSql);
+        assert!(postgres.contains("\"at\" TIMESTAMP,"));
+        assert!(postgres.contains("\"data\" BYTEA,"));
+        assert!(postgres.contains("\"name\" VARCHAR(20) NOT NULL DEFAULT 'x',"));
+        assert!(postgres.contains("\"price\" DECIMAL(10,2)"));
+
+        let sql_server = script(SqlDialect::SqlServer);
+        assert!(sql_server.contains("[flag] BIT,"));
+        assert!(sql_server.contains("[at] DATETIME2,"));
+        assert!(sql_server.contains("[memo] NVARCHAR(MAX),"));
+        assert!(sql_server.contains("[data] VARBINARY(MAX),"));
+
+        // 모르는 타입이나 그대로 쓰는 타입은 변환하지 않음
+        assert!(script(SqlDialect::MySql).contains("`at` DATETIME,"));
+        assert!(script(SqlDialect::Sqlite).contains("\"flag\" BOOLEAN,"));
+    }
+
+    #[test]
+    fn auto_increment_per_dialect() {
+        assert!(script(SqlDialect::PostgreSql).contains("\"id\" INT GENERATED BY DEFAULT AS IDENTITY NOT NULL,"));
+        assert!(script(SqlDialect::MySql).contains("`id` INT AUTO_INCREMENT NOT NULL,"));
+        assert!(script(SqlDialect::SqlServer).contains("[id] INT IDENTITY(1,1) NOT NULL,"));
+
+        // SQLite는 PK를 컬럼 정의에 넣고 테이블 PK 제약조건은 생략
+        let sqlite = script(SqlDialect::Sqlite);
+        assert!(sqlite.contains("\"id\" INTEGER PRIMARY KEY AUTOINCREMENT"));
+        assert!(!sqlite.contains("\"pk_user\""));
+    }
+
+    #[test]
+    fn composite_primary_keys() {
+        assert!(script(SqlDialect::PostgreSql).contains("CONSTRAINT \"pk_line\" PRIMARY KEY (\"order_id\", \"line_no\")"));
+        assert!(script(SqlDialect::MySql).contains("CONSTRAINT `pk_line` PRIMARY KEY (`order_id`, `line_no`)"));
+        assert!(script(SqlDialect::Sqlite).contains("CONSTRAINT \"pk_line\" PRIMARY KEY (\"order_id\", \"line_no\")"));
+        assert!(script(SqlDialect::SqlServer).contains("CONSTRAINT [pk_line] PRIMARY KEY ([order_id], [line_no])"));
+    }
+
+    // SQLite는 ALTER TABLE로 FK를 추가할 수 없어 CREATE TABLE 안에 넣음
+    #[test]
+    fn foreign_key_constraints() {
+        let postgres = script(SqlDialect::PostgreSql);
+        assert!(postgres.contains(
+            "ALTER TABLE \"ship\" ADD CONSTRAINT \"fk_ship_order_id_line_no\" FOREIGN KEY (\"order_id\", \"line_no\") REFERENCES \"line\" (\"order_id\", \"line_no\");"
+        ));
+        assert!(postgres.contains("FOREIGN KEY (\"user_id\") REFERENCES \"user\" (\"id\");"));
+        assert!(script(SqlDialect::MySql).contains("FOREIGN KEY (`order_id`, `line_no`) REFERENCES `line` (`order_id`, `line_no`);"));
+        assert!(script(SqlDialect::SqlServer).contains("FOREIGN KEY ([user_id]) REFERENCES [user] ([id]);"));
+
+        let sqlite = script(SqlDialect::Sqlite);
+        assert!(!sqlite.contains("ALTER TABLE"));
+        assert!(sqlite.contains(
+            "    CONSTRAINT \"fk_ship_order_id_line_no\" FOREIGN KEY (\"order_id\", \"line_no\") REFERENCES \"line\" (\"order_id\", \"line_no\")\n);"
+        ));
+    }
+}
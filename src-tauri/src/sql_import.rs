@@ -0,0 +1,1206 @@
+use crate::erd::{
+    Attribute, Cardinality, CheckConstraint, ColumnPair, DomainType, Entity, EntityKind, EnumType, ErdDiagram, Index, IndexColumn,
+    IndexMethod, Multiplicity, Relation, SortOrder, UniqueConstraint, UserType,
+};
+use crate::sql::{self, ForeignKey};
+use crate::views;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlImportResult {
+    pub diagram: ErdDiagram,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),   // 키워드, 비인용 식별자, 숫자
+    Quoted(String), // "ident", `ident`, [ident]
+    Str(String),    // 'literal'
+    Symbol(String),
+}
+
+impl Token {
+    fn is_kw(&self, kw: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self, Token::Symbol(s) if s == symbol)
+    }
+
+    fn ident(&self) -> Option<&str> {
+        match self {
+            Token::Word(w) | Token::Quoted(w) => Some(w),
+            _ => None,
+        }
+    }
+}
+
+// CREATE TABLE / CREATE VIEW / CREATE INDEX / CREATE TYPE / CREATE DOMAIN / ALTER TABLE ADD CONSTRAINT 구문을 다이어그램으로 변환
+pub fn parse_sql(script: &str) -> SqlImportResult {
+    let mut diagram = ErdDiagram::default();
+    let mut warnings = Vec::new();
+    let mut foreign_keys = Vec::new();
+    let chars: Vec<char> = script.chars().collect();
+    let mut view_columns = Vec::new();
+
+    for spanned in split_statements(tokenize(script)) {
+        let statement: Vec<Token> = spanned.iter().map(|(t, _)| t.clone()).collect();
+        let result = if starts_with(&statement, &["CREATE", "TABLE"])
+            || starts_with(&statement, &["CREATE", "TEMP", "TABLE"])
+            || starts_with(&statement, &["CREATE", "TEMPORARY", "TABLE"])
+            || starts_with(&statement, &["CREATE", "UNLOGGED", "TABLE"])
+        {
+            parse_create_table(&statement, &mut diagram, &mut foreign_keys, &mut warnings)
+        } else if starts_with(&statement, &["ALTER", "TABLE"]) {
+            parse_alter_table(&statement, &mut diagram, &mut foreign_keys, &mut warnings)
+        } else if is_create_index(&statement) {
+            parse_create_index(&statement, &mut diagram, &mut warnings)
+        } else if starts_with(&statement, &["CREATE", "TYPE"]) {
+            parse_create_type(&statement, &mut diagram)
+        } else if starts_with(&statement, &["CREATE", "DOMAIN"]) {
+            parse_create_domain(&statement, &mut diagram, &mut warnings)
+        } else if let Some(view) = view_keyword(&statement) {
+            parse_create_view(&spanned, view, &chars, &mut diagram, &mut view_columns)
+        } else {
+            Err(format!("지원하지 않는 구문을 건너뜀: {}", preview(&statement)))
+        };
+        if let Err(warning) = result {
+            warnings.push(warning);
+        }
+    }
+
+    apply_foreign_keys(&mut diagram, foreign_keys, &mut warnings);
+    // 뷰는 모든 테이블을 읽은 뒤 참조와 컬럼을 정의에서 도출 (컬럼 목록이 있으면 그 이름을 씀)
+    for (id, names) in view_columns {
+        warnings.extend(views::refresh_entity(&mut diagram, &id, true, &names));
+    }
+    diagram.arrange_grid();
+    diagram.normalize();
+
+    SqlImportResult { diagram, warnings }
+}
+
+// 선언된 FK를 속성 플래그와 Relation으로 반영 (참조 대상이 뒤에 정의돼도 되도록 마지막에 처리)
+pub fn apply_foreign_keys(diagram: &mut ErdDiagram, foreign_keys: Vec<ForeignKey>, warnings: &mut Vec<String>) {
+    for fk in foreign_keys {
+        let Some(child_id) = diagram.entity_id_by_physical_name(&fk.table) else {
+            warnings.push(format!("FK '{}': 테이블 '{}'을(를) 찾을 수 없음", fk.name, fk.table));
+            continue;
+        };
+        let Some(parent_id) = diagram.entity_id_by_physical_name(&fk.referenced_table) else {
+            warnings.push(format!("FK '{}': 참조 테이블 '{}'을(를) 찾을 수 없음", fk.name, fk.referenced_table));
+            continue;
+        };
+
+        // 참조 컬럼을 생략하면 부모의 PK를 참조
+        let mut referenced_columns = fk.referenced_columns.clone();
+        if referenced_columns.is_empty() {
+            referenced_columns = diagram.entities[&parent_id]
+                .attributes
+                .iter()
+                .filter(|a| a.is_primary_key)
+                .map(|a| a.physical_name.clone())
+                .collect();
+        }
+        if fk.columns.is_empty() || referenced_columns.len() != fk.columns.len() {
+            warnings.push(format!("FK '{}': 컬럼 수가 참조 컬럼 수와 다름", fk.name));
+            continue;
+        }
+
+        let parent_table = diagram.entities[&parent_id].physical_name.clone();
+        let mut one_to_one = false;
+        let mut optional_parent = false;
+        let mut identifying = false;
+        if let Some(child) = diagram.entities.get_mut(&child_id) {
+            for (column, referenced) in fk.columns.iter().zip(&referenced_columns) {
+                match child.attributes.iter_mut().find(|a| a.physical_name.eq_ignore_ascii_case(column)) {
+                    Some(attr) => {
+                        attr.is_foreign_key = true;
+                        attr.foreign_key_reference = Some(format!("{}.{}", parent_table, referenced));
+                    }
+                    None => warnings.push(format!("FK '{}': 컬럼 '{}.{}'을(를) 찾을 수 없음", fk.name, fk.table, column)),
+                }
+            }
+            // FK 컬럼이 자식의 PK 전체이거나 단일 UNIQUE 컬럼이면 1:1
+            let is_fk_column = |a: &Attribute| fk.columns.iter().any(|c| a.physical_name.eq_ignore_ascii_case(c));
+            let primary_keys: Vec<&Attribute> = child.attributes.iter().filter(|a| a.is_primary_key).collect();
+            let unique_column = fk.columns.len() == 1
+                && child.attributes.iter().any(|a| is_fk_column(a) && a.is_unique);
+            one_to_one = unique_column
+                || (primary_keys.len() == fk.columns.len() && primary_keys.iter().all(|a| is_fk_column(a)));
+            // NULL 가능한 FK는 부모가 없어도 되고, FK가 모두 PK에 포함되면 식별 관계
+            optional_parent = child.attributes.iter().any(|a| is_fk_column(a) && a.is_nullable && !a.is_primary_key);
+            identifying = fk
+                .columns
+                .iter()
+                .all(|c| child.attributes.iter().any(|a| a.is_primary_key && a.physical_name.eq_ignore_ascii_case(c)));
+        }
+
+        let mut relation = Relation {
+            id: format!("relation_{}", fk.name),
+            from_entity_id: parent_id,
+            from_attribute: String::new(),
+            to_entity_id: child_id,
+            to_attribute: None,
+            columns: Vec::new(),
+            cardinality: if one_to_one { Cardinality::OneToOne } else { Cardinality::OneToMany },
+            name: fk.name,
+            from_multiplicity: Some(if optional_parent { Multiplicity::ZERO_OR_ONE } else { Multiplicity::EXACTLY_ONE }),
+            to_multiplicity: Some(if one_to_one { Multiplicity::ZERO_OR_ONE } else { Multiplicity::ZERO_OR_MANY }),
+            is_identifying: identifying,
+        };
+        relation.set_pairs(
+            referenced_columns
+                .into_iter()
+                .zip(fk.columns)
+                .map(|(from, to)| ColumnPair::new(from, Some(to)))
+                .collect(),
+        );
+        diagram.relations.push(relation);
+    }
+}
+
+fn parse_create_table(
+    tokens: &[Token],
+    diagram: &mut ErdDiagram,
+    foreign_keys: &mut Vec<ForeignKey>,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    let mut pos = tokens.iter().position(|t| t.is_kw("TABLE")).unwrap_or(0) + 1;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("IF")) {
+        pos += 3; // IF NOT EXISTS
+    }
+    let (table, next) = table_name(tokens, pos).ok_or_else(|| format!("테이블 이름을 읽을 수 없음: {}", preview(tokens)))?;
+    pos = next;
+
+    if !tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+        return Err(format!("컬럼 정의가 없는 CREATE TABLE은 지원하지 않음: {}", table));
+    }
+    let (body, _) = paren_group(tokens, pos);
+
+    if diagram.entities.values().any(|e| e.qualified_name().eq_ignore_ascii_case(&table)) {
+        return Err(format!("테이블 '{}'이(가) 중복 정의되어 건너뜀", table));
+    }
+    let (schema, name) = sql::split_table(&table);
+    let mut entity = Entity::new(&format!("entity_{}", table), name);
+    entity.schema = schema.map(str::to_string);
+
+    for item in split_commas(&body) {
+        let Some(first) = item.first() else { continue };
+        let is_constraint = ["CONSTRAINT", "PRIMARY", "UNIQUE", "FOREIGN", "CHECK", "KEY", "INDEX", "FULLTEXT", "SPATIAL", "EXCLUDE", "LIKE"]
+            .iter()
+            .any(|kw| first.is_kw(kw));
+        if is_constraint {
+            if let Err(warning) = apply_table_constraint(&item, &mut entity, foreign_keys) {
+                warnings.push(format!("{}: {}", table, warning));
+            }
+        } else {
+            entity.attributes.push(parse_column(&item, &table, foreign_keys, warnings)?);
+        }
+    }
+
+    diagram.entities.insert(entity.id.clone(), entity);
+    Ok(())
+}
+
+// CREATE [OR REPLACE | OR ALTER] [MATERIALIZED] VIEW의 VIEW 위치
+// (MySQL의 ALGORITHM=, DEFINER=, SQL SECURITY 같은 옵션은 건너뜀)
+fn view_keyword(tokens: &[Token]) -> Option<usize> {
+    if !tokens.first().is_some_and(|t| t.is_kw("CREATE")) {
+        return None;
+    }
+    let view = tokens.iter().take(16).position(|t| t.is_kw("VIEW"))?;
+    let other = tokens[..view].iter().any(|t| t.is_kw("TABLE") || t.is_kw("INDEX") || t.is_kw("AS"));
+    (!other).then_some(view)
+}
+
+// 뷰 정의는 원문 그대로 보관 (인용 부호나 줄바꿈 유지). 컬럼 목록은 정의에서 도출한 뒤 이름만 덮어씀
+fn parse_create_view(
+    spanned: &[(Token, Range<usize>)],
+    view: usize,
+    chars: &[char],
+    diagram: &mut ErdDiagram,
+    view_columns: &mut Vec<(String, Vec<String>)>,
+) -> Result<(), String> {
+    let tokens: Vec<Token> = spanned.iter().map(|(t, _)| t.clone()).collect();
+    let materialized = tokens[..view].iter().any(|t| t.is_kw("MATERIALIZED"));
+    let mut pos = view + 1;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("IF")) {
+        pos += 3; // IF NOT EXISTS
+    }
+    let (name, next) = table_name(&tokens, pos).ok_or_else(|| format!("뷰 이름을 읽을 수 없음: {}", preview(&tokens)))?;
+    pos = next;
+
+    let mut columns = Vec::new();
+    if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+        let (group, next) = paren_group(&tokens, pos);
+        columns = column_list(&group);
+        pos = next;
+    }
+    // PostgreSQL의 WITH (옵션)
+    if tokens.get(pos).is_some_and(|t| t.is_kw("WITH")) && tokens.get(pos + 1).is_some_and(|t| t.is_symbol("(")) {
+        pos = paren_group(&tokens, pos + 1).1;
+    }
+    if !tokens.get(pos).is_some_and(|t| t.is_kw("AS")) {
+        return Err(format!("뷰 '{}'의 정의(AS SELECT ...)를 찾을 수 없음", name));
+    }
+    pos += 1;
+
+    // 끝의 WITH [CASCADED | LOCAL] CHECK OPTION, WITH [NO] DATA는 정의에서 뺌
+    let mut end = tokens.len();
+    if let Some(with) = tokens.iter().rposition(|t| t.is_kw("WITH")).filter(|&i| i > pos) {
+        let tail = &tokens[with + 1..];
+        let is_option = tail.iter().all(|t| ["CASCADED", "LOCAL", "CHECK", "OPTION", "NO", "DATA"].iter().any(|kw| t.is_kw(kw)));
+        if is_option && !tail.is_empty() {
+            end = with;
+        }
+    }
+    if pos >= end {
+        return Err(format!("뷰 '{}'의 정의가 비어 있음", name));
+    }
+    let definition: String = chars[spanned[pos].1.start..spanned[end - 1].1.end].iter().collect();
+
+    if diagram.entities.values().any(|e| e.qualified_name().eq_ignore_ascii_case(&name)) {
+        return Err(format!("'{}'이(가) 중복 정의되어 건너뜀", name));
+    }
+    let (schema, physical_name) = sql::split_table(&name);
+    let mut entity = Entity::new(&format!("entity_{}", name), physical_name);
+    entity.schema = schema.map(str::to_string);
+    entity.kind = if materialized { EntityKind::MaterializedView } else { EntityKind::View };
+    entity.view_definition = Some(definition);
+    view_columns.push((entity.id.clone(), columns));
+    diagram.entities.insert(entity.id.clone(), entity);
+    Ok(())
+}
+
+// 뷰 SELECT 문에서 읽은 참조 테이블과 출력 컬럼
+#[derive(Debug, Clone, Default)]
+pub struct ViewQuery {
+    pub tables: Vec<ViewTable>, // FROM/JOIN 대상 (WITH 절 이름 제외)
+    pub columns: Vec<SelectColumn>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewTable {
+    pub name: String, // "스키마.테이블" 가능
+    pub alias: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum SelectColumn {
+    All(Option<String>), // * 또는 테이블.*
+    Column { name: String, table: Option<String>, column: Option<String> }, // 출력 이름, 단순 컬럼 참조면 원본
+    Unnamed(String),     // 별칭 없는 식
+}
+
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "WHERE", "JOIN", "INNER", "LEFT", "RIGHT", "FULL", "CROSS", "OUTER", "NATURAL", "ON", "USING", "GROUP", "ORDER", "HAVING",
+    "LIMIT", "OFFSET", "FETCH", "UNION", "EXCEPT", "INTERSECT", "WINDOW", "LATERAL", "END", "AND", "OR", "NOT", "NULL", "FROM",
+];
+
+pub fn parse_view_query(definition: &str) -> ViewQuery {
+    let tokens: Vec<Token> = tokenize(definition).into_iter().map(|(t, _)| t).filter(|t| !t.is_symbol(";")).collect();
+    let mut query = ViewQuery::default();
+
+    // WITH 절 이름은 테이블이 아님
+    let mut ctes: Vec<String> = Vec::new();
+    let mut pos = 0;
+    if tokens.first().is_some_and(|t| t.is_kw("WITH")) {
+        pos = 1;
+        if tokens.get(pos).is_some_and(|t| t.is_kw("RECURSIVE")) {
+            pos += 1;
+        }
+        while let Some(name) = tokens.get(pos).and_then(|t| t.ident()) {
+            ctes.push(name.to_lowercase());
+            pos += 1;
+            while pos < tokens.len() && !tokens[pos].is_symbol("(") {
+                pos += 1;
+            }
+            // (컬럼 목록) AS [NOT] [MATERIALIZED] (쿼리)
+            pos = paren_group(&tokens, pos).1;
+            while pos < tokens.len() && !tokens[pos].is_symbol("(") && !tokens[pos].is_symbol(",") && !tokens[pos].is_kw("SELECT") {
+                pos += 1;
+            }
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                pos = paren_group(&tokens, pos).1;
+            }
+            if !tokens.get(pos).is_some_and(|t| t.is_symbol(",")) {
+                break;
+            }
+            pos += 1;
+        }
+    }
+
+    // 첫 SELECT의 컬럼 목록 (괄호 밖의 FROM까지)
+    if let Some(select) = (pos..tokens.len()).find(|&i| tokens[i].is_kw("SELECT")) {
+        let mut start = select + 1;
+        while tokens.get(start).is_some_and(|t| t.is_kw("DISTINCT") || t.is_kw("ALL")) {
+            start += 1;
+            if tokens.get(start).is_some_and(|t| t.is_kw("ON")) && tokens.get(start + 1).is_some_and(|t| t.is_symbol("(")) {
+                start = paren_group(&tokens, start + 1).1;
+            }
+        }
+        if tokens.get(start).is_some_and(|t| t.is_kw("TOP")) {
+            start += 2;
+        }
+        let mut depth = 0;
+        let mut end = tokens.len();
+        for (i, token) in tokens.iter().enumerate().skip(start) {
+            if token.is_symbol("(") { depth += 1; }
+            if token.is_symbol(")") { depth -= 1; }
+            if depth == 0 && (token.is_kw("FROM") || token.is_kw("UNION")) {
+                end = i;
+                break;
+            }
+        }
+        query.columns = split_commas(&tokens[start.min(end)..end]).iter().map(|item| select_column(item)).collect();
+    }
+
+    // FROM/JOIN 뒤의 테이블 (하위 쿼리 안 포함, EXTRACT(x FROM y) 같은 함수 인자는 제외)
+    let mut subquery: Vec<bool> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_symbol("(") {
+            subquery.push(tokens.get(i + 1).is_some_and(|t| t.is_kw("SELECT") || t.is_kw("WITH")));
+        } else if token.is_symbol(")") {
+            subquery.pop();
+        } else if (token.is_kw("FROM") || token.is_kw("JOIN")) && subquery.last().copied().unwrap_or(true) {
+            let mut pos = i + 1;
+            loop {
+                while tokens.get(pos).is_some_and(|t| t.is_kw("ONLY") || t.is_kw("LATERAL")) {
+                    pos += 1;
+                }
+                if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                    pos = paren_group(&tokens, pos).1;
+                } else if let Some((name, next)) = table_name(&tokens, pos) {
+                    pos = next;
+                    let (alias, next) = table_alias(&tokens, pos);
+                    pos = next;
+                    if !ctes.contains(&sql::split_table(&name).1.to_lowercase()) {
+                        query.tables.push(ViewTable { name, alias });
+                    }
+                } else {
+                    break;
+                }
+                if token.is_kw("JOIN") || !tokens.get(pos).is_some_and(|t| t.is_symbol(",")) {
+                    break;
+                }
+                pos += 1;
+            }
+        }
+    }
+    query
+}
+
+// [AS] 별칭 (절 키워드면 별칭 아님)
+fn table_alias(tokens: &[Token], mut pos: usize) -> (Option<String>, usize) {
+    if tokens.get(pos).is_some_and(|t| t.is_kw("AS")) {
+        pos += 1;
+    }
+    match tokens.get(pos) {
+        Some(Token::Quoted(q)) => (Some(q.clone()), pos + 1),
+        Some(Token::Word(w)) if !CLAUSE_KEYWORDS.iter().any(|kw| w.eq_ignore_ascii_case(kw)) => (Some(w.clone()), pos + 1),
+        _ => (None, pos),
+    }
+}
+
+fn select_column(item: &[Token]) -> SelectColumn {
+    match item {
+        [t] if t.is_symbol("*") => return SelectColumn::All(None),
+        [.., table, dot, star] if dot.is_symbol(".") && star.is_symbol("*") => return SelectColumn::All(table.ident().map(str::to_string)),
+        _ => {}
+    }
+
+    // "식 AS 별칭" 또는 "식 별칭"
+    let (expression, alias) = match item {
+        [expression @ .., as_kw, alias] if as_kw.is_kw("AS") && alias.ident().is_some() => (expression, alias.ident()),
+        [expression @ .., previous, alias]
+            if alias.ident().is_some()
+                && !matches!(alias, Token::Word(w) if CLAUSE_KEYWORDS.iter().any(|kw| w.eq_ignore_ascii_case(kw)))
+                && (previous.ident().is_some() || previous.is_symbol(")") || matches!(previous, Token::Str(_))) =>
+        {
+            (&item[..expression.len() + 1], alias.ident())
+        }
+        _ => (item, None),
+    };
+    let reference = match expression {
+        [column] => column.ident().map(|c| (None, c.to_string())),
+        [.., table, dot, column] if dot.is_symbol(".") => match (table.ident(), column.ident()) {
+            (Some(table), Some(column)) if expression.len() == 3 || expression[expression.len() - 4].is_symbol(".") => {
+                Some((Some(table.to_string()), column.to_string()))
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    match (alias, reference) {
+        (alias, Some((table, column))) => SelectColumn::Column {
+            name: alias.map(str::to_string).unwrap_or_else(|| column.clone()),
+            table,
+            column: Some(column),
+        },
+        (Some(alias), None) => SelectColumn::Column { name: alias.to_string(), table: None, column: None },
+        (None, None) => SelectColumn::Unnamed(render(expression)),
+    }
+}
+
+fn parse_alter_table(
+    tokens: &[Token],
+    diagram: &mut ErdDiagram,
+    foreign_keys: &mut Vec<ForeignKey>,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    let mut pos = 2;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("IF")) {
+        pos += 2; // IF EXISTS
+    }
+    if tokens.get(pos).is_some_and(|t| t.is_kw("ONLY")) {
+        pos += 1;
+    }
+    let (table, next) = table_name(tokens, pos).ok_or_else(|| format!("테이블 이름을 읽을 수 없음: {}", preview(tokens)))?;
+    let entity_id = diagram
+        .entity_id_by_physical_name(&table)
+        .ok_or_else(|| format!("ALTER TABLE 대상 '{}'이(가) 정의되지 않음", table))?;
+    let entity = diagram.entities.get_mut(&entity_id).unwrap();
+
+    for clause in split_commas(&tokens[next..]) {
+        let is_add_constraint = clause.first().is_some_and(|t| t.is_kw("ADD"))
+            && clause.get(1).is_some_and(|t| ["CONSTRAINT", "PRIMARY", "UNIQUE", "FOREIGN", "CHECK"].iter().any(|kw| t.is_kw(kw)));
+        if !is_add_constraint {
+            warnings.push(format!("지원하지 않는 ALTER TABLE 절을 건너뜀: {}", preview(tokens)));
+            continue;
+        }
+        if let Err(warning) = apply_table_constraint(&clause[1..], entity, foreign_keys) {
+            warnings.push(format!("{}: {}", table, warning));
+        }
+    }
+    Ok(())
+}
+
+// CREATE TYPE name AS ENUM ('a', 'b')
+fn parse_create_type(tokens: &[Token], diagram: &mut ErdDiagram) -> Result<(), String> {
+    let (name, pos) = qualified_name(tokens, 2).ok_or_else(|| format!("타입 이름을 읽을 수 없음: {}", preview(tokens)))?;
+    let is_enum = tokens.get(pos).is_some_and(|t| t.is_kw("AS"))
+        && tokens.get(pos + 1).is_some_and(|t| t.is_kw("ENUM"))
+        && tokens.get(pos + 2).is_some_and(|t| t.is_symbol("("));
+    if !is_enum {
+        return Err(format!("열거형이 아닌 타입 '{}'은(는) 지원하지 않아 건너뜀", name));
+    }
+    let values = split_commas(&paren_group(tokens, pos + 2).0)
+        .iter()
+        .filter_map(|item| match item.as_slice() {
+            [Token::Str(value)] => Some(value.clone()),
+            _ => None,
+        })
+        .collect();
+    add_user_type(diagram, UserType::Enum(EnumType { name, values }))
+}
+
+// CREATE DOMAIN name [AS] type [DEFAULT ...] [CONSTRAINT n] [CHECK (...)] (컬럼 정의와 같은 문법)
+fn parse_create_domain(tokens: &[Token], diagram: &mut ErdDiagram, warnings: &mut Vec<String>) -> Result<(), String> {
+    let (name, mut pos) = qualified_name(tokens, 2).ok_or_else(|| format!("도메인 이름을 읽을 수 없음: {}", preview(tokens)))?;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("AS")) {
+        pos += 1;
+    }
+    let mut definition = vec![Token::Word(name.clone())];
+    definition.extend_from_slice(&tokens[pos..]);
+    let attr = parse_column(&definition, &name, &mut Vec::new(), warnings)?;
+    add_user_type(
+        diagram,
+        UserType::Domain(DomainType {
+            name,
+            base_type: attr.data_type,
+            length: attr.length,
+            default_value: attr.default_value,
+            check: attr.check,
+        }),
+    )
+}
+
+fn add_user_type(diagram: &mut ErdDiagram, user_type: UserType) -> Result<(), String> {
+    if diagram.user_type(user_type.name()).is_some() {
+        return Err(format!("타입 '{}'이(가) 중복 정의되어 건너뜀", user_type.name()));
+    }
+    diagram.user_types.push(user_type);
+    Ok(())
+}
+
+// CREATE [UNIQUE] [CLUSTERED | NONCLUSTERED] INDEX ...
+fn is_create_index(tokens: &[Token]) -> bool {
+    tokens.first().is_some_and(|t| t.is_kw("CREATE"))
+        && tokens[1..]
+            .iter()
+            .find(|t| !["UNIQUE", "CLUSTERED", "NONCLUSTERED"].iter().any(|kw| t.is_kw(kw)))
+            .is_some_and(|t| t.is_kw("INDEX"))
+}
+
+// CREATE [UNIQUE] INDEX [IF NOT EXISTS] name [USING m] ON table [USING m] (cols) [USING m] [WHERE ...]
+fn parse_create_index(tokens: &[Token], diagram: &mut ErdDiagram, warnings: &mut Vec<String>) -> Result<(), String> {
+    let is_unique = tokens.iter().take_while(|t| !t.is_kw("INDEX")).any(|t| t.is_kw("UNIQUE"));
+    let mut pos = tokens.iter().position(|t| t.is_kw("INDEX")).unwrap_or(0) + 1;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("CONCURRENTLY")) {
+        pos += 1;
+    }
+    if tokens.get(pos).is_some_and(|t| t.is_kw("IF")) {
+        pos += 3; // IF NOT EXISTS
+    }
+    let (name, _) = qualified_name(tokens, pos).ok_or_else(|| format!("인덱스 이름을 읽을 수 없음: {}", preview(tokens)))?;
+
+    let on = tokens.iter().position(|t| t.is_kw("ON")).ok_or_else(|| format!("인덱스 '{}'의 테이블을 읽을 수 없음", name))?;
+    pos = on + 1;
+    if tokens.get(pos).is_some_and(|t| t.is_kw("ONLY")) {
+        pos += 1;
+    }
+    let (table, mut pos) = table_name(tokens, pos).ok_or_else(|| format!("인덱스 '{}'의 테이블을 읽을 수 없음", name))?;
+    let entity_id = diagram
+        .entity_id_by_physical_name(&table)
+        .ok_or_else(|| format!("인덱스 '{}'의 테이블 '{}'이(가) 정의되지 않음", name, table))?;
+
+    let mut method = IndexMethod::BTree;
+    let mut predicate = None;
+    let mut columns = Vec::new();
+    while let Some(token) = tokens.get(pos) {
+        if token.is_kw("USING") {
+            method = match tokens.get(pos + 1).and_then(|t| t.ident()).map(str::to_lowercase).as_deref() {
+                Some("btree") => IndexMethod::BTree,
+                Some("hash") => IndexMethod::Hash,
+                Some("gin") => IndexMethod::Gin,
+                other => {
+                    warnings.push(format!("인덱스 '{}': 지원하지 않는 방식 '{}'을(를) btree로 가져옴", name, other.unwrap_or("")));
+                    IndexMethod::BTree
+                }
+            };
+            pos += 2;
+        } else if token.is_symbol("(") && columns.is_empty() {
+            let (body, next) = paren_group(tokens, pos);
+            columns = index_columns(&body);
+            pos = next;
+        } else if token.is_kw("WHERE") {
+            predicate = Some(render(&tokens[pos + 1..]));
+            break;
+        } else if token.is_symbol("(") {
+            pos = paren_group(tokens, pos).1; // INCLUDE (...), WITH (...)
+        } else {
+            pos += 1;
+        }
+    }
+    // USING이 테이블 이름 앞에 오는 MySQL 형식
+    if let Some(using) = tokens[..on].iter().position(|t| t.is_kw("USING")) {
+        if tokens.get(using + 1).is_some_and(|t| t.is_kw("HASH")) {
+            method = IndexMethod::Hash;
+        }
+    }
+    if columns.is_empty() {
+        return Err(format!("인덱스 '{}'의 컬럼을 읽을 수 없음", name));
+    }
+
+    let entity = diagram.entities.get_mut(&entity_id).unwrap();
+    entity.indexes.retain(|i| !i.name.eq_ignore_ascii_case(&name));
+    entity.indexes.push(Index { name, columns, is_unique, predicate, method });
+    Ok(())
+}
+
+// 식 인덱스는 식 전체를 컬럼 이름으로 둠
+fn index_columns(tokens: &[Token]) -> Vec<IndexColumn> {
+    split_commas(tokens)
+        .iter()
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let order = if item.last().is_some_and(|t| t.is_kw("DESC")) { SortOrder::Desc } else { SortOrder::Asc };
+            let end = item.iter().position(|t| t.is_kw("ASC") || t.is_kw("DESC") || t.is_kw("NULLS")).unwrap_or(item.len());
+            let name = match &item[..end] {
+                [single] => single.ident().map(str::to_string).unwrap_or_else(|| render(&item[..end])),
+                expression => render(expression),
+            };
+            IndexColumn { name, order }
+        })
+        .collect()
+}
+
+fn parse_column(
+    tokens: &[Token],
+    table: &str,
+    foreign_keys: &mut Vec<ForeignKey>,
+    warnings: &mut Vec<String>,
+) -> Result<Attribute, String> {
+    let name = tokens[0].ident().ok_or_else(|| format!("{}: 컬럼 이름을 읽을 수 없음", table))?;
+
+    // 타입: 첫 제약 키워드가 나올 때까지의 단어들 + 괄호 안 길이
+    let mut type_words: Vec<String> = Vec::new();
+    let mut length = None;
+    let mut pos = 1;
+    while let Some(token) = tokens.get(pos) {
+        // CHARACTER VARYING은 타입, CHARACTER SET은 옵션
+        if is_column_option(token) || (token.is_kw("CHARACTER") && tokens.get(pos + 1).is_some_and(|t| t.is_kw("SET"))) {
+            break;
+        }
+        if token.is_symbol("(") {
+            let (inner, next) = paren_group(tokens, pos);
+            length = Some(render(&inner).replace(' ', ""));
+            pos = next;
+            continue;
+        }
+        // 배열 차원 ([], [3])은 앞 단어에 붙임 (VARCHAR(10)[]처럼 길이가 앞에 있으면 길이까지 타입에 넣음)
+        if token.is_symbol("[") {
+            let close = tokens[pos..].iter().position(|t| t.is_symbol("]")).map_or(tokens.len(), |p| pos + p + 1);
+            let mut suffix = render(&tokens[pos..close]).replace(' ', "");
+            if let Some(length) = length.take() {
+                suffix = format!("({}){}", length, suffix);
+            }
+            match type_words.last_mut() {
+                Some(last) => last.push_str(&suffix),
+                None => type_words.push(suffix),
+            }
+            pos = close;
+            continue;
+        }
+        match token {
+            Token::Word(w) | Token::Quoted(w) => type_words.push(w.clone()),
+            Token::Symbol(s) => type_words.push(s.clone()),
+            Token::Str(_) => break,
+        }
+        pos += 1;
+    }
+
+    let mut attr = Attribute::new(name, &type_words.join(" ").to_uppercase());
+    attr.length = length;
+    match attr.data_type.as_str() {
+        "SERIAL" | "SERIAL4" => { attr.data_type = "INT".to_string(); attr.is_auto_increment = true; }
+        "BIGSERIAL" | "SERIAL8" => { attr.data_type = "BIGINT".to_string(); attr.is_auto_increment = true; }
+        "SMALLSERIAL" | "SERIAL2" => { attr.data_type = "SMALLINT".to_string(); attr.is_auto_increment = true; }
+        _ => {}
+    }
+
+    let mut constraint_name = None;
+    while let Some(token) = tokens.get(pos) {
+        pos += 1;
+        if token.is_kw("NOT") && tokens.get(pos).is_some_and(|t| t.is_kw("NULL")) {
+            attr.is_nullable = false;
+            pos += 1;
+        } else if token.is_kw("NULL") {
+            attr.is_nullable = true;
+        } else if token.is_kw("PRIMARY") {
+            attr.is_primary_key = true;
+            attr.is_nullable = false;
+            pos += 1; // KEY
+        } else if token.is_kw("UNIQUE") {
+            attr.is_unique = true;
+            if tokens.get(pos).is_some_and(|t| t.is_kw("KEY")) {
+                pos += 1;
+            }
+        } else if token.is_kw("AUTO_INCREMENT") || token.is_kw("AUTOINCREMENT") {
+            attr.is_auto_increment = true;
+        } else if token.is_kw("IDENTITY") {
+            attr.is_auto_increment = true;
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                pos = paren_group(tokens, pos).1;
+            }
+        } else if token.is_kw("GENERATED") {
+            // GENERATED { ALWAYS | BY DEFAULT } AS { IDENTITY [(...)] | (expr) [STORED] }
+            while tokens.get(pos).is_some_and(|t| !t.is_kw("AS")) {
+                pos += 1;
+            }
+            pos += 1;
+            if tokens.get(pos).is_some_and(|t| t.is_kw("IDENTITY")) {
+                attr.is_auto_increment = true;
+                pos += 1;
+            }
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                pos = paren_group(tokens, pos).1;
+            }
+        } else if token.is_kw("DEFAULT") {
+            let start = pos;
+            let mut depth = 0;
+            while let Some(t) = tokens.get(pos) {
+                if depth == 0 && pos > start && is_column_option(t) {
+                    break;
+                }
+                if t.is_symbol("(") { depth += 1; }
+                if t.is_symbol(")") { depth -= 1; }
+                pos += 1;
+            }
+            attr.default_value = Some(render(&tokens[start..pos]));
+        } else if token.is_kw("REFERENCES") {
+            let (referenced_table, next) = table_name(tokens, pos).ok_or_else(|| format!("{}.{}: 참조 테이블을 읽을 수 없음", table, name))?;
+            pos = next;
+            let mut referenced_columns = Vec::new();
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                let (inner, next) = paren_group(tokens, pos);
+                referenced_columns = column_list(&inner);
+                pos = next;
+            }
+            foreign_keys.push(ForeignKey {
+                name: constraint_name.take().unwrap_or_else(|| format!("fk_{}_{}", sql::split_table(table).1, name)),
+                table: table.to_string(),
+                columns: vec![name.to_string()],
+                referenced_table,
+                referenced_columns,
+            });
+        } else if token.is_kw("ON") {
+            pos = skip_referential_action(tokens, pos);
+        } else if token.is_kw("CONSTRAINT") {
+            constraint_name = tokens.get(pos).and_then(|t| t.ident()).map(str::to_string);
+            pos += 1;
+        } else if token.is_kw("CHECK") {
+            // 컬럼 CHECK 이름은 DDL 생성 시 ck_테이블_컬럼으로 다시 정함
+            constraint_name = None;
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                let (inner, next) = paren_group(tokens, pos);
+                attr.check = Some(render(&inner));
+                pos = next;
+            }
+        } else if token.is_kw("COMMENT") {
+            if let Some(Token::Str(comment)) = tokens.get(pos) {
+                attr.remark = Some(comment.clone());
+                pos += 1;
+            }
+        } else if token.is_kw("COLLATE") {
+            pos += 1;
+        } else if token.is_kw("CHARACTER") && tokens.get(pos).is_some_and(|t| t.is_kw("SET")) {
+            pos += 2;
+        } else if token.is_kw("ASC") || token.is_kw("DESC") || token.is_kw("UNSIGNED") || token.is_kw("ZEROFILL") {
+            // 의미 없는 수식어
+        } else {
+            warnings.push(format!("{}.{}: 알 수 없는 컬럼 옵션 '{}' 무시", table, name, render(std::slice::from_ref(token))));
+        }
+    }
+
+    Ok(attr)
+}
+
+// [CONSTRAINT name] PRIMARY KEY (...) | UNIQUE (...) | FOREIGN KEY (...) REFERENCES t (...) | CHECK (...)
+fn apply_table_constraint(tokens: &[Token], entity: &mut Entity, foreign_keys: &mut Vec<ForeignKey>) -> Result<(), String> {
+    let mut pos = 0;
+    let mut constraint_name = None;
+    if tokens[0].is_kw("CONSTRAINT") {
+        constraint_name = tokens.get(1).and_then(|t| t.ident()).map(str::to_string);
+        pos = 2;
+    }
+    let kind = tokens.get(pos).ok_or("제약조건 종류가 없음")?;
+
+    // 다음 괄호 묶음의 컬럼 목록
+    let columns_after = |from: usize| -> Option<(Vec<String>, usize)> {
+        let open = (from..tokens.len()).find(|&i| tokens[i].is_symbol("("))?;
+        let (inner, next) = paren_group(tokens, open);
+        Some((column_list(&inner), next))
+    };
+
+    if kind.is_kw("PRIMARY") {
+        let (columns, _) = columns_after(pos).ok_or("PRIMARY KEY 컬럼 목록이 없음")?;
+        for column in &columns {
+            let attr = find_attribute(entity, column)?;
+            attr.is_primary_key = true;
+            attr.is_nullable = false;
+        }
+        Ok(())
+    } else if kind.is_kw("UNIQUE") {
+        let (columns, _) = columns_after(pos).ok_or("UNIQUE 컬럼 목록이 없음")?;
+        if let [column] = columns.as_slice() {
+            find_attribute(entity, column)?.is_unique = true;
+            return Ok(());
+        }
+        for column in &columns {
+            find_attribute(entity, column)?;
+        }
+        entity.unique_constraints.push(UniqueConstraint {
+            name: constraint_name.unwrap_or_else(|| format!("uq_{}_{}", entity.physical_name, columns.join("_"))),
+            columns,
+        });
+        Ok(())
+    } else if kind.is_kw("FOREIGN") {
+        let (columns, next) = columns_after(pos).ok_or("FOREIGN KEY 컬럼 목록이 없음")?;
+        let references = (next..tokens.len()).find(|&i| tokens[i].is_kw("REFERENCES")).ok_or("REFERENCES 절이 없음")?;
+        let (referenced_table, next) = table_name(tokens, references + 1).ok_or("참조 테이블을 읽을 수 없음")?;
+        let referenced_columns = if tokens.get(next).is_some_and(|t| t.is_symbol("(")) {
+            column_list(&paren_group(tokens, next).0)
+        } else {
+            Vec::new()
+        };
+        foreign_keys.push(ForeignKey {
+            name: constraint_name.unwrap_or_else(|| format!("fk_{}_{}", entity.physical_name, columns.join("_"))),
+            table: entity.qualified_name(),
+            columns,
+            referenced_table,
+            referenced_columns,
+        });
+        Ok(())
+    } else if kind.is_kw("CHECK") {
+        let open = (pos..tokens.len()).find(|&i| tokens[i].is_symbol("(")).ok_or("CHECK 식이 없음")?;
+        let expression = render(&paren_group(tokens, open).0);
+        let name = constraint_name
+            .unwrap_or_else(|| format!("ck_{}_{}", entity.physical_name, entity.check_constraints.len() + 1));
+        entity.check_constraints.push(CheckConstraint { name, expression });
+        Ok(())
+    } else if kind.is_kw("KEY") || kind.is_kw("INDEX") {
+        // MySQL 테이블 안 인덱스: KEY [이름] [USING 방식] (컬럼, ...) [USING 방식]
+        let open = (pos..tokens.len()).find(|&i| tokens[i].is_symbol("(")).ok_or("인덱스 컬럼 목록이 없음")?;
+        let columns = index_columns(&paren_group(tokens, open).0);
+        if columns.is_empty() {
+            return Err("인덱스 컬럼 목록이 없음".to_string());
+        }
+        let name = tokens[pos + 1..open]
+            .first()
+            .filter(|t| !t.is_kw("USING"))
+            .and_then(|t| t.ident())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+                format!("idx_{}_{}", entity.physical_name, names.join("_"))
+            });
+        let method = match tokens.iter().position(|t| t.is_kw("USING")).and_then(|i| tokens.get(i + 1)) {
+            Some(t) if t.is_kw("HASH") => IndexMethod::Hash,
+            _ => IndexMethod::BTree,
+        };
+        entity.indexes.retain(|i| !i.name.eq_ignore_ascii_case(&name));
+        entity.indexes.push(Index { name, columns, is_unique: false, predicate: None, method });
+        Ok(())
+    } else {
+        Err(format!("지원하지 않는 테이블 제약조건을 건너뜀: {}", preview(tokens)))
+    }
+}
+
+fn find_attribute<'a>(entity: &'a mut Entity, column: &str) -> Result<&'a mut Attribute, String> {
+    entity
+        .attributes
+        .iter_mut()
+        .find(|a| a.physical_name.eq_ignore_ascii_case(column))
+        .ok_or_else(|| format!("컬럼 '{}'을(를) 찾을 수 없음", column))
+}
+
+fn is_column_option(token: &Token) -> bool {
+    [
+        "NOT", "NULL", "PRIMARY", "UNIQUE", "DEFAULT", "REFERENCES", "CONSTRAINT", "CHECK", "AUTO_INCREMENT",
+        "AUTOINCREMENT", "IDENTITY", "GENERATED", "COMMENT", "COLLATE", "ON",
+    ]
+    .iter()
+    .any(|kw| token.is_kw(kw))
+}
+
+// ON { DELETE | UPDATE } { CASCADE | RESTRICT | SET NULL | SET DEFAULT | NO ACTION | <expr> }
+fn skip_referential_action(tokens: &[Token], mut pos: usize) -> usize {
+    pos += 1; // DELETE / UPDATE
+    match tokens.get(pos) {
+        Some(t) if t.is_kw("SET") || t.is_kw("NO") => pos + 2,
+        Some(_) => {
+            pos += 1;
+            if tokens.get(pos).is_some_and(|t| t.is_symbol("(")) {
+                pos = paren_group(tokens, pos).1;
+            }
+            pos
+        }
+        None => pos,
+    }
+}
+
+// 테이블 이름은 "스키마.테이블"까지 유지 (db.schema.table이면 뒤의 두 부분)
+fn table_name(tokens: &[Token], mut pos: usize) -> Option<(String, usize)> {
+    let mut parts = vec![tokens.get(pos)?.ident()?.to_string()];
+    pos += 1;
+    while tokens.get(pos).is_some_and(|t| t.is_symbol(".")) {
+        parts.push(tokens.get(pos + 1)?.ident()?.to_string());
+        pos += 2;
+    }
+    let name = parts.pop()?;
+    Some((sql::qualify(parts.pop().as_deref(), &name), pos))
+}
+
+// schema.name 형태면 마지막 부분만 사용
+fn qualified_name(tokens: &[Token], mut pos: usize) -> Option<(String, usize)> {
+    let mut name = tokens.get(pos)?.ident()?.to_string();
+    pos += 1;
+    while tokens.get(pos).is_some_and(|t| t.is_symbol(".")) {
+        name = tokens.get(pos + 1)?.ident()?.to_string();
+        pos += 2;
+    }
+    Some((name, pos))
+}
+
+// 각 항목의 첫 식별자만 사용 (정렬 방향, 접두 길이 등은 무시)
+fn column_list(tokens: &[Token]) -> Vec<String> {
+    split_commas(tokens)
+        .iter()
+        .filter_map(|item| item.first().and_then(|t| t.ident()).map(str::to_string))
+        .collect()
+}
+
+// tokens[open]이 '('일 때 내부 토큰과 닫는 괄호 다음 위치를 반환
+fn paren_group(tokens: &[Token], open: usize) -> (Vec<Token>, usize) {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+            if depth == 0 {
+                return (tokens[open + 1..i].to_vec(), i + 1);
+            }
+        }
+    }
+    (tokens[(open + 1).min(tokens.len())..].to_vec(), tokens.len())
+}
+
+fn split_commas(tokens: &[Token]) -> Vec<Vec<Token>> {
+    let mut items = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0;
+    for token in tokens {
+        if token.is_symbol("(") { depth += 1; }
+        if token.is_symbol(")") { depth -= 1; }
+        if depth == 0 && token.is_symbol(",") {
+            items.push(std::mem::take(&mut current));
+        } else {
+            current.push(token.clone());
+        }
+    }
+    if !current.is_empty() {
+        items.push(current);
+    }
+    items
+}
+
+fn starts_with(tokens: &[Token], keywords: &[&str]) -> bool {
+    tokens.len() >= keywords.len() && keywords.iter().zip(tokens).all(|(kw, t)| t.is_kw(kw))
+}
+
+fn split_statements(tokens: Vec<(Token, Range<usize>)>) -> Vec<Vec<(Token, Range<usize>)>> {
+    let mut statements = Vec::new();
+    let mut current = Vec::new();
+    for (token, span) in tokens {
+        if token.is_symbol(";") {
+            if !current.is_empty() {
+                statements.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push((token, span));
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+fn preview(tokens: &[Token]) -> String {
+    let text = render(&tokens[..tokens.len().min(8)]);
+    if tokens.len() > 8 { format!("{} ...", text) } else { text }
+}
+
+// 토큰을 SQL 텍스트로 되돌림 (DEFAULT 식, 경고 메시지용)
+fn render(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        let glue = match (previous, token) {
+            (None, _) => true,
+            (Some(p), _) if p.is_symbol("(") || p.is_symbol("[") || p.is_symbol("::") || p.is_symbol(".") => true,
+            (_, t) if t.is_symbol(")") || t.is_symbol("[") || t.is_symbol("]") || t.is_symbol(",") || t.is_symbol("::") || t.is_symbol(".") => true,
+            // 함수 호출은 붙이고 CHECK 식의 연산자 키워드는 띄움
+            (Some(Token::Word(w)), t) if t.is_symbol("(") => {
+                !["AND", "OR", "NOT", "IN", "IS", "EXISTS", "BETWEEN", "LIKE"].iter().any(|kw| w.eq_ignore_ascii_case(kw))
+            }
+            _ => false,
+        };
+        if !glue {
+            text.push(' ');
+        }
+        match token {
+            Token::Word(w) | Token::Symbol(w) => text.push_str(w),
+            Token::Quoted(q) => text.push_str(&format!("\"{}\"", q)),
+            Token::Str(s) => text.push_str(&format!("'{}'", s.replace('\'', "''"))),
+        }
+        previous = Some(token);
+    }
+    text
+}
+
+// 토큰과 원문에서의 위치(문자 단위)
+fn tokenize(script: &str) -> Vec<(Token, Range<usize>)> {
+    let chars: Vec<char> = script.chars().collect();
+    let mut tokens = Vec::new();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    // 닫는 문자까지 읽기 (같은 문자 두 번은 이스케이프)
+    let read_until = |i: &mut usize, close: char| -> String {
+        let mut value = String::new();
+        while *i < chars.len() {
+            if chars[*i] == close {
+                if chars.get(*i + 1) == Some(&close) {
+                    value.push(close);
+                    *i += 2;
+                    continue;
+                }
+                *i += 1;
+                break;
+            }
+            value.push(chars[*i]);
+            *i += 1;
+        }
+        value
+    };
+
+    while i < chars.len() {
+        let (token_start, count) = (i, tokens.len());
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if (c == '-' && chars.get(i + 1) == Some(&'-')) || c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '\'' {
+            i += 1;
+            tokens.push(Token::Str(read_until(&mut i, '\'')));
+        } else if c == '"' || c == '`' {
+            i += 1;
+            tokens.push(Token::Quoted(read_until(&mut i, c)));
+        } else if c == '[' && !is_array_bracket(&chars, i, tokens.last()) {
+            i += 1;
+            tokens.push(Token::Quoted(read_until(&mut i, ']')));
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$' || (chars[i] == '.' && chars[start].is_ascii_digit())) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // SQL Server 배치 구분자 GO는 한 줄에 혼자 있을 때만 (세미콜론 없이 끝난 배치도 나눔)
+            if word.eq_ignore_ascii_case("GO") && alone_on_line(&chars, start, i) {
+                tokens.push(Token::Symbol(";".to_string()));
+            } else {
+                tokens.push(Token::Word(word));
+            }
+        } else {
+            let pair: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if ["::", "<=", ">=", "<>", "!=", "||"].contains(&pair.as_str()) {
+                tokens.push(Token::Symbol(pair));
+                i += 2;
+            } else {
+                tokens.push(Token::Symbol(c.to_string()));
+                i += 1;
+            }
+        }
+        if tokens.len() > count {
+            spans.push(token_start..i);
+        }
+    }
+    tokens.into_iter().zip(spans).collect()
+}
+
+// 부분 인덱스 정의에서 WHERE 뒤의 조건 원문 (문자열 리터럴이나 괄호 안의 WHERE는 건너뜀)
+pub fn where_clause(sql: &str) -> Option<String> {
+    let mut depth = 0;
+    let start = tokenize(sql).into_iter().find_map(|(token, span)| {
+        if token.is_symbol("(") {
+            depth += 1;
+        } else if token.is_symbol(")") {
+            depth -= 1;
+        } else if depth == 0 && token.is_kw("WHERE") {
+            return Some(span.end);
+        }
+        None
+    })?;
+    Some(sql.chars().skip(start).collect::<String>().trim().trim_end_matches(';').trim_end().to_string())
+}
+
+// 단어 바로 뒤에 붙었거나 비어 있는 []는 SQL Server 인용 식별자가 아니라 PostgreSQL 배열 (TEXT[], INT[3])
+fn is_array_bracket(chars: &[char], i: usize, previous: Option<&Token>) -> bool {
+    let after_word = matches!(previous, Some(Token::Word(_))) && i > 0 && !chars[i - 1].is_whitespace();
+    let empty = chars[i + 1..].iter().find(|c| !c.is_whitespace()) == Some(&']');
+    after_word || empty
+}
+
+fn alone_on_line(chars: &[char], start: usize, end: usize) -> bool {
+    let before = chars[..start].iter().rev().take_while(|c| **c != '\n').all(|c| c.is_whitespace());
+    let after = chars[end..].iter().take_while(|c| **c != '\n').all(|c| c.is_whitespace());
+    before && after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::SqlDialect;
+
+    fn entity<'a>(result: &'a SqlImportResult, name: &str) -> &'a Entity {
+        result.diagram.entities.values().find(|e| e.physical_name == name).expect("테이블이 없음")
+    }
+
+    fn data_type(entity: &Entity, column: &str) -> String {
+        entity.attributes.iter().find(|a| a.physical_name == column).expect("컬럼이 없음").data_type.clone()
+    }
+
+    #[test]
+    fn go_separates_batches_without_semicolons() {
+        let result = parse_sql("CREATE TABLE a (id INT)\nGO\nCREATE TABLE b (id INT)\n  go  \nCREATE TABLE c (go_col INT)\n");
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.diagram.entities.len(), 3);
+        assert_eq!(data_type(entity(&result, "c"), "go_col"), "INT");
+    }
+
+    #[test]
+    fn go_inside_a_line_is_not_a_separator() {
+        let result = parse_sql("CREATE TABLE a (id INT, go INT);");
+        assert_eq!(entity(&result, "a").attributes.len(), 2);
+    }
+
+    #[test]
+    fn postgres_array_types_keep_brackets() {
+        let result = parse_sql("CREATE TABLE t (tags TEXT[], matrix INTEGER[3][], codes VARCHAR(10)[], spaced TEXT [])");
+        let table = entity(&result, "t");
+        assert_eq!(data_type(table, "tags"), "TEXT[]");
+        assert_eq!(data_type(table, "matrix"), "INTEGER[3][]");
+        assert_eq!(data_type(table, "codes"), "VARCHAR(10)[]");
+        assert_eq!(data_type(table, "spaced"), "TEXT[]");
+        assert!(sql::create_script(&result.diagram, SqlDialect::PostgreSql.dialect()).contains("\"tags\" TEXT[]"));
+    }
+
+    #[test]
+    fn sql_server_brackets_are_identifiers() {
+        let result = parse_sql("CREATE TABLE [dbo].[order items] ([item id] INT NOT NULL PRIMARY KEY, [name] NVARCHAR(50))\nGO\n");
+        let table = entity(&result, "order items");
+        assert_eq!(table.schema.as_deref(), Some("dbo"));
+        assert!(table.attributes.iter().any(|a| a.physical_name == "item id" && a.is_primary_key));
+        assert_eq!(data_type(table, "name"), "NVARCHAR");
+    }
+
+    #[test]
+    fn mysql_inline_keys_become_indexes() {
+        let result = parse_sql("CREATE TABLE t (id INT PRIMARY KEY, a INT, b INT, KEY idx_a (a), INDEX (a, b DESC), KEY k_b USING HASH (b));");
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        let indexes = &entity(&result, "t").indexes;
+        assert_eq!(indexes.len(), 3);
+        assert_eq!(indexes[0].name, "idx_a");
+        assert!(!indexes[0].is_unique);
+        assert_eq!(indexes[1].name, "idx_t_a_b");
+        assert_eq!(indexes[1].columns[1].order, SortOrder::Desc);
+        assert_eq!(indexes[2].method, IndexMethod::Hash);
+    }
+
+    // 가져온 다이어그램을 다시 내보내고 가져와도 같은 DDL이 나와야 함
+    #[test]
+    fn round_trip_per_dialect() {
+        let source = parse_sql(
+            "CREATE TABLE users (id INT NOT NULL PRIMARY KEY, email VARCHAR(100) NOT NULL UNIQUE, age INT CHECK (age >= 0), created_at TIMESTAMP);
+             CREATE TABLE posts (id INT NOT NULL, user_id INT NOT NULL, title VARCHAR(200), PRIMARY KEY (id),
+                 CONSTRAINT fk_posts_users FOREIGN KEY (user_id) REFERENCES users (id));
+             CREATE INDEX idx_posts_title ON posts (title);",
+        );
+        assert!(source.warnings.is_empty(), "{:?}", source.warnings);
+        assert_eq!(source.diagram.relations.len(), 1);
+
+        for dialect in [SqlDialect::PostgreSql, SqlDialect::MySql, SqlDialect::Sqlite, SqlDialect::SqlServer] {
+            let ddl = source.diagram.to_sql(dialect);
+            let imported = parse_sql(&ddl);
+            assert!(imported.warnings.is_empty(), "{:?}: {:?}", dialect, imported.warnings);
+            assert_eq!(imported.diagram.relations.len(), 1, "{:?}", dialect);
+            assert_eq!(imported.diagram.to_sql(dialect), ddl, "{:?}", dialect);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use std::fs;
+use std::path::PathBuf;
+
+// 테스트용 빈 임시 폴더 (이전 실행의 잔여물은 지움)
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("erd_editor_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
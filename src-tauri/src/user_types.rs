@@ -0,0 +1,194 @@
+use crate::erd::{Attribute, DomainType, EnumType, ErdDiagram, UserType};
+
+// 타입 추가/수정. old_name이 있으면 그 타입을 바꾸고, 이름이 바뀌면 참조하는 속성도 따라감
+// (도메인 정의는 DDL 생성 시 참조로 풀리므로 속성에 따로 복사하지 않음)
+pub fn save(diagram: &ErdDiagram, old_name: Option<&str>, mut user_type: UserType) -> Result<ErdDiagram, String> {
+    validate(&user_type)?;
+    let name = user_type.name().trim().to_string();
+    let duplicate = diagram.user_types.iter().any(|t| {
+        t.name().eq_ignore_ascii_case(&name) && !old_name.is_some_and(|old| t.name().eq_ignore_ascii_case(old))
+    });
+    if duplicate {
+        return Err(format!("타입 '{}'이(가) 이미 있습니다.", name));
+    }
+
+    // 속성은 다듬은 이름으로 참조하므로 저장하는 이름도 같게
+    match &mut user_type {
+        UserType::Enum(e) => e.name = name.clone(),
+        UserType::Domain(d) => d.name = name.clone(),
+    }
+
+    let mut diagram = diagram.clone();
+    let position = old_name.and_then(|old| diagram.user_types.iter().position(|t| t.name().eq_ignore_ascii_case(old)));
+    match position {
+        Some(position) => diagram.user_types[position] = user_type,
+        None => diagram.user_types.push(user_type),
+    }
+
+    for attr in diagram.entities.values_mut().flat_map(|e| e.attributes.iter_mut()) {
+        let data_type = attr.data_type.trim();
+        if data_type.eq_ignore_ascii_case(&name) || old_name.is_some_and(|old| data_type.eq_ignore_ascii_case(old)) {
+            attr.data_type = name.clone();
+            attr.length = None;
+        }
+    }
+    Ok(diagram)
+}
+
+// 타입을 삭제하고 참조하던 속성에는 정의를 풀어서 넣음
+pub fn remove(diagram: &ErdDiagram, name: &str) -> Result<ErdDiagram, String> {
+    let mut diagram = diagram.clone();
+    let position = diagram
+        .user_types
+        .iter()
+        .position(|t| t.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("타입 '{}'이(가) 없습니다.", name))?;
+    let user_type = diagram.user_types.remove(position);
+
+    for attr in diagram.entities.values_mut().flat_map(|e| e.attributes.iter_mut()) {
+        if attr.data_type.trim().eq_ignore_ascii_case(name) {
+            inline_attribute(attr, &user_type, None);
+        }
+    }
+    Ok(diagram)
+}
+
+// 사용자 정의 타입을 지원하지 않는 DBMS용으로 속성에 타입 정의를 펼침
+// native_enum: MySQL의 ENUM('a', 'b')처럼 열거형을 표현하는 고유 타입 (없으면 VARCHAR + CHECK)
+pub fn inline_attribute(attr: &mut Attribute, user_type: &UserType, native_enum: Option<String>) {
+    let column = attr.physical_name.clone();
+    match user_type {
+        UserType::Enum(e) => match native_enum {
+            Some(native) => {
+                attr.data_type = native;
+                attr.length = None;
+            }
+            None => {
+                let longest = e.values.iter().map(|v| v.chars().count()).max().unwrap_or(1).max(1);
+                attr.data_type = "VARCHAR".to_string();
+                attr.length = Some(longest.to_string());
+                let values: Vec<String> = e.values.iter().map(|v| format!("'{}'", v.replace('\'', "''"))).collect();
+                add_check(attr, format!("{} IN ({})", column, values.join(", ")));
+            }
+        },
+        UserType::Domain(d) => {
+            attr.data_type = d.base_type.clone();
+            attr.length = d.length.clone();
+            if attr.default_value.as_deref().map(str::trim).filter(|v| !v.is_empty()).is_none() {
+                attr.default_value = d.default_value.clone();
+            }
+            if let Some(check) = d.check.as_deref().filter(|c| !c.trim().is_empty()) {
+                add_check(attr, replace_value_keyword(check, &column));
+            }
+        }
+    }
+}
+
+fn validate(user_type: &UserType) -> Result<(), String> {
+    if user_type.name().trim().is_empty() {
+        return Err("타입 이름이 비어 있습니다.".to_string());
+    }
+    match user_type {
+        UserType::Enum(EnumType { name, values }) => {
+            if values.is_empty() {
+                return Err(format!("열거형 '{}'에 값이 없습니다.", name));
+            }
+            for (i, value) in values.iter().enumerate() {
+                if values[..i].contains(value) {
+                    return Err(format!("열거형 '{}'에 값 '{}'이(가) 중복됩니다.", name, value));
+                }
+            }
+        }
+        UserType::Domain(DomainType { name, base_type, .. }) => {
+            if base_type.trim().is_empty() {
+                return Err(format!("도메인 '{}'의 기반 타입이 비어 있습니다.", name));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 속성 CHECK가 이미 있으면 AND로 묶음
+fn add_check(attr: &mut Attribute, check: String) {
+    attr.check = match attr.check.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(existing) => Some(format!("({}) AND ({})", check, existing)),
+        None => Some(check),
+    };
+}
+
+// 도메인 CHECK의 VALUE를 컬럼 이름으로 바꿈 (문자열 리터럴 안은 그대로)
+pub fn replace_value_keyword(check: &str, column: &str) -> String {
+    let mut out = String::new();
+    let mut word = String::new();
+    let mut in_string = false;
+    for c in check.chars().chain(std::iter::once(' ')) {
+        if !in_string && (c.is_alphanumeric() || c == '_') {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            out.push_str(if word.eq_ignore_ascii_case("VALUE") { column } else { &word });
+            word.clear();
+        }
+        if c == '\'' {
+            in_string = !in_string;
+        }
+        out.push(c);
+    }
+    out.pop();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erd::Entity;
+
+    fn diagram_with_column(data_type: &str) -> ErdDiagram {
+        let mut diagram = ErdDiagram::default();
+        let mut entity = Entity::new("entity_orders", "orders");
+        entity.attributes.push(Attribute::new("state", data_type));
+        diagram.entities.insert(entity.id.clone(), entity);
+        diagram
+    }
+
+    fn status(name: &str, values: &[&str]) -> UserType {
+        UserType::Enum(EnumType { name: name.to_string(), values: values.iter().map(|v| v.to_string()).collect() })
+    }
+
+    fn state(diagram: &ErdDiagram) -> &Attribute {
+        &diagram.entities["entity_orders"].attributes[0]
+    }
+
+    #[test]
+    fn save_stores_the_trimmed_name() {
+        let diagram = save(&diagram_with_column("status"), None, status(" status ", &["A", "B"])).unwrap();
+        assert_eq!(diagram.user_types[0].name(), "status");
+        assert_eq!(state(&diagram).data_type, "status");
+        assert!(diagram.user_type(&state(&diagram).data_type).is_some());
+    }
+
+    #[test]
+    fn rename_follows_references() {
+        let diagram = save(&diagram_with_column("status"), None, status("status", &["A"])).unwrap();
+        let diagram = save(&diagram, Some("status"), status("order_status", &["A"])).unwrap();
+        assert_eq!(diagram.user_types.len(), 1);
+        assert_eq!(state(&diagram).data_type, "order_status");
+    }
+
+    #[test]
+    fn duplicate_name_is_rejected() {
+        let diagram = save(&diagram_with_column("INT"), None, status("status", &["A"])).unwrap();
+        assert!(save(&diagram, None, status("STATUS", &["B"])).is_err());
+    }
+
+    #[test]
+    fn remove_inlines_the_enum() {
+        let diagram = save(&diagram_with_column("status"), None, status("status", &["A", "BB"])).unwrap();
+        let diagram = remove(&diagram, "status").unwrap();
+        let attr = state(&diagram);
+        assert_eq!(attr.data_type, "VARCHAR");
+        assert_eq!(attr.length.as_deref(), Some("2"));
+        assert_eq!(attr.check.as_deref(), Some("state IN ('A', 'BB')"));
+    }
+}
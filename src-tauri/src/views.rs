@@ -0,0 +1,277 @@
+use crate::erd::{Attribute, Entity, ErdDiagram};
+use crate::sql;
+use crate::sql_import::{self, SelectColumn, ViewTable};
+use serde::{Deserialize, Serialize};
+
+// 정의만으로 타입을 알 수 없는 뷰 컬럼 (계산식 등)
+pub const UNKNOWN_TYPE: &str = "UNKNOWN";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewRefreshResult {
+    pub diagram: ErdDiagram,
+    pub warnings: Vec<String>,
+}
+
+// 뷰 정의에서 참조 테이블을 다시 읽고, derive_columns면 SELECT 목록으로 컬럼도 다시 만듦
+pub fn refresh(diagram: &ErdDiagram, entity_id: &str, derive_columns: bool) -> Result<ViewRefreshResult, String> {
+    let view = diagram
+        .entities
+        .get(entity_id)
+        .ok_or_else(|| format!("엔티티 '{}'이(가) 없습니다.", entity_id))?;
+    if !view.is_view() {
+        return Err(format!("'{}'은(는) 뷰가 아닙니다.", view.physical_name));
+    }
+    if view.view_query().is_none() {
+        return Err(format!("뷰 '{}'의 정의가 비어 있습니다.", view.physical_name));
+    }
+    let mut diagram = diagram.clone();
+    let warnings = refresh_entity(&mut diagram, entity_id, derive_columns, &[]);
+    Ok(ViewRefreshResult { diagram, warnings })
+}
+
+// 정의가 없는 뷰는 그대로 둠 (직접 선언한 컬럼과 참조 유지)
+// column_names는 CREATE VIEW v (a, b) 같은 컬럼 목록으로, SELECT 목록의 이름을 순서대로 대신함
+pub fn refresh_entity(diagram: &mut ErdDiagram, entity_id: &str, derive_columns: bool, column_names: &[String]) -> Vec<String> {
+    let Some(view) = diagram.entities.get(entity_id) else { return Vec::new() };
+    let Some(definition) = view.view_query() else { return Vec::new() };
+    let query = sql_import::parse_view_query(definition);
+    let mut warnings = Vec::new();
+
+    // FROM/JOIN 테이블 -> 엔티티 (스키마 없는 이름은 뷰와 같은 스키마를 먼저 찾음)
+    let mut sources: Vec<(&ViewTable, &Entity)> = Vec::new();
+    for table in &query.tables {
+        let same_schema = match (view.schema_name(), sql::split_table(&table.name)) {
+            (Some(schema), (None, name)) => diagram.entity_id_by_physical_name(&sql::qualify(Some(schema), name)),
+            _ => None,
+        };
+        match same_schema.or_else(|| diagram.entity_id_by_physical_name(&table.name)) {
+            Some(id) if id != view.id => sources.push((table, &diagram.entities[&id])),
+            Some(_) => warnings.push(format!("뷰 '{}'이(가) 자기 자신을 참조합니다", view.physical_name)),
+            None => warnings.push(format!("뷰 '{}': 테이블 '{}'을(를) 찾을 수 없음", view.physical_name, table.name)),
+        }
+    }
+    let mut dependencies: Vec<String> = Vec::new();
+    for (_, entity) in &sources {
+        if !dependencies.contains(&entity.id) {
+            dependencies.push(entity.id.clone());
+        }
+    }
+
+    let attributes = derive_columns.then(|| {
+        // 별칭이나 테이블 이름으로 찾음
+        let source = |qualifier: &str| {
+            sources
+                .iter()
+                .find(|(t, _)| {
+                    t.alias.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(qualifier))
+                        || sql::split_table(&t.name).1.eq_ignore_ascii_case(qualifier)
+                })
+                .map(|(_, e)| *e)
+        };
+        let mut attributes: Vec<Attribute> = Vec::new();
+        let mut unnamed: Vec<(usize, &str)> = Vec::new();
+        for column in &query.columns {
+            match column {
+                SelectColumn::All(None) => {
+                    for (_, entity) in &sources {
+                        attributes.extend(entity.attributes.iter().map(view_column));
+                    }
+                }
+                SelectColumn::All(Some(qualifier)) => match source(qualifier) {
+                    Some(entity) => attributes.extend(entity.attributes.iter().map(view_column)),
+                    None => warnings.push(format!("뷰 '{}': '{}.*'의 테이블을 찾을 수 없음", view.physical_name, qualifier)),
+                },
+                SelectColumn::Column { name, table, column } => {
+                    let original = column.as_deref().and_then(|column| {
+                        let candidates: Vec<&Entity> = match table {
+                            Some(qualifier) => source(qualifier).into_iter().collect(),
+                            None => sources.iter().map(|(_, e)| *e).collect(),
+                        };
+                        candidates
+                            .into_iter()
+                            .find_map(|e| e.attributes.iter().find(|a| a.physical_name.eq_ignore_ascii_case(column)))
+                    });
+                    let mut attr = match original {
+                        Some(original) => view_column(original),
+                        None => Attribute::new(name, UNKNOWN_TYPE),
+                    };
+                    // 별칭으로 이름을 바꿨으면 논리명도 별칭을 따름
+                    if !matches!(original, Some(o) if o.physical_name.eq_ignore_ascii_case(name)) {
+                        attr.logical_name = name.clone();
+                    }
+                    attr.physical_name = name.clone();
+                    attributes.push(attr);
+                }
+                SelectColumn::Unnamed(expression) => {
+                    unnamed.push((attributes.len(), expression));
+                    attributes.push(Attribute::new(expression, UNKNOWN_TYPE));
+                }
+            }
+        }
+
+        if column_names.is_empty() || column_names.len() != attributes.len() {
+            if !column_names.is_empty() {
+                warnings.push(format!(
+                    "뷰 '{}': 컬럼 목록({}개)과 SELECT 결과({}개)의 개수가 달라 타입을 알 수 없음",
+                    view.physical_name,
+                    column_names.len(),
+                    attributes.len()
+                ));
+                attributes = column_names.iter().map(|name| Attribute::new(name, UNKNOWN_TYPE)).collect();
+            } else {
+                for (_, expression) in &unnamed {
+                    warnings.push(format!("뷰 '{}': 별칭 없는 식 '{}'은(는) 컬럼으로 만들지 않음", view.physical_name, expression));
+                }
+                for (position, _) in unnamed.iter().rev() {
+                    attributes.remove(*position);
+                }
+            }
+        } else {
+            for (attr, name) in attributes.iter_mut().zip(column_names) {
+                attr.logical_name = name.clone();
+                attr.physical_name = name.clone();
+            }
+        }
+
+        // 직접 적어 둔 id/논리명/비고/타입은 같은 이름의 컬럼에 유지
+        for attr in attributes.iter_mut() {
+            if let Some(declared) = view.attributes.iter().find(|a| a.physical_name.eq_ignore_ascii_case(&attr.physical_name)) {
+                attr.id = declared.id.clone();
+                attr.logical_name = declared.logical_name.clone();
+                attr.remark = declared.remark.clone();
+                if attr.data_type == UNKNOWN_TYPE {
+                    attr.data_type = declared.data_type.clone();
+                    attr.length = declared.length.clone();
+                }
+            }
+        }
+        attributes
+    });
+
+    let view = diagram.entities.get_mut(entity_id).unwrap();
+    view.view_dependencies = dependencies;
+    if let Some(attributes) = attributes {
+        view.attributes = attributes;
+    }
+    warnings
+}
+
+// 뷰를 의존 순서대로 (참조하는 뷰가 먼저). 순환하면 남은 뷰를 이름순으로 뒤에 붙임
+pub fn ordered_views(diagram: &ErdDiagram) -> Vec<&Entity> {
+    let mut pending: Vec<&Entity> = diagram.entities.values().filter(|e| e.is_view()).collect();
+    pending.sort_by_key(|e| e.qualified_name().to_lowercase());
+    let mut ordered: Vec<&Entity> = Vec::new();
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|view| {
+            view.view_dependencies
+                .iter()
+                .all(|id| id == &view.id || !pending.iter().any(|p| &p.id == id))
+        });
+        match ready {
+            Some(position) => ordered.push(pending.remove(position)),
+            None => ordered.append(&mut pending),
+        }
+    }
+    ordered
+}
+
+// 원본 컬럼의 타입만 가져오고 키/제약조건은 뷰에 의미가 없으므로 뺌
+fn view_column(original: &Attribute) -> Attribute {
+    let mut attr = Attribute::new(&original.physical_name, &original.data_type);
+    attr.logical_name = original.logical_name.clone();
+    attr.length = original.length.clone();
+    attr.is_nullable = original.is_nullable;
+    attr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_import::parse_sql;
+
+    const TABLES: &str = "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL);
+        CREATE TABLE posts (id BIGINT PRIMARY KEY, user_id INT, title TEXT);";
+
+    fn columns(diagram: &ErdDiagram, entity_id: &str) -> Vec<(String, String)> {
+        diagram.entities[entity_id].attributes.iter().map(|a| (a.physical_name.clone(), a.data_type.clone())).collect()
+    }
+
+    fn pairs(columns: &[(&str, &str)]) -> Vec<(String, String)> {
+        columns.iter().map(|(n, t)| (n.to_string(), t.to_string())).collect()
+    }
+
+    #[test]
+    fn columns_from_star_qualified_star_and_aliases() {
+        let result = parse_sql(&format!(
+            "{}
+             CREATE VIEW all_users AS SELECT * FROM users;
+             CREATE VIEW post_view AS SELECT p.*, u.name AS author, LENGTH(p.title) AS title_length
+                 FROM posts p JOIN users u ON u.id = p.user_id;",
+            TABLES
+        ));
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        let diagram = &result.diagram;
+        assert_eq!(columns(diagram, "entity_all_users"), pairs(&[("id", "INT"), ("name", "VARCHAR")]));
+        // 뷰 컬럼에는 키/제약조건을 가져오지 않음
+        assert!(!diagram.entities["entity_all_users"].attributes[0].is_primary_key);
+        assert!(!diagram.entities["entity_all_users"].attributes[1].is_nullable);
+
+        assert_eq!(
+            columns(diagram, "entity_post_view"),
+            pairs(&[("id", "BIGINT"), ("user_id", "INT"), ("title", "TEXT"), ("author", "VARCHAR"), ("title_length", UNKNOWN_TYPE)])
+        );
+        let author = &diagram.entities["entity_post_view"].attributes[3];
+        assert_eq!((author.logical_name.as_str(), author.length.as_deref()), ("author", Some("20")));
+        assert_eq!(diagram.entities["entity_post_view"].view_dependencies, ["entity_posts", "entity_users"]);
+    }
+
+    // 스키마 없이 쓴 테이블은 뷰와 같은 스키마에서 먼저 찾음
+    #[test]
+    fn dependencies_resolve_within_the_view_schema() {
+        let diagram = parse_sql(
+            "CREATE TABLE users (id INT PRIMARY KEY);
+             CREATE TABLE sales.users (id INT PRIMARY KEY, region TEXT);
+             CREATE VIEW sales.v AS SELECT * FROM users;
+             CREATE VIEW v AS SELECT * FROM users;",
+        )
+        .diagram;
+        assert_eq!(diagram.entities["entity_sales.v"].view_dependencies, ["entity_sales.users"]);
+        assert_eq!(diagram.entities["entity_sales.v"].attributes.len(), 2);
+        assert_eq!(diagram.entities["entity_v"].view_dependencies, ["entity_users"]);
+    }
+
+    #[test]
+    fn unknown_tables_are_reported() {
+        let diagram = parse_sql(&format!(
+            "{}
+             CREATE VIEW v AS SELECT u.*, m.* FROM users u JOIN missing m ON m.user_id = u.id;",
+            TABLES
+        ))
+        .diagram;
+        let result = refresh(&diagram, "entity_v", true).unwrap();
+        assert_eq!(
+            result.warnings,
+            ["뷰 'v': 테이블 'missing'을(를) 찾을 수 없음", "뷰 'v': 'm.*'의 테이블을 찾을 수 없음"]
+        );
+        assert_eq!(result.diagram.entities["entity_v"].view_dependencies, ["entity_users"]);
+        assert!(refresh(&diagram, "entity_users", true).is_err());
+    }
+
+    // 참조하는 뷰가 먼저 오고, 순환하면 남은 뷰를 이름순으로
+    #[test]
+    fn ordered_views_follow_dependencies() {
+        let mut diagram = parse_sql(&format!(
+            "{}
+             CREATE VIEW z_base AS SELECT id, name FROM users;
+             CREATE VIEW m_mid AS SELECT id FROM z_base;
+             CREATE VIEW a_top AS SELECT id FROM m_mid;",
+            TABLES
+        ))
+        .diagram;
+        let names = |diagram: &ErdDiagram| -> Vec<String> { ordered_views(diagram).iter().map(|v| v.physical_name.clone()).collect() };
+        assert_eq!(names(&diagram), ["z_base", "m_mid", "a_top"]);
+
+        diagram.entities.get_mut("entity_z_base").unwrap().view_dependencies = vec!["entity_a_top".to_string()];
+        assert_eq!(names(&diagram), ["a_top", "m_mid", "z_base"]);
+    }
+}
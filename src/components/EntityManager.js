@@ -95,6 +95,8 @@ export default class EntityManager {
         
         const attributeDiv = document.createElement('div');
         attributeDiv.className = 'attribute-field';
+        // 관계가 참조하는 속성 id (이름을 바꿔도 유지, 새 속성은 백엔드에서 채움)
+        attributeDiv.dataset.attributeId = attribute && attribute.id ? attribute.id : '';
         // 스타일은 모두 CSS에서 처리
         
         attributeDiv.innerHTML = `
@@ -274,6 +276,7 @@ export default class EntityManager {
             
             if (logicalName.trim() && physicalName.trim()) {
                 attributes.push({
+                    id: field.dataset.attributeId || '',
                     logical_name: logicalName.trim(),
                     physical_name: physicalName.trim(),
                     name: physicalName.trim(), // backward compatibility
@@ -286,7 +286,7 @@ class ERDEditor {
         this.fileHandler.markAsSaved();
         this.updateEntityList();
         this.render();
-        this.createAutoBackup();
+        invoke('discard_recovery_session', { key: 'untitled' }).catch(() => {});
     }
     
     async openDiagram() {
@@ -319,7 +319,14 @@ class ERDEditor {
             console.log('로드된 엔티티 수:', Object.keys(this.diagram.entities).length);
             console.log('로드된 관계 수:', this.diagram.relations.length);
             
-            alert('파일을 성공적으로 불러왔습니다!');
+            const notes = [];
+            if (result.migrations && result.migrations.length > 0) {
+                notes.push('이전 형식의 파일을 현재 형식으로 변환했습니다:\n' + result.migrations.join('\n'));
+            }
+            if (result.warnings && result.warnings.length > 0) {
+                notes.push('연결할 수 없는 관계 속성이 있습니다:\n' + result.warnings.join('\n'));
+            }
+            alert(notes.length > 0 ? '파일을 불러왔습니다.\n\n' + notes.join('\n\n') : '파일을 성공적으로 불러왔습니다!');
         } catch (error) {
             console.error('파일 열기 에러:', error);
             if (error !== 'Open cancelled' && !String(error).includes('cancelled')) {
@@ -337,7 +344,7 @@ class ERDEditor {
                 console.log('기존 파일에 저장:', this.currentFilePath);
                 filePath = await invoke('save_diagram_to_path', {
                     diagram: this.diagram,
-                    file_path: this.currentFilePath
+                    filePath: this.currentFilePath
                 });
                 this.showSuccessMessage('저장 완료', `파일이 저장되었습니다: ${filePath}`);
             } else {
@@ -355,6 +362,10 @@ class ERDEditor {
     
     async saveAsDialog() {
         const filePath = await invoke('save_diagram_to_file', { diagram: this.diagram });
+        if (!this.currentFilePath) {
+            // 저장하지 않은 다이어그램의 자동 저장본은 이제 필요 없음
+            invoke('discard_recovery_session', { key: 'untitled' }).catch(() => {});
+        }
         this.currentFilePath = filePath; // 새로 선택한 경로 저장
         this.showSuccessMessage('저장 완료', `파일이 저장되었습니다: ${filePath}`);
         return filePath;
@@ -608,16 +619,16 @@ class ERDEditor {
         }
     }
     
-    // 자동 백업 생성
-    createAutoBackup() {
+    // 자동 백업 생성 (열린 파일 경로별 복구 슬롯에 백엔드가 저장)
+    async createAutoBackup() {
+        if (!this.fileHandler.getModifiedStatus()) {
+            return;
+        }
         try {
-            const backupData = {
+            await invoke('autosave_diagram', {
                 diagram: this.diagram,
-                timestamp: new Date().toISOString(),
-                version: '1.0'
-            };
-            
-            localStorage.setItem('erd-editor-auto-backup', JSON.stringify(backupData));
+                filePath: this.currentFilePath
+            });
             this.lastBackupTime = new Date();
             console.log('자동 백업 완료:', this.lastBackupTime.toLocaleString());
         } catch (error) {
@@ -625,28 +636,30 @@ class ERDEditor {
         }
     }
     
-    // 자동 백업 복구
-    loadAutoBackup() {
+    // 자동 백업 복구 (디스크 파일보다 새로운 복구 슬롯만, 최근 것부터)
+    async loadAutoBackup() {
         try {
-            const backupData = localStorage.getItem('erd-editor-auto-backup');
-            if (backupData) {
-                const parsed = JSON.parse(backupData);
-                const backupTime = new Date(parsed.timestamp);
-                
-                // 백업이 24시간 이내인지 확인
-                const now = new Date();
-                const hoursDiff = (now - backupTime) / (1000 * 60 * 60);
-                
-                if (hoursDiff < 24 && parsed.diagram) {
-                    if (confirm(`자동 저장된 데이터가 있습니다 (${backupTime.toLocaleString()}). 복구하시겠습니까?`)) {
-                        this.diagram = parsed.diagram;
-                        this.entityManager.setEntities(this.diagram.entities);
-                        this.relationManager.setRelations(this.diagram.relations);
-                        this.updateEntityList();
-                        this.render();
-                        this.showSuccessMessage('복구 완료', '자동 백업에서 다이어그램을 복구했습니다.');
-                    }
+            const sessions = await invoke('list_recovery_sessions');
+            for (const session of sessions) {
+                const backupTime = new Date(session.saved_at).toLocaleString();
+                const target = session.file_path || '저장하지 않은 다이어그램';
+                if (!confirm(`저장하지 않은 변경사항이 있습니다.\n${target} (${backupTime}, 엔티티 ${session.entity_count}개)\n\n복구하시겠습니까?`)) {
+                    await invoke('discard_recovery_session', { key: session.key });
+                    continue;
                 }
+                
+                const result = await invoke('restore_recovery_session', { key: session.key });
+                this.diagram = result.diagram;
+                this.currentFilePath = result.file_path || null;
+                this.canvas.setEntities(this.diagram.entities);
+                this.canvas.setRelations(this.diagram.relations);
+                this.entityManager.setEntities(this.diagram.entities);
+                this.relationManager.setRelations(this.diagram.relations);
+                this.fileHandler.markAsModified(); // 파일에는 아직 저장되지 않음
+                this.updateEntityList();
+                this.render();
+                this.showSuccessMessage('복구 완료', '자동 백업에서 다이어그램을 복구했습니다.');
+                break;
             }
         } catch (error) {
             console.error('자동 백업 로드 실패:', error);